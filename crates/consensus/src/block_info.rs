@@ -0,0 +1,211 @@
+//! Block reference types.
+
+use crate::{L1InfoError, L1InfoOrigin, OpTransaction};
+use alloy_consensus::{Block, Header};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::B256;
+
+/// A reference to a block, with the subset of its header fields needed to track the chain.
+///
+/// This matches the `BlockRef` and `L1BlockRef` types of op-node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BlockInfo {
+    /// The block hash.
+    pub hash: B256,
+    /// The block number.
+    pub number: u64,
+    /// The parent block hash.
+    pub parent_hash: B256,
+    /// The block timestamp.
+    pub timestamp: u64,
+}
+
+impl BlockInfo {
+    /// Creates a new [`BlockInfo`].
+    pub const fn new(hash: B256, number: u64, parent_hash: B256, timestamp: u64) -> Self {
+        Self { hash, number, parent_hash, timestamp }
+    }
+
+    /// Returns the block number and hash.
+    pub const fn id(&self) -> BlockNumHash {
+        BlockNumHash { number: self.number, hash: self.hash }
+    }
+
+    /// Returns the parent block number and hash.
+    pub const fn parent_id(&self) -> BlockNumHash {
+        BlockNumHash { number: self.number.saturating_sub(1), hash: self.parent_hash }
+    }
+
+    /// Returns true if this block is the parent of the given block.
+    pub fn is_parent_of(&self, block: &Self) -> bool {
+        self.number + 1 == block.number && self.hash == block.parent_hash
+    }
+
+    /// Creates a new [`BlockInfo`] from the given block hash and header.
+    pub const fn from_header(hash: B256, header: &Header) -> Self {
+        Self::new(hash, header.number, header.parent_hash, header.timestamp)
+    }
+}
+
+impl From<&Header> for BlockInfo {
+    /// Creates a new [`BlockInfo`] from the header, computing the block hash.
+    fn from(header: &Header) -> Self {
+        Self::from_header(header.hash_slow(), header)
+    }
+}
+
+#[cfg(feature = "alloy-compat")]
+impl From<&alloy_rpc_types_eth::Header> for BlockInfo {
+    fn from(header: &alloy_rpc_types_eth::Header) -> Self {
+        Self::from_header(header.hash, &header.inner)
+    }
+}
+
+/// A reference to an L2 block, with its L1 origin and sequence number.
+///
+/// This matches the `L2BlockRef` type of op-node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct L2BlockInfo {
+    /// The block reference.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub block_info: BlockInfo,
+    /// The L1 origin of the block.
+    #[cfg_attr(feature = "serde", serde(rename = "l1origin"))]
+    pub l1_origin: BlockNumHash,
+    /// The distance to the first block of the epoch, i.e. of the L1 origin.
+    #[cfg_attr(feature = "serde", serde(rename = "sequenceNumber"))]
+    pub seq_num: u64,
+}
+
+impl L2BlockInfo {
+    /// Creates a new [`L2BlockInfo`].
+    pub const fn new(block_info: BlockInfo, l1_origin: BlockNumHash, seq_num: u64) -> Self {
+        Self { block_info, l1_origin, seq_num }
+    }
+
+    /// Creates a new [`L2BlockInfo`] from the block reference and the first transaction of the
+    /// block, the L1 info deposit.
+    ///
+    /// The genesis block has no L1 info deposit, its L1 origin is set in the rollup config.
+    pub fn from_l1_info_deposit<T: OpTransaction>(
+        block_info: BlockInfo,
+        first_tx: Option<&T>,
+    ) -> Result<Self, L2BlockInfoError> {
+        let deposit = first_tx
+            .and_then(OpTransaction::as_deposit)
+            .ok_or(L2BlockInfoError::MissingL1InfoDeposit)?;
+        let L1InfoOrigin { l1_origin, seq_num } = L1InfoOrigin::decode(&deposit.input)?;
+        Ok(Self::new(block_info, l1_origin, seq_num))
+    }
+
+    /// Creates a new [`L2BlockInfo`] from a block, e.g. an [`OpBlock`](crate::OpBlock), decoding
+    /// the L1 origin from its L1 info deposit.
+    ///
+    /// The block hash is computed from the header.
+    pub fn from_block<T: OpTransaction>(block: &Block<T>) -> Result<Self, L2BlockInfoError> {
+        Self::from_l1_info_deposit(BlockInfo::from(&block.header), block.body.transactions.first())
+    }
+
+    /// Creates a new [`L2BlockInfo`] from an RPC block with full transactions, decoding the L1
+    /// origin from its L1 info deposit.
+    #[cfg(feature = "alloy-compat")]
+    pub fn from_rpc_block<T: OpTransaction>(
+        block: &alloy_rpc_types_eth::Block<T>,
+    ) -> Result<Self, L2BlockInfoError> {
+        let transactions =
+            block.transactions.as_transactions().ok_or(L2BlockInfoError::MissingTransactions)?;
+        Self::from_l1_info_deposit(BlockInfo::from(&block.header), transactions.first())
+    }
+}
+
+/// An error creating an [`L2BlockInfo`] from a block.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum L2BlockInfoError {
+    /// The first transaction of the block is not a deposit.
+    #[error("Missing L1 info deposit")]
+    MissingL1InfoDeposit,
+    /// The RPC block does not contain full transactions.
+    #[error("Missing block transactions")]
+    MissingTransactions,
+    /// The L1 info deposit is invalid.
+    #[error(transparent)]
+    L1Info(#[from] L1InfoError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OpBlock, OpTxEnvelope, TxDeposit};
+    use alloc::vec;
+    use alloy_consensus::{BlockBody, Sealable};
+    use alloy_primitives::{Bytes, hex};
+
+    #[test]
+    fn test_parent_of() {
+        let parent = BlockInfo::new(B256::repeat_byte(1), 10, B256::ZERO, 100);
+        let child = BlockInfo::new(B256::repeat_byte(2), 11, parent.hash, 102);
+
+        assert!(parent.is_parent_of(&child));
+        assert!(!child.is_parent_of(&parent));
+        assert_eq!(child.parent_id(), parent.id());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_block_info() {
+        let json = r#"{"hash":"0x0101010101010101010101010101010101010101010101010101010101010101","number":10,"parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","timestamp":100}"#;
+        let block: BlockInfo = serde_json::from_str(json).unwrap();
+
+        assert_eq!(block, BlockInfo::new(B256::repeat_byte(1), 10, B256::ZERO, 100));
+        assert_eq!(serde_json::to_string(&block).unwrap(), json);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_l2_block_info() {
+        let json = r#"{"hash":"0x0101010101010101010101010101010101010101010101010101010101010101","number":10,"parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","timestamp":100,"l1origin":{"hash":"0x0202020202020202020202020202020202020202020202020202020202020202","number":5},"sequenceNumber":3}"#;
+        let block: L2BlockInfo = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            block,
+            L2BlockInfo::new(
+                BlockInfo::new(B256::repeat_byte(1), 10, B256::ZERO, 100),
+                BlockNumHash::new(5, B256::repeat_byte(2)),
+                3
+            )
+        );
+        assert_eq!(
+            serde_json::to_value(block).unwrap(),
+            serde_json::from_str::<serde_json::Value>(json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_l2_block_info_from_block() {
+        let deposit = TxDeposit {
+            input: Bytes::from_static(&hex!(
+                "440a5e20000008dd00101c1200000000000000040000000066c352bb000000000139c4f500000000000000000000000000000000000000000000000000000000c0cff1460000000000000000000000000000000000000000000000000000000000000001d4c88f4065ac9671e8b1329b90773e89b5ddff9cf8675b2b5e9c1b28320609930000000000000000000000005050f69a9786f081509234f1a7f4684b5e5b76c9"
+            )),
+            ..Default::default()
+        };
+        let mut block = OpBlock {
+            header: Header { number: 10, timestamp: 100, ..Default::default() },
+            body: BlockBody {
+                transactions: vec![OpTxEnvelope::Deposit(deposit.seal_slow())],
+                ..Default::default()
+            },
+        };
+        let info = L2BlockInfo::from_block(&block).unwrap();
+
+        assert_eq!(info.block_info, BlockInfo::from(&block.header));
+        assert_eq!(info.block_info.hash, block.header.hash_slow());
+        assert_eq!(info.l1_origin.number, 0x139c4f5);
+        assert_eq!(info.seq_num, 4);
+
+        block.body.transactions.clear();
+        assert_eq!(L2BlockInfo::from_block(&block), Err(L2BlockInfoError::MissingL1InfoDeposit));
+    }
+}
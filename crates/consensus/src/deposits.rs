@@ -0,0 +1,278 @@
+//! Decoding of user deposits from the `TransactionDeposited` events of the deposit contract.
+//!
+//! Spec: <https://specs.optimism.io/protocol/deposits.html#deposit-contract>
+
+use crate::{TxDeposit, UserDepositSource};
+use alloc::vec::Vec;
+use alloy_consensus::TxReceipt;
+use alloy_primitives::{Address, B256, Bytes, Log, TxKind, U256, b256};
+
+/// The topic of the `TransactionDeposited(address,address,uint256,bytes)` event.
+pub const DEPOSIT_EVENT_ABI_HASH: B256 =
+    b256!("0xb3813568d9991fc951961fcb4c784893574240a28925604d09fc577c55bb7c32");
+
+/// The only supported version of the deposit event.
+pub const DEPOSIT_EVENT_VERSION_0: B256 = B256::ZERO;
+
+/// The minimum length of the version 0 opaque data: `mint`, `value`, `ethValue`, `ethTxValue`,
+/// `gasLimit` and `isCreation`.
+const OPAQUE_DATA_V0_MIN_LEN: usize = 32 * 4 + 8 + 1;
+
+/// An error decoding a deposit from a `TransactionDeposited` event.
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+pub enum DepositError {
+    /// The log was not emitted by the deposit contract.
+    #[error("Log not emitted by the deposit contract: {0}")]
+    UnexpectedAddress(Address),
+    /// The log does not have the expected number of topics.
+    #[error("Unexpected number of deposit event topics: {0}")]
+    UnexpectedTopicsLen(usize),
+    /// The first topic is not the deposit event topic.
+    #[error("Invalid deposit event selector: {0}")]
+    InvalidSelector(B256),
+    /// The version of the deposit event is not supported.
+    #[error("Unsupported deposit event version: {0}")]
+    InvalidVersion(B256),
+    /// The ABI encoding of the opaque data is malformed.
+    #[error("Malformed deposit event data")]
+    MalformedData,
+    /// The opaque data is too short.
+    #[error("Unexpected opaque data length: {0}")]
+    UnexpectedOpaqueDataLength(usize),
+    /// A value in the opaque data does not fit its target type.
+    #[error("Deposit value overflow")]
+    ValueOverflow,
+}
+
+/// Decodes the [`TxDeposit`] from a `TransactionDeposited` event emitted by the deposit contract.
+///
+/// The `log_index` is the index of the log in the L1 block, which together with the
+/// `l1_block_hash` makes up the [`UserDepositSource`] of the deposit.
+///
+/// The version 0 opaque data is tightly packed:
+/// `mint (32) ++ value (32) ++ ethValue (32) ++ ethTxValue (32) ++ gasLimit (8) ++ isCreation (1)
+/// ++ data`.
+pub fn decode_deposit_log(
+    deposit_contract: Address,
+    l1_block_hash: B256,
+    log_index: u64,
+    log: &Log,
+) -> Result<TxDeposit, DepositError> {
+    if log.address != deposit_contract {
+        return Err(DepositError::UnexpectedAddress(log.address));
+    }
+    let topics = log.topics();
+    if topics.len() != 4 {
+        return Err(DepositError::UnexpectedTopicsLen(topics.len()));
+    }
+    if topics[0] != DEPOSIT_EVENT_ABI_HASH {
+        return Err(DepositError::InvalidSelector(topics[0]));
+    }
+    if topics[3] != DEPOSIT_EVENT_VERSION_0 {
+        return Err(DepositError::InvalidVersion(topics[3]));
+    }
+
+    let from = Address::from_word(topics[1]);
+    let to = Address::from_word(topics[2]);
+    let opaque_data = abi_decode_bytes(&log.data.data)?;
+    if opaque_data.len() < OPAQUE_DATA_V0_MIN_LEN {
+        return Err(DepositError::UnexpectedOpaqueDataLength(opaque_data.len()));
+    }
+
+    let mint = read_u128(&opaque_data[..32])?;
+    let value = U256::from_be_slice(&opaque_data[32..64]);
+    let eth_value = read_u128(&opaque_data[64..96])?;
+    let eth_tx_value = read_u128(&opaque_data[96..128])?;
+    let gas_limit =
+        u64::from_be_bytes(opaque_data[128..136].try_into().expect("sufficient length"));
+    let is_creation = opaque_data[136] != 0;
+    let input = Bytes::copy_from_slice(&opaque_data[137..]);
+
+    Ok(TxDeposit {
+        source_hash: UserDepositSource::new(l1_block_hash, log_index).source_hash(),
+        from,
+        to: if is_creation { TxKind::Create } else { TxKind::Call(to) },
+        mint,
+        value,
+        gas_limit,
+        is_system_transaction: false,
+        eth_value,
+        input,
+        eth_tx_value: (eth_tx_value != 0).then_some(eth_tx_value),
+    })
+}
+
+/// Decodes all user deposits emitted by the deposit contract in the receipts of an L1 block.
+///
+/// Logs of failed receipts are skipped. The receipts must be the complete, ordered list of
+/// receipts of the block, since the log index of each deposit is derived from its position.
+pub fn decode_deposits_from_receipts<R>(
+    deposit_contract: Address,
+    l1_block_hash: B256,
+    receipts: &[R],
+) -> Result<Vec<TxDeposit>, DepositError>
+where
+    R: TxReceipt<Log = Log>,
+{
+    let mut deposits = Vec::new();
+    let mut log_index = 0u64;
+    for receipt in receipts {
+        let logs = receipt.logs();
+        if receipt.status() {
+            for (index, log) in logs.iter().enumerate() {
+                if log.address == deposit_contract
+                    && log.topics().first() == Some(&DEPOSIT_EVENT_ABI_HASH)
+                {
+                    deposits.push(decode_deposit_log(
+                        deposit_contract,
+                        l1_block_hash,
+                        log_index + index as u64,
+                        log,
+                    )?);
+                }
+            }
+        }
+        log_index += logs.len() as u64;
+    }
+    Ok(deposits)
+}
+
+/// Decodes the ABI encoding of a single `bytes` value.
+fn abi_decode_bytes(data: &[u8]) -> Result<&[u8], DepositError> {
+    if data.len() < 64 {
+        return Err(DepositError::MalformedData);
+    }
+    let offset: usize =
+        U256::from_be_slice(&data[..32]).try_into().map_err(|_| DepositError::MalformedData)?;
+    if offset != 32 {
+        return Err(DepositError::MalformedData);
+    }
+    let len: usize =
+        U256::from_be_slice(&data[32..64]).try_into().map_err(|_| DepositError::MalformedData)?;
+    let end = len.checked_add(64).ok_or(DepositError::MalformedData)?;
+    data.get(64..end).ok_or(DepositError::MalformedData)
+}
+
+fn read_u128(word: &[u8]) -> Result<u128, DepositError> {
+    U256::from_be_slice(word).try_into().map_err(|_| DepositError::ValueOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_consensus::{Eip658Value, Receipt};
+    use alloy_primitives::{address, bytes};
+
+    const DEPOSIT_CONTRACT: Address = address!("0xc92470d7ffa21473611ab6c6e2fcfb8637c8f330");
+
+    /// Encodes a deposit into a `TransactionDeposited` event, the inverse of
+    /// [`decode_deposit_log`].
+    fn encode_deposit_log(deposit_contract: Address, deposit: &TxDeposit) -> Log {
+        let mut opaque_data = Vec::new();
+        opaque_data.extend_from_slice(&U256::from(deposit.mint).to_be_bytes::<32>());
+        opaque_data.extend_from_slice(&deposit.value.to_be_bytes::<32>());
+        opaque_data.extend_from_slice(&U256::from(deposit.eth_value).to_be_bytes::<32>());
+        opaque_data.extend_from_slice(
+            &U256::from(deposit.eth_tx_value.unwrap_or_default()).to_be_bytes::<32>(),
+        );
+        opaque_data.extend_from_slice(&deposit.gas_limit.to_be_bytes());
+        opaque_data.push(deposit.to.is_create() as u8);
+        opaque_data.extend_from_slice(&deposit.input);
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&U256::from(32).to_be_bytes::<32>());
+        data.extend_from_slice(&U256::from(opaque_data.len()).to_be_bytes::<32>());
+        data.extend_from_slice(&opaque_data);
+        data.resize(data.len().next_multiple_of(32), 0);
+
+        Log::new_unchecked(
+            deposit_contract,
+            vec![
+                DEPOSIT_EVENT_ABI_HASH,
+                deposit.from.into_word(),
+                deposit.to.to().copied().unwrap_or_default().into_word(),
+                DEPOSIT_EVENT_VERSION_0,
+            ],
+            data.into(),
+        )
+    }
+
+    fn deposit(log_index: u64) -> TxDeposit {
+        TxDeposit {
+            source_hash: UserDepositSource::new(B256::repeat_byte(0xaa), log_index).source_hash(),
+            from: address!("0x1111111111111111111111111111111111111111"),
+            to: TxKind::Call(address!("0x2222222222222222222222222222222222222222")),
+            mint: 100,
+            value: U256::from(50),
+            gas_limit: 21_000,
+            is_system_transaction: false,
+            eth_value: 7,
+            input: bytes!("deadbeef"),
+            eth_tx_value: Some(3),
+        }
+    }
+
+    #[test]
+    fn test_decode_deposit_log_roundtrip() {
+        let expected = deposit(3);
+        let log = encode_deposit_log(DEPOSIT_CONTRACT, &expected);
+
+        let decoded =
+            decode_deposit_log(DEPOSIT_CONTRACT, B256::repeat_byte(0xaa), 3, &log).unwrap();
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn test_decode_deposit_log_creation() {
+        let expected = TxDeposit { to: TxKind::Create, eth_tx_value: None, ..deposit(0) };
+        let log = encode_deposit_log(DEPOSIT_CONTRACT, &expected);
+
+        let decoded =
+            decode_deposit_log(DEPOSIT_CONTRACT, B256::repeat_byte(0xaa), 0, &log).unwrap();
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn test_decode_deposit_log_invalid() {
+        let log = encode_deposit_log(DEPOSIT_CONTRACT, &deposit(0));
+
+        let err = decode_deposit_log(Address::ZERO, B256::ZERO, 0, &log).unwrap_err();
+        assert_eq!(err, DepositError::UnexpectedAddress(DEPOSIT_CONTRACT));
+
+        let mut topics = log.topics().to_vec();
+        topics[3] = B256::with_last_byte(1);
+        let versioned = Log::new_unchecked(log.address, topics, log.data.data.clone());
+        let err = decode_deposit_log(DEPOSIT_CONTRACT, B256::ZERO, 0, &versioned).unwrap_err();
+        assert_eq!(err, DepositError::InvalidVersion(B256::with_last_byte(1)));
+
+        let mut truncated = log;
+        truncated.data.data = truncated.data.data.slice(..96);
+        let err = decode_deposit_log(DEPOSIT_CONTRACT, B256::ZERO, 0, &truncated).unwrap_err();
+        assert_eq!(err, DepositError::MalformedData);
+    }
+
+    #[test]
+    fn test_decode_deposits_from_receipts() {
+        let other_log = Log::new_unchecked(Address::ZERO, vec![], Bytes::new());
+        let receipts = vec![
+            Receipt {
+                status: Eip658Value::Eip658(true),
+                cumulative_gas_used: 0,
+                logs: vec![other_log.clone(), encode_deposit_log(DEPOSIT_CONTRACT, &deposit(1))],
+            },
+            // Failed receipts don't emit logs.
+            Receipt { status: Eip658Value::Eip658(false), cumulative_gas_used: 0, logs: vec![] },
+            Receipt {
+                status: Eip658Value::Eip658(true),
+                cumulative_gas_used: 0,
+                logs: vec![encode_deposit_log(DEPOSIT_CONTRACT, &deposit(2)), other_log],
+            },
+        ];
+
+        let deposits =
+            decode_deposits_from_receipts(DEPOSIT_CONTRACT, B256::repeat_byte(0xaa), &receipts)
+                .unwrap();
+        assert_eq!(deposits, vec![deposit(1), deposit(2)]);
+    }
+}
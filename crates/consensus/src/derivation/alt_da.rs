@@ -0,0 +1,296 @@
+//! Alt-DA commitments, posted to L1 instead of frames when batch data is stored on an alternative
+//! DA layer.
+//!
+//! Batcher transaction data carrying a commitment is `version ++ commitment_type ++ commitment`.
+//! A keccak256 commitment is the hash of the batch data, a generic commitment is a DA layer byte
+//! followed by an opaque payload, like an EigenDA certificate.
+//!
+//! Spec: <https://specs.optimism.io/experimental/alt-da.html#input-commitment-submission>
+
+use super::{DERIVATION_VERSION_0, Frame, FrameError};
+use alloc::vec::Vec;
+use alloy_primitives::{B256, Bytes, keccak256};
+
+/// The version byte of batcher transaction data carrying an Alt-DA commitment.
+pub const DERIVATION_VERSION_1: u8 = 1;
+
+/// The DA layer byte of generic commitments to EigenDA certificates.
+pub const EIGENDA_DA_LAYER: u8 = 0;
+
+/// The type of an [`AltDaCommitment`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum CommitmentType {
+    /// The keccak256 hash of the batch data.
+    Keccak256 = 0,
+    /// A commitment of a DA layer, identified by its DA layer byte.
+    Generic = 1,
+}
+
+impl TryFrom<u8> for CommitmentType {
+    type Error = AltDaCommitmentError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Keccak256),
+            1 => Ok(Self::Generic),
+            _ => Err(AltDaCommitmentError::UnsupportedCommitmentType(value)),
+        }
+    }
+}
+
+/// A commitment to batch data stored on an Alt-DA layer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AltDaCommitment {
+    /// The keccak256 hash of the batch data.
+    Keccak256(B256),
+    /// A commitment of a DA layer.
+    Generic {
+        /// The DA layer byte, e.g. [`EIGENDA_DA_LAYER`].
+        da_layer: u8,
+        /// The commitment payload, opaque to the derivation pipeline.
+        payload: Bytes,
+    },
+}
+
+impl AltDaCommitment {
+    /// Creates the keccak256 commitment to the given batch data.
+    pub fn keccak256(data: &[u8]) -> Self {
+        Self::Keccak256(keccak256(data))
+    }
+
+    /// Creates a generic commitment to the given EigenDA certificate.
+    pub const fn eigenda(certificate: Bytes) -> Self {
+        Self::Generic { da_layer: EIGENDA_DA_LAYER, payload: certificate }
+    }
+
+    /// Returns the type of the commitment.
+    pub const fn commitment_type(&self) -> CommitmentType {
+        match self {
+            Self::Keccak256(_) => CommitmentType::Keccak256,
+            Self::Generic { .. } => CommitmentType::Generic,
+        }
+    }
+
+    /// Returns the DA layer byte of a generic commitment.
+    pub const fn da_layer(&self) -> Option<u8> {
+        match self {
+            Self::Keccak256(_) => None,
+            Self::Generic { da_layer, .. } => Some(*da_layer),
+        }
+    }
+
+    /// Returns the EigenDA certificate of a generic commitment on the EigenDA layer.
+    pub const fn eigenda_certificate(&self) -> Option<&Bytes> {
+        match self {
+            Self::Generic { da_layer: EIGENDA_DA_LAYER, payload } => Some(payload),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the commitment is a keccak256 commitment to the given batch data.
+    pub fn verify(&self, data: &[u8]) -> bool {
+        matches!(self, Self::Keccak256(hash) if *hash == keccak256(data))
+    }
+
+    /// Returns the length of the encoded commitment.
+    pub fn length(&self) -> usize {
+        1 + match self {
+            Self::Keccak256(_) => B256::len_bytes(),
+            Self::Generic { payload, .. } => 1 + payload.len(),
+        }
+    }
+
+    /// Encodes the commitment, appending `commitment_type ++ commitment` to `out`.
+    pub fn encode_to(&self, out: &mut Vec<u8>) {
+        out.push(self.commitment_type() as u8);
+        match self {
+            Self::Keccak256(hash) => out.extend_from_slice(hash.as_slice()),
+            Self::Generic { da_layer, payload } => {
+                out.push(*da_layer);
+                out.extend_from_slice(payload);
+            }
+        }
+    }
+
+    /// Returns the encoded commitment: `commitment_type ++ commitment`.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.length());
+        self.encode_to(&mut out);
+        out
+    }
+
+    /// Decodes a commitment: `commitment_type ++ commitment`.
+    pub fn decode(data: &[u8]) -> Result<Self, AltDaCommitmentError> {
+        let (&commitment_type, commitment) =
+            data.split_first().ok_or(AltDaCommitmentError::Empty)?;
+        match CommitmentType::try_from(commitment_type)? {
+            CommitmentType::Keccak256 => B256::try_from(commitment)
+                .map(Self::Keccak256)
+                .map_err(|_| AltDaCommitmentError::InvalidKeccak256Length(commitment.len())),
+            CommitmentType::Generic => {
+                let (&da_layer, payload) =
+                    commitment.split_first().ok_or(AltDaCommitmentError::MissingDaLayer)?;
+                Ok(Self::Generic { da_layer, payload: Bytes::copy_from_slice(payload) })
+            }
+        }
+    }
+
+    /// Returns the batcher transaction data carrying the commitment:
+    /// `version ++ commitment_type ++ commitment`.
+    pub fn to_tx_data(&self) -> Bytes {
+        let mut out = Vec::with_capacity(1 + self.length());
+        out.push(DERIVATION_VERSION_1);
+        self.encode_to(&mut out);
+        out.into()
+    }
+
+    /// Decodes the commitment from batcher transaction data:
+    /// `version ++ commitment_type ++ commitment`.
+    pub fn from_tx_data(data: &[u8]) -> Result<Self, AltDaCommitmentError> {
+        let (&version, commitment) = data.split_first().ok_or(AltDaCommitmentError::Empty)?;
+        if version != DERIVATION_VERSION_1 {
+            return Err(AltDaCommitmentError::UnsupportedVersion(version));
+        }
+        Self::decode(commitment)
+    }
+}
+
+/// The decoded data of a batcher transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BatcherTxData {
+    /// Frames posted directly to L1.
+    Frames(Vec<Frame>),
+    /// A commitment to batch data stored on an Alt-DA layer.
+    AltDa(AltDaCommitment),
+}
+
+impl BatcherTxData {
+    /// Decodes batcher transaction data, depending on its version byte.
+    pub fn decode(data: &[u8]) -> Result<Self, BatcherTxDataError> {
+        match data.first() {
+            None => Err(BatcherTxDataError::Empty),
+            Some(&DERIVATION_VERSION_0) => Ok(Self::Frames(Frame::parse_frames(data)?)),
+            Some(&DERIVATION_VERSION_1) => Ok(Self::AltDa(AltDaCommitment::from_tx_data(data)?)),
+            Some(&version) => Err(BatcherTxDataError::UnsupportedVersion(version)),
+        }
+    }
+}
+
+/// An error decoding an [`AltDaCommitment`].
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum AltDaCommitmentError {
+    /// The data is empty.
+    #[error("Empty Alt-DA commitment")]
+    Empty,
+    /// The batcher transaction data has a version other than [`DERIVATION_VERSION_1`].
+    #[error("Unsupported derivation version: {0}")]
+    UnsupportedVersion(u8),
+    /// The commitment type is unknown.
+    #[error("Unsupported commitment type: {0}")]
+    UnsupportedCommitmentType(u8),
+    /// A keccak256 commitment is not 32 bytes long.
+    #[error("Invalid keccak256 commitment length: {0}")]
+    InvalidKeccak256Length(usize),
+    /// A generic commitment has no DA layer byte.
+    #[error("Missing DA layer byte of generic commitment")]
+    MissingDaLayer,
+}
+
+/// An error decoding [`BatcherTxData`].
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum BatcherTxDataError {
+    /// The batcher transaction data is empty.
+    #[error("Empty batcher transaction data")]
+    Empty,
+    /// The batcher transaction data has an unknown version.
+    #[error("Unsupported derivation version: {0}")]
+    UnsupportedVersion(u8),
+    /// The frames are invalid.
+    #[error(transparent)]
+    Frame(#[from] FrameError),
+    /// The Alt-DA commitment is invalid.
+    #[error(transparent)]
+    AltDa(#[from] AltDaCommitmentError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn test_keccak256_commitment_roundtrip() {
+        let commitment = AltDaCommitment::keccak256(b"batch data");
+        let tx_data = commitment.to_tx_data();
+
+        assert_eq!(tx_data.len(), 34);
+        assert_eq!(tx_data[..2], [DERIVATION_VERSION_1, CommitmentType::Keccak256 as u8]);
+        assert_eq!(tx_data[2..], keccak256(b"batch data")[..]);
+        assert_eq!(AltDaCommitment::from_tx_data(&tx_data), Ok(commitment.clone()));
+        assert!(commitment.verify(b"batch data"));
+        assert!(!commitment.verify(b"other data"));
+        assert_eq!(commitment.da_layer(), None);
+    }
+
+    #[test]
+    fn test_eigenda_commitment_roundtrip() {
+        let tx_data = hex!("010100f901aabbcc");
+        let commitment = AltDaCommitment::from_tx_data(&tx_data).unwrap();
+
+        assert_eq!(commitment, AltDaCommitment::eigenda(Bytes::from_static(&hex!("f901aabbcc"))));
+        assert_eq!(commitment.commitment_type(), CommitmentType::Generic);
+        assert_eq!(commitment.da_layer(), Some(EIGENDA_DA_LAYER));
+        assert_eq!(commitment.eigenda_certificate().unwrap()[..], hex!("f901aabbcc"));
+        assert_eq!(commitment.to_tx_data()[..], tx_data);
+
+        let other = AltDaCommitment::Generic { da_layer: 0xff, payload: Bytes::new() };
+        assert_eq!(other.eigenda_certificate(), None);
+        assert_eq!(AltDaCommitment::decode(&other.encode()), Ok(other));
+    }
+
+    #[test]
+    fn test_decode_invalid_commitment() {
+        assert_eq!(AltDaCommitment::from_tx_data(&[]), Err(AltDaCommitmentError::Empty));
+        assert_eq!(
+            AltDaCommitment::from_tx_data(&[DERIVATION_VERSION_0, 0]),
+            Err(AltDaCommitmentError::UnsupportedVersion(0))
+        );
+        assert_eq!(AltDaCommitment::decode(&[]), Err(AltDaCommitmentError::Empty));
+        assert_eq!(
+            AltDaCommitment::decode(&[2]),
+            Err(AltDaCommitmentError::UnsupportedCommitmentType(2))
+        );
+        assert_eq!(
+            AltDaCommitment::decode(&[0, 1, 2]),
+            Err(AltDaCommitmentError::InvalidKeccak256Length(2))
+        );
+        assert_eq!(AltDaCommitment::decode(&[1]), Err(AltDaCommitmentError::MissingDaLayer));
+    }
+
+    #[test]
+    fn test_decode_batcher_tx_data() {
+        let frames = vec![Frame::new([1; 16], 0, Bytes::from_static(b"hello"), true)];
+        let commitment = AltDaCommitment::eigenda(Bytes::from_static(b"cert"));
+
+        assert_eq!(
+            BatcherTxData::decode(&Frame::encode_frames(&frames)),
+            Ok(BatcherTxData::Frames(frames))
+        );
+        assert_eq!(
+            BatcherTxData::decode(&commitment.to_tx_data()),
+            Ok(BatcherTxData::AltDa(commitment))
+        );
+        assert_eq!(BatcherTxData::decode(&[]), Err(BatcherTxDataError::Empty));
+        assert_eq!(BatcherTxData::decode(&[2]), Err(BatcherTxDataError::UnsupportedVersion(2)));
+        assert_eq!(
+            BatcherTxData::decode(&[DERIVATION_VERSION_0]),
+            Err(BatcherTxDataError::Frame(FrameError::NoFrames))
+        );
+        assert_eq!(
+            BatcherTxData::decode(&[DERIVATION_VERSION_1, 1]),
+            Err(BatcherTxDataError::AltDa(AltDaCommitmentError::MissingDaLayer))
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Primitive encodings of span batches: unsigned varints and bitlists.
+
+use super::SpanBatchError;
+use alloc::vec::Vec;
+
+/// The maximum length of an unsigned varint encoding a `u64`.
+const MAX_VARINT_LEN: usize = 10;
+
+/// Appends the unsigned LEB128 varint encoding of `value` to `out`, like Go's `binary.PutUvarint`.
+pub(crate) fn write_uvarint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Reads an unsigned LEB128 varint from the start of `buf`, like Go's `binary.ReadUvarint`.
+pub(crate) fn read_uvarint(buf: &mut &[u8]) -> Result<u64, SpanBatchError> {
+    let mut value = 0u64;
+    for i in 0..MAX_VARINT_LEN {
+        let (&byte, rest) = buf.split_first().ok_or(SpanBatchError::Truncated)?;
+        *buf = rest;
+        if byte < 0x80 {
+            if i == MAX_VARINT_LEN - 1 && byte > 1 {
+                return Err(SpanBatchError::InvalidVarint);
+            }
+            return Ok(value | (byte as u64) << (7 * i));
+        }
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+    }
+    Err(SpanBatchError::InvalidVarint)
+}
+
+/// Reads `len` bytes from the start of `buf`.
+pub(crate) const fn read_bytes<'a>(
+    buf: &mut &'a [u8],
+    len: usize,
+) -> Result<&'a [u8], SpanBatchError> {
+    if buf.len() < len {
+        return Err(SpanBatchError::Truncated);
+    }
+    let (bytes, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(bytes)
+}
+
+/// Appends the bitlist encoding of `bits` to `out`: a big-endian integer of `ceil(len / 8)` bytes,
+/// with bit `i` set if `bits[i]` is true.
+pub(crate) fn write_bitlist(out: &mut Vec<u8>, bits: &[bool]) {
+    let len = bits.len().div_ceil(8);
+    let start = out.len();
+    out.resize(start + len, 0);
+    for (i, _) in bits.iter().enumerate().filter(|(_, bit)| **bit) {
+        out[start + len - 1 - i / 8] |= 1 << (i % 8);
+    }
+}
+
+/// Reads a bitlist of `count` bits from the start of `buf`.
+///
+/// Returns an error if bits beyond `count` are set.
+pub(crate) fn read_bitlist(buf: &mut &[u8], count: usize) -> Result<Vec<bool>, SpanBatchError> {
+    let bytes = read_bytes(buf, count.div_ceil(8))?;
+    let bit = |i: usize| bytes[bytes.len() - 1 - i / 8] & (1 << (i % 8)) != 0;
+    if (count..bytes.len() * 8).any(bit) {
+        return Err(SpanBatchError::InvalidBitlist);
+    }
+    Ok((0..count).map(bit).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_uvarint_roundtrip() {
+        for value in [0, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let mut out = Vec::new();
+            write_uvarint(&mut out, value);
+            assert_eq!(read_uvarint(&mut out.as_slice()), Ok(value));
+        }
+
+        let mut out = Vec::new();
+        write_uvarint(&mut out, 300);
+        assert_eq!(out, vec![0xac, 0x02]);
+        assert_eq!(read_uvarint(&mut &out[..1]), Err(SpanBatchError::Truncated));
+        assert_eq!(read_uvarint(&mut [0xff; 10].as_slice()), Err(SpanBatchError::InvalidVarint));
+    }
+
+    #[test]
+    fn test_bitlist_roundtrip() {
+        let bits = vec![true, false, false, false, false, false, false, false, false, true];
+        let mut out = Vec::new();
+        write_bitlist(&mut out, &bits);
+
+        assert_eq!(out, vec![0x02, 0x01]);
+        assert_eq!(read_bitlist(&mut out.as_slice(), bits.len()), Ok(bits));
+        assert_eq!(
+            read_bitlist(&mut [0x04, 0x01].as_slice(), 10),
+            Err(SpanBatchError::InvalidBitlist)
+        );
+        assert_eq!(read_bitlist(&mut [].as_slice(), 0), Ok(vec![]));
+    }
+}
@@ -0,0 +1,219 @@
+//! Batch formats, the items of the batch stream of a channel.
+//!
+//! Each batch is encoded as `batch_type ++ batch_data`.
+//!
+//! Spec: <https://specs.optimism.io/protocol/derivation.html#batch-format>
+
+mod bits;
+
+mod singular;
+pub use singular::SingularBatch;
+
+mod span;
+pub use span::{MAX_SPAN_BATCH_ELEMENT_COUNT, SpanBatch, SpanBatchError};
+
+mod span_tx;
+pub use span_tx::{SpanBatchTransaction, SpanBatchTransactionData};
+
+use alloc::vec::Vec;
+use alloy_rlp::{Decodable, Encodable};
+
+/// The type byte of a [`SingularBatch`].
+pub const SINGULAR_BATCH_TYPE: u8 = 0;
+
+/// The type byte of a [`SpanBatch`].
+pub const SPAN_BATCH_TYPE: u8 = 1;
+
+/// A batch of the batch stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Batch {
+    /// A batch of a single L2 block.
+    Single(SingularBatch),
+    /// A batch of a span of L2 blocks, since Delta.
+    Span(SpanBatch),
+}
+
+impl Batch {
+    /// Returns the type byte of the batch.
+    pub const fn batch_type(&self) -> u8 {
+        match self {
+            Self::Single(_) => SINGULAR_BATCH_TYPE,
+            Self::Span(_) => SPAN_BATCH_TYPE,
+        }
+    }
+
+    /// Returns the encoded batch, `batch_type ++ batch_data`.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = alloc::vec![self.batch_type()];
+        match self {
+            Self::Single(batch) => batch.encode(&mut out),
+            Self::Span(batch) => batch.encode(&mut out),
+        }
+        out
+    }
+
+    /// Decodes a batch, e.g. an item of the [`BatchStream`](super::BatchStream).
+    pub fn decode(data: &[u8]) -> Result<Self, BatchDecodingError> {
+        let (&batch_type, mut buf) = data.split_first().ok_or(BatchDecodingError::Empty)?;
+        match batch_type {
+            SINGULAR_BATCH_TYPE => Ok(Self::Single(SingularBatch::decode(&mut buf)?)),
+            SPAN_BATCH_TYPE => Ok(Self::Span(SpanBatch::decode(buf)?)),
+            ty => Err(BatchDecodingError::UnsupportedBatchType(ty)),
+        }
+    }
+}
+
+/// An error decoding a [`Batch`].
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum BatchDecodingError {
+    /// The batch data is empty.
+    #[error("Empty batch")]
+    Empty,
+    /// The batch has an unsupported type.
+    #[error("Unsupported batch type: {0}")]
+    UnsupportedBatchType(u8),
+    /// The singular batch RLP is invalid.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+    /// The span batch is invalid.
+    #[error(transparent)]
+    SpanBatch(#[from] SpanBatchError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlockInfo, OpTxEnvelope, derivation::BatchStream};
+    use alloy_consensus::{SignableTransaction, TxEip1559};
+    use alloy_eips::{BlockNumHash, eip2718::Encodable2718};
+    use alloy_primitives::{B256, Bytes, Signature, TxKind, U256, b256, hex};
+
+    /// The signed example transaction of EIP-155, on chain 1.
+    const EIP155_TX: [u8; 110] = hex!(
+        "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+    );
+
+    #[test]
+    fn test_batch_stream_roundtrip() {
+        let batches = [
+            Batch::Single(SingularBatch {
+                parent_hash: B256::repeat_byte(1),
+                epoch_num: 10,
+                epoch_hash: B256::repeat_byte(2),
+                timestamp: 100,
+                transactions: Vec::new(),
+            }),
+            Batch::Span(SpanBatch {
+                rel_timestamp: 2,
+                l1_origin_num: 10,
+                origin_bits: alloc::vec![true],
+                block_tx_counts: alloc::vec![0],
+                ..Default::default()
+            }),
+        ];
+
+        let mut stream = Vec::new();
+        for batch in &batches {
+            Bytes::from(batch.encode()).encode(&mut stream);
+        }
+        let decoded = BatchStream::new(&stream)
+            .map(|item| Batch::decode(&item.unwrap()).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(decoded, batches);
+
+        assert_eq!(Batch::decode(&[]), Err(BatchDecodingError::Empty));
+        assert_eq!(Batch::decode(&[2]), Err(BatchDecodingError::UnsupportedBatchType(2)));
+    }
+
+    #[test]
+    fn test_decode_singular_batch_fixture() {
+        // Assembled field by field from the batch format of the derivation spec.
+        let mut data = hex!(
+            "00" // batch type
+            "f8ba" // list header
+            "a01111111111111111111111111111111111111111111111111111111111111111" // parent hash
+            "64" // epoch number
+            "a02222222222222222222222222222222222222222222222222222222222222222" // epoch hash
+            "846553f100" // timestamp
+            "f870b86e" // transaction list header, transaction header
+        )
+        .to_vec();
+        data.extend_from_slice(&EIP155_TX);
+
+        let Batch::Single(batch) = Batch::decode(&data).unwrap() else {
+            panic!("expected singular batch");
+        };
+        assert_eq!(batch.parent_hash, B256::repeat_byte(0x11));
+        assert_eq!(batch.epoch(), BlockNumHash::new(100, B256::repeat_byte(0x22)));
+        assert_eq!(batch.timestamp, 1_700_000_000);
+        let txs = batch.decode_transactions().unwrap();
+        assert_eq!(
+            txs.iter().map(|tx| tx.tx_hash()).collect::<Vec<_>>(),
+            [b256!("0x33469b22e9f636356c4160a87eb19df52b7412e8eac32a4a55ffe88ea8350788")]
+        );
+        assert_eq!(Batch::Single(batch).encode(), data);
+    }
+
+    #[test]
+    fn test_decode_span_batch_fixture() {
+        // Assembled field by field from the span batch format of the Delta spec, with the
+        // EIP-155 transaction in the first block and a contract creation in the second.
+        let data = hex!(
+            "01" // batch type
+            "e807" // rel_timestamp
+            "64" // l1_origin_num
+            "1111111111111111111111111111111111111111" // parent_check
+            "2222222222222222222222222222222222222222" // l1_origin_check
+            "02" // block_count
+            "01" // origin_bits
+            "0101" // block_tx_counts
+            "02" // contract_creation_bits
+            "02" // y_parity_bits
+            "28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276" // tx_sigs
+            "67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+            "3333333333333333333333333333333333333333333333333333333333333333"
+            "3535353535353535353535353535353535353535" // tx_tos
+            "d0880de0b6b3a76400008504a817c80080" // tx_datas
+            "02d280843b9aca008477359400856080604052c0"
+            "0900" // tx_nonces
+            "88a401a08d06" // tx_gases
+            "01" // protected_bits
+        );
+
+        let Batch::Span(span) = Batch::decode(&data).unwrap() else {
+            panic!("expected span batch");
+        };
+        assert_eq!(span.origin_bits, [true, false]);
+        assert_eq!(span.block_tx_counts, [1, 1]);
+        assert_eq!(span.epoch_nums(), [100, 100]);
+        assert!(span.transactions[0].data.is_protected());
+
+        let creation = TxEip1559 {
+            chain_id: 1,
+            nonce: 0,
+            gas_limit: 100_000,
+            max_fee_per_gas: 2_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            to: TxKind::Create,
+            input: hex!("6080604052").into(),
+            ..Default::default()
+        };
+        let signature =
+            Signature::new(U256::from_be_bytes([0xaa; 32]), U256::from_be_bytes([0x33; 32]), true);
+        let creation = OpTxEnvelope::from(creation.into_signed(signature)).encoded_2718();
+        let l1_origins = [BlockInfo::new(B256::repeat_byte(0x22), 100, B256::ZERO, 0)];
+        let batch = |timestamp, tx: &[u8]| SingularBatch {
+            parent_hash: B256::ZERO,
+            epoch_num: 100,
+            epoch_hash: B256::repeat_byte(0x22),
+            timestamp,
+            transactions: alloc::vec![Bytes::copy_from_slice(tx)],
+        };
+        assert_eq!(
+            span.to_singular_batches(&l1_origins, 0, 2, 1),
+            Ok(alloc::vec![batch(1_000, &EIP155_TX), batch(1_002, &creation)])
+        );
+        assert_eq!(Batch::Span(span).encode(), data);
+    }
+}
@@ -0,0 +1,136 @@
+//! The singular batch format, a batch of a single L2 block.
+//!
+//! Spec: <https://specs.optimism.io/protocol/derivation.html#batch-format>
+
+use crate::{DEPOSIT_TX_TYPE_ID, OpTxEnvelope};
+use alloc::vec::Vec;
+use alloy_eips::{
+    BlockNumHash,
+    eip2718::{Decodable2718, Eip2718Result, Encodable2718},
+};
+use alloy_primitives::{B256, Bytes};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header};
+
+/// A batch of a single L2 block:
+/// `rlp([parent_hash, epoch_number, epoch_hash, timestamp, transaction_list])`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SingularBatch {
+    /// The hash of the parent L2 block.
+    pub parent_hash: B256,
+    /// The number of the L1 origin of the block.
+    pub epoch_num: u64,
+    /// The hash of the L1 origin of the block.
+    pub epoch_hash: B256,
+    /// The timestamp of the block.
+    pub timestamp: u64,
+    /// The EIP-2718 encoded transactions of the block, excluding deposits.
+    pub transactions: Vec<Bytes>,
+}
+
+impl SingularBatch {
+    /// Creates a new [`SingularBatch`] from [`OpTxEnvelope`]s.
+    pub fn from_transactions<'a>(
+        parent_hash: B256,
+        epoch: BlockNumHash,
+        timestamp: u64,
+        transactions: impl IntoIterator<Item = &'a OpTxEnvelope>,
+    ) -> Self {
+        Self {
+            parent_hash,
+            epoch_num: epoch.number,
+            epoch_hash: epoch.hash,
+            timestamp,
+            transactions: transactions.into_iter().map(|tx| tx.encoded_2718().into()).collect(),
+        }
+    }
+
+    /// Returns the L1 origin of the block.
+    pub const fn epoch(&self) -> BlockNumHash {
+        BlockNumHash { number: self.epoch_num, hash: self.epoch_hash }
+    }
+
+    /// Returns true if the batch contains a deposit transaction.
+    pub fn has_deposits(&self) -> bool {
+        self.transactions.iter().any(|tx| tx.first() == Some(&DEPOSIT_TX_TYPE_ID))
+    }
+
+    /// Decodes the transactions of the batch.
+    pub fn decode_transactions(&self) -> Eip2718Result<Vec<OpTxEnvelope>> {
+        self.transactions.iter().map(|tx| OpTxEnvelope::decode_2718(&mut tx.as_ref())).collect()
+    }
+
+    fn rlp_payload_length(&self) -> usize {
+        self.parent_hash.length()
+            + self.epoch_num.length()
+            + self.epoch_hash.length()
+            + self.timestamp.length()
+            + self.transactions.length()
+    }
+}
+
+impl Encodable for SingularBatch {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.rlp_payload_length() }.encode(out);
+        self.parent_hash.encode(out);
+        self.epoch_num.encode(out);
+        self.epoch_hash.encode(out);
+        self.timestamp.encode(out);
+        self.transactions.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.rlp_payload_length();
+        Header { list: true, payload_length }.length() + payload_length
+    }
+}
+
+impl Decodable for SingularBatch {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let mut body = Header::decode_bytes(buf, true)?;
+        let batch = Self {
+            parent_hash: Decodable::decode(&mut body)?,
+            epoch_num: Decodable::decode(&mut body)?,
+            epoch_hash: Decodable::decode(&mut body)?,
+            timestamp: Decodable::decode(&mut body)?,
+            transactions: Decodable::decode(&mut body)?,
+        };
+        if !body.is_empty() {
+            return Err(alloy_rlp::Error::UnexpectedLength);
+        }
+        Ok(batch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_consensus::{SignableTransaction, TxEip1559};
+    use alloy_primitives::Signature;
+
+    #[test]
+    fn test_singular_batch_roundtrip() {
+        let tx: OpTxEnvelope = TxEip1559::default().into_signed(Signature::test_signature()).into();
+        let batch = SingularBatch::from_transactions(
+            B256::repeat_byte(1),
+            BlockNumHash { number: 10, hash: B256::repeat_byte(2) },
+            100,
+            [&tx],
+        );
+
+        let encoded = alloy_rlp::encode(&batch);
+        assert_eq!(encoded.len(), batch.length());
+        assert_eq!(SingularBatch::decode(&mut encoded.as_slice()), Ok(batch.clone()));
+        assert_eq!(batch.decode_transactions().unwrap(), vec![tx]);
+        assert!(!batch.has_deposits());
+    }
+
+    #[test]
+    fn test_has_deposits() {
+        let batch = SingularBatch {
+            transactions: vec![Bytes::from_static(&[DEPOSIT_TX_TYPE_ID, 0xc0])],
+            ..Default::default()
+        };
+        assert!(batch.has_deposits());
+    }
+}
@@ -0,0 +1,517 @@
+//! The span batch format, a batch of a span of consecutive L2 blocks.
+//!
+//! Spec: <https://specs.optimism.io/protocol/delta/span-batches.html>
+
+use super::{
+    SingularBatch, SpanBatchTransaction, SpanBatchTransactionData,
+    bits::{read_bitlist, read_bytes, read_uvarint, write_bitlist, write_uvarint},
+};
+use crate::{BlockInfo, OpTxEnvelope};
+use alloc::vec::Vec;
+use alloy_eips::eip2718::{Decodable2718, Eip2718Error, Encodable2718};
+use alloy_primitives::{Address, B256, ChainId, FixedBytes, Signature, U256};
+
+/// The maximum number of blocks or transactions in a span batch.
+pub const MAX_SPAN_BATCH_ELEMENT_COUNT: u64 = 10_000_000;
+
+/// A batch of a span of consecutive L2 blocks: `prefix ++ payload`, with
+///
+/// ```text
+/// prefix  = rel_timestamp ++ l1_origin_num ++ parent_check ++ l1_origin_check
+/// payload = block_count ++ origin_bits ++ block_tx_counts ++ txs
+/// txs     = contract_creation_bits ++ y_parity_bits ++ tx_sigs ++ tx_tos ++ tx_datas ++
+///           tx_nonces ++ tx_gases ++ protected_bits
+/// ```
+///
+/// The [`origin_bits`](Self::origin_bits) and [`block_tx_counts`](Self::block_tx_counts) have one
+/// entry per block, and the [`block_tx_counts`](Self::block_tx_counts) sum up to the number of
+/// [`transactions`](Self::transactions).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpanBatch {
+    /// The timestamp of the first block, relative to the L2 genesis timestamp.
+    pub rel_timestamp: u64,
+    /// The number of the L1 origin of the last block.
+    pub l1_origin_num: u64,
+    /// The first 20 bytes of the parent hash of the first block.
+    pub parent_check: FixedBytes<20>,
+    /// The first 20 bytes of the hash of the L1 origin of the last block.
+    pub l1_origin_check: FixedBytes<20>,
+    /// For each block, whether its L1 origin differs from the one of the previous block.
+    pub origin_bits: Vec<bool>,
+    /// The number of transactions of each block.
+    pub block_tx_counts: Vec<u64>,
+    /// The transactions of all blocks.
+    pub transactions: Vec<SpanBatchTransaction>,
+}
+
+impl SpanBatch {
+    /// Creates a span batch from consecutive [`SingularBatch`]es of the chain with the given id.
+    ///
+    /// `origin_changed` is whether the L1 origin of the first block differs from the one of its
+    /// parent, i.e. whether the first block starts an epoch.
+    pub fn from_singular_batches(
+        batches: &[SingularBatch],
+        origin_changed: bool,
+        genesis_timestamp: u64,
+        chain_id: ChainId,
+    ) -> Result<Self, SpanBatchError> {
+        let (first, last) =
+            batches.first().zip(batches.last()).ok_or(SpanBatchError::EmptySpanBatch)?;
+        let mut batch = Self {
+            rel_timestamp: first
+                .timestamp
+                .checked_sub(genesis_timestamp)
+                .ok_or(SpanBatchError::TimestampBeforeGenesis)?,
+            l1_origin_num: last.epoch_num,
+            parent_check: FixedBytes::from_slice(&first.parent_hash[..20]),
+            l1_origin_check: FixedBytes::from_slice(&last.epoch_hash[..20]),
+            ..Default::default()
+        };
+
+        let mut prev_epoch = None;
+        for singular in batches {
+            batch
+                .origin_bits
+                .push(prev_epoch.map_or(origin_changed, |epoch| epoch != singular.epoch_num));
+            prev_epoch = Some(singular.epoch_num);
+            batch.block_tx_counts.push(singular.transactions.len() as u64);
+            for tx in &singular.transactions {
+                let tx = OpTxEnvelope::decode_2718(&mut tx.as_ref())?;
+                batch.transactions.push(SpanBatchTransaction::from_envelope(&tx, chain_id)?);
+            }
+        }
+        Ok(batch)
+    }
+
+    /// Returns the number of blocks in the batch.
+    pub const fn block_count(&self) -> usize {
+        self.block_tx_counts.len()
+    }
+
+    /// Returns the timestamp of the first block.
+    ///
+    /// The [`rel_timestamp`](Self::rel_timestamp) is untrusted, so the timestamps of a span batch
+    /// wrap around on overflow, like the `uint64` arithmetic of op-node.
+    pub const fn starting_timestamp(&self, genesis_timestamp: u64) -> u64 {
+        genesis_timestamp.wrapping_add(self.rel_timestamp)
+    }
+
+    /// Returns the timestamp of the last block.
+    pub const fn final_timestamp(&self, genesis_timestamp: u64, block_time: u64) -> u64 {
+        self.starting_timestamp(genesis_timestamp)
+            .wrapping_add(block_time.wrapping_mul(self.block_count().saturating_sub(1) as u64))
+    }
+
+    /// Returns the timestamps of the blocks.
+    pub fn block_timestamps(
+        &self,
+        genesis_timestamp: u64,
+        block_time: u64,
+    ) -> impl Iterator<Item = u64> + use<> {
+        let start = self.starting_timestamp(genesis_timestamp);
+        (0..self.block_count() as u64).map(move |i| start.wrapping_add(block_time.wrapping_mul(i)))
+    }
+
+    /// Returns the L1 origin numbers of the blocks, derived backwards from the
+    /// [`l1_origin_num`](Self::l1_origin_num) of the last block and the
+    /// [`origin_bits`](Self::origin_bits).
+    pub fn epoch_nums(&self) -> Vec<u64> {
+        let mut epoch_nums = alloc::vec![0; self.block_count()];
+        let mut epoch_num = self.l1_origin_num;
+        for i in (0..epoch_nums.len()).rev() {
+            epoch_nums[i] = epoch_num;
+            if i > 0 && self.origin_bits.get(i).copied().unwrap_or_default() {
+                epoch_num = epoch_num.saturating_sub(1);
+            }
+        }
+        epoch_nums
+    }
+
+    /// Returns the transactions of each block.
+    pub fn block_transactions(&self) -> impl Iterator<Item = &[SpanBatchTransaction]> {
+        let mut remaining = self.transactions.as_slice();
+        self.block_tx_counts.iter().map(move |count| {
+            let (block, rest) = remaining.split_at((*count as usize).min(remaining.len()));
+            remaining = rest;
+            block
+        })
+    }
+
+    /// Converts the span batch into [`SingularBatch`]es of the chain with the given id.
+    ///
+    /// The epoch hashes are looked up in `l1_origins`, which must contain the L1 origins of all
+    /// blocks in ascending order. The parent hashes are not part of a span batch and are left
+    /// zero.
+    pub fn to_singular_batches(
+        &self,
+        l1_origins: &[BlockInfo],
+        genesis_timestamp: u64,
+        block_time: u64,
+        chain_id: ChainId,
+    ) -> Result<Vec<SingularBatch>, SpanBatchError> {
+        let mut origin_index = 0;
+        let mut batches = Vec::with_capacity(self.block_count());
+        for ((timestamp, epoch_num), transactions) in self
+            .block_timestamps(genesis_timestamp, block_time)
+            .zip(self.epoch_nums())
+            .zip(self.block_transactions())
+        {
+            origin_index += l1_origins[origin_index..]
+                .iter()
+                .position(|origin| origin.number == epoch_num)
+                .ok_or(SpanBatchError::L1OriginNotFound(epoch_num))?;
+            let epoch_hash = l1_origins[origin_index].hash;
+            batches.push(SingularBatch {
+                parent_hash: B256::ZERO,
+                epoch_num,
+                epoch_hash,
+                timestamp,
+                transactions: transactions
+                    .iter()
+                    .map(|tx| Ok(tx.to_envelope(chain_id)?.encoded_2718().into()))
+                    .collect::<Result<_, SpanBatchError>>()?,
+            });
+        }
+        Ok(batches)
+    }
+
+    /// Appends the encoded span batch to `out`.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        write_uvarint(out, self.rel_timestamp);
+        write_uvarint(out, self.l1_origin_num);
+        out.extend_from_slice(self.parent_check.as_slice());
+        out.extend_from_slice(self.l1_origin_check.as_slice());
+
+        write_uvarint(out, self.block_count() as u64);
+        write_bitlist(out, &self.origin_bits);
+        for count in &self.block_tx_counts {
+            write_uvarint(out, *count);
+        }
+
+        let txs = &self.transactions;
+        write_bitlist(out, &txs.iter().map(|tx| tx.to.is_none()).collect::<Vec<_>>());
+        write_bitlist(out, &txs.iter().map(|tx| tx.signature.v()).collect::<Vec<_>>());
+        for tx in txs {
+            out.extend_from_slice(&tx.signature.r().to_be_bytes::<32>());
+            out.extend_from_slice(&tx.signature.s().to_be_bytes::<32>());
+        }
+        for to in txs.iter().filter_map(|tx| tx.to) {
+            out.extend_from_slice(to.as_slice());
+        }
+        for tx in txs {
+            tx.data.encode(out);
+        }
+        for tx in txs {
+            write_uvarint(out, tx.nonce);
+        }
+        for tx in txs {
+            write_uvarint(out, tx.gas_limit);
+        }
+        write_bitlist(
+            out,
+            &txs.iter()
+                .filter(|tx| tx.data.is_legacy())
+                .map(|tx| tx.data.is_protected())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    /// Returns the encoded span batch.
+    pub fn encoded(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode(&mut out);
+        out
+    }
+
+    /// Decodes a span batch.
+    pub fn decode(mut buf: &[u8]) -> Result<Self, SpanBatchError> {
+        let buf = &mut buf;
+        let rel_timestamp = read_uvarint(buf)?;
+        let l1_origin_num = read_uvarint(buf)?;
+        let parent_check = FixedBytes::from_slice(read_bytes(buf, 20)?);
+        let l1_origin_check = FixedBytes::from_slice(read_bytes(buf, 20)?);
+
+        let block_count = read_element_count(buf)?;
+        if block_count == 0 {
+            return Err(SpanBatchError::EmptySpanBatch);
+        }
+        let origin_bits = read_bitlist(buf, block_count)?;
+        let mut block_tx_counts = Vec::with_capacity(block_count);
+        let mut tx_count = 0usize;
+        for _ in 0..block_count {
+            let count = read_element_count(buf)?;
+            tx_count += count;
+            if tx_count as u64 > MAX_SPAN_BATCH_ELEMENT_COUNT {
+                return Err(SpanBatchError::TooBigSpanBatchSize);
+            }
+            block_tx_counts.push(count as u64);
+        }
+
+        let contract_creation_bits = read_bitlist(buf, tx_count)?;
+        let y_parity_bits = read_bitlist(buf, tx_count)?;
+        let signatures = (0..tx_count)
+            .map(|i| {
+                let sig = read_bytes(buf, 64)?;
+                Ok(Signature::new(
+                    U256::from_be_slice(&sig[..32]),
+                    U256::from_be_slice(&sig[32..]),
+                    y_parity_bits[i],
+                ))
+            })
+            .collect::<Result<Vec<_>, SpanBatchError>>()?;
+        let mut tos = Vec::with_capacity(tx_count);
+        for is_creation in &contract_creation_bits {
+            tos.push(if *is_creation {
+                None
+            } else {
+                Some(Address::from_slice(read_bytes(buf, 20)?))
+            });
+        }
+        let mut datas = (0..tx_count)
+            .map(|_| SpanBatchTransactionData::decode(buf))
+            .collect::<Result<Vec<_>, _>>()?;
+        let nonces = (0..tx_count).map(|_| read_uvarint(buf)).collect::<Result<Vec<_>, _>>()?;
+        let gases = (0..tx_count).map(|_| read_uvarint(buf)).collect::<Result<Vec<_>, _>>()?;
+        let legacy_count = datas.iter().filter(|data| data.is_legacy()).count();
+        let protected_bits = read_bitlist(buf, legacy_count)?;
+        for (data, bit) in datas.iter_mut().filter(|data| data.is_legacy()).zip(protected_bits) {
+            if let SpanBatchTransactionData::Legacy { protected, .. } = data {
+                *protected = bit;
+            }
+        }
+
+        let transactions = signatures
+            .into_iter()
+            .zip(tos)
+            .zip(datas)
+            .zip(nonces.into_iter().zip(gases))
+            .map(|(((signature, to), data), (nonce, gas_limit))| SpanBatchTransaction {
+                signature,
+                to,
+                nonce,
+                gas_limit,
+                data,
+            })
+            .collect();
+
+        Ok(Self {
+            rel_timestamp,
+            l1_origin_num,
+            parent_check,
+            l1_origin_check,
+            origin_bits,
+            block_tx_counts,
+            transactions,
+        })
+    }
+}
+
+/// Reads a block or transaction count, bounded by [`MAX_SPAN_BATCH_ELEMENT_COUNT`].
+fn read_element_count(buf: &mut &[u8]) -> Result<usize, SpanBatchError> {
+    let count = read_uvarint(buf)?;
+    if count > MAX_SPAN_BATCH_ELEMENT_COUNT {
+        return Err(SpanBatchError::TooBigSpanBatchSize);
+    }
+    Ok(count as usize)
+}
+
+/// An error encoding, decoding or converting a [`SpanBatch`].
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum SpanBatchError {
+    /// The span batch data is truncated.
+    #[error("Truncated span batch")]
+    Truncated,
+    /// A varint overflows a `u64`.
+    #[error("Invalid varint in span batch")]
+    InvalidVarint,
+    /// A bitlist has bits set beyond its length.
+    #[error("Invalid bitlist in span batch")]
+    InvalidBitlist,
+    /// The span batch has no blocks.
+    #[error("Empty span batch")]
+    EmptySpanBatch,
+    /// The number of blocks or transactions exceeds [`MAX_SPAN_BATCH_ELEMENT_COUNT`].
+    #[error("Span batch size exceeds the maximum element count")]
+    TooBigSpanBatchSize,
+    /// A transaction has an unsupported type.
+    #[error("Invalid span batch transaction type: {0}")]
+    InvalidTransactionType(u8),
+    /// The RLP encoded data of a transaction is invalid.
+    #[error("Invalid span batch transaction data: {0}")]
+    InvalidTransactionData(#[from] alloy_rlp::Error),
+    /// An EIP-7702 transaction has no recipient.
+    #[error("EIP-7702 span batch transaction without recipient")]
+    MissingRecipient,
+    /// A transaction is signed for another chain.
+    #[error("Transaction chain id {actual} does not match chain id {expected}")]
+    ChainIdMismatch {
+        /// The chain id of the span batch.
+        expected: ChainId,
+        /// The chain id of the transaction.
+        actual: ChainId,
+    },
+    /// The L1 origin of a block is not in the supplied L1 origins.
+    #[error("L1 origin {0} not found")]
+    L1OriginNotFound(u64),
+    /// The first block is older than the L2 genesis.
+    #[error("Span batch timestamp before genesis")]
+    TimestampBeforeGenesis,
+}
+
+impl From<Eip2718Error> for SpanBatchError {
+    fn from(err: Eip2718Error) -> Self {
+        match err {
+            Eip2718Error::UnexpectedType(ty) => Self::InvalidTransactionType(ty),
+            Eip2718Error::RlpError(err) => Self::InvalidTransactionData(err),
+            _ => Self::InvalidTransactionData(alloy_rlp::Error::Custom("invalid transaction")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_consensus::{SignableTransaction, TxEip1559, TxEip7702, TxLegacy};
+    use alloy_eips::{
+        eip2930::{AccessList, AccessListItem},
+        eip7702::Authorization,
+    };
+    use alloy_primitives::{Bytes, TxKind};
+
+    const CHAIN_ID: ChainId = 5000;
+
+    fn signature(seed: u8, y_parity: bool) -> Signature {
+        Signature::new(U256::from(seed) << 200, U256::from(seed + 1) << 100, y_parity)
+    }
+
+    fn transactions() -> Vec<OpTxEnvelope> {
+        vec![
+            TxLegacy {
+                chain_id: Some(CHAIN_ID),
+                nonce: 1,
+                gas_price: 10,
+                gas_limit: 21_000,
+                to: TxKind::Call(Address::repeat_byte(1)),
+                value: U256::from(100),
+                input: Bytes::new(),
+            }
+            .into_signed(signature(1, true))
+            .into(),
+            TxLegacy { chain_id: None, gas_limit: 100_000, ..Default::default() }
+                .into_signed(signature(2, false))
+                .into(),
+            TxEip1559 {
+                chain_id: CHAIN_ID,
+                nonce: 300,
+                gas_limit: 50_000,
+                max_fee_per_gas: 1_000,
+                max_priority_fee_per_gas: 1,
+                to: TxKind::Call(Address::repeat_byte(2)),
+                value: U256::ZERO,
+                access_list: AccessList(vec![AccessListItem {
+                    address: Address::repeat_byte(3),
+                    storage_keys: vec![B256::repeat_byte(4)],
+                }]),
+                input: Bytes::from_static(&[1, 2, 3]),
+            }
+            .into_signed(signature(3, true))
+            .into(),
+            TxEip7702 {
+                chain_id: CHAIN_ID,
+                to: Address::repeat_byte(5),
+                authorization_list: vec![
+                    Authorization {
+                        chain_id: U256::from(CHAIN_ID),
+                        address: Address::repeat_byte(6),
+                        nonce: 1,
+                    }
+                    .into_signed(signature(4, false)),
+                ],
+                ..Default::default()
+            }
+            .into_signed(signature(5, false))
+            .into(),
+        ]
+    }
+
+    fn singular_batches() -> Vec<SingularBatch> {
+        let txs = transactions();
+        let batch = |epoch_num: u64, timestamp, txs: &[OpTxEnvelope]| SingularBatch {
+            parent_hash: B256::ZERO,
+            epoch_num,
+            epoch_hash: B256::with_last_byte(epoch_num as u8),
+            timestamp,
+            transactions: txs.iter().map(|tx| tx.encoded_2718().into()).collect(),
+        };
+        vec![batch(10, 1_002, &txs[..2]), batch(10, 1_004, &[]), batch(11, 1_006, &txs[2..])]
+    }
+
+    fn l1_origins() -> Vec<BlockInfo> {
+        (9..=11).map(|n| BlockInfo::new(B256::with_last_byte(n as u8), n, B256::ZERO, 0)).collect()
+    }
+
+    #[test]
+    fn test_span_batch_roundtrip() {
+        let batches = singular_batches();
+        let span = SpanBatch::from_singular_batches(&batches, true, 1_000, CHAIN_ID).unwrap();
+
+        assert_eq!(span.rel_timestamp, 2);
+        assert_eq!(span.origin_bits, vec![true, false, true]);
+        assert_eq!(span.block_tx_counts, vec![2, 0, 2]);
+        assert_eq!(span.epoch_nums(), vec![10, 10, 11]);
+        assert_eq!(span.final_timestamp(1_000, 2), 1_006);
+
+        let encoded = span.encoded();
+        assert_eq!(SpanBatch::decode(&encoded), Ok(span.clone()));
+        assert_eq!(span.to_singular_batches(&l1_origins(), 1_000, 2, CHAIN_ID), Ok(batches));
+    }
+
+    #[test]
+    fn test_span_batch_errors() {
+        let batches = singular_batches();
+        let span = SpanBatch::from_singular_batches(&batches, true, 1_000, CHAIN_ID).unwrap();
+        let encoded = span.encoded();
+
+        assert_eq!(
+            SpanBatch::decode(&encoded[..encoded.len() - 1]),
+            Err(SpanBatchError::Truncated)
+        );
+        assert_eq!(
+            span.to_singular_batches(&l1_origins()[..2], 1_000, 2, CHAIN_ID),
+            Err(SpanBatchError::L1OriginNotFound(11))
+        );
+        assert_eq!(
+            SpanBatch::from_singular_batches(&batches, true, 1_000, 1),
+            Err(SpanBatchError::ChainIdMismatch { expected: 1, actual: CHAIN_ID })
+        );
+        assert_eq!(
+            SpanBatch::from_singular_batches(&[], true, 1_000, CHAIN_ID),
+            Err(SpanBatchError::EmptySpanBatch)
+        );
+        assert_eq!(
+            SpanBatch::decode(&SpanBatch::default().encoded()),
+            Err(SpanBatchError::EmptySpanBatch)
+        );
+    }
+
+    #[test]
+    fn test_span_batch_timestamp_overflow() {
+        let batches = singular_batches();
+        let span = SpanBatch {
+            rel_timestamp: u64::MAX,
+            ..SpanBatch::from_singular_batches(&batches, true, 1_000, CHAIN_ID).unwrap()
+        };
+        let span = SpanBatch::decode(&span.encoded()).unwrap();
+
+        assert_eq!(span.rel_timestamp, u64::MAX);
+        assert_eq!(span.starting_timestamp(1_000), 999);
+        assert_eq!(span.final_timestamp(1_000, 2), 1_003);
+        assert_eq!(span.block_timestamps(1_000, 2).collect::<Vec<_>>(), vec![999, 1_001, 1_003]);
+        let singular = span.to_singular_batches(&l1_origins(), 1_000, 2, CHAIN_ID).unwrap();
+        assert_eq!(
+            singular.iter().map(|batch| batch.timestamp).collect::<Vec<_>>(),
+            [999, 1_001, 1_003]
+        );
+    }
+}
@@ -0,0 +1,321 @@
+//! The transactions of a span batch.
+//!
+//! Spec: <https://specs.optimism.io/protocol/delta/span-batches.html#span-batch-format>
+
+use super::SpanBatchError;
+use crate::OpTxEnvelope;
+use alloc::{vec, vec::Vec};
+use alloy_consensus::{
+    SignableTransaction, Transaction, TxEip1559, TxEip2930, TxEip7702, TxLegacy,
+};
+use alloy_eips::{eip2930::AccessList, eip7702::SignedAuthorization};
+use alloy_primitives::{Address, Bytes, ChainId, Signature, TxKind, U256};
+use alloy_rlp::{Decodable, Encodable, Header};
+
+/// A transaction of a span batch, without its chain id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanBatchTransaction {
+    /// The signature of the transaction.
+    pub signature: Signature,
+    /// The recipient of the transaction, [`None`] for contract creations.
+    pub to: Option<Address>,
+    /// The nonce of the transaction.
+    pub nonce: u64,
+    /// The gas limit of the transaction.
+    pub gas_limit: u64,
+    /// The type specific fields of the transaction.
+    pub data: SpanBatchTransactionData,
+}
+
+/// The type specific fields of a [`SpanBatchTransaction`], encoded in the `tx_datas` of a span
+/// batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpanBatchTransactionData {
+    /// A legacy transaction: `rlp([value, gas_price, data])`.
+    Legacy {
+        /// The value of the transaction.
+        value: U256,
+        /// The gas price of the transaction.
+        gas_price: u128,
+        /// The input of the transaction.
+        input: Bytes,
+        /// Whether the transaction is EIP-155 replay protected.
+        protected: bool,
+    },
+    /// An EIP-2930 transaction: `0x01 ++ rlp([value, gas_price, data, access_list])`.
+    Eip2930 {
+        /// The value of the transaction.
+        value: U256,
+        /// The gas price of the transaction.
+        gas_price: u128,
+        /// The input of the transaction.
+        input: Bytes,
+        /// The access list of the transaction.
+        access_list: AccessList,
+    },
+    /// An EIP-1559 transaction:
+    /// `0x02 ++ rlp([value, max_priority_fee_per_gas, max_fee_per_gas, data, access_list])`.
+    Eip1559 {
+        /// The value of the transaction.
+        value: U256,
+        /// The max priority fee per gas of the transaction.
+        max_priority_fee_per_gas: u128,
+        /// The max fee per gas of the transaction.
+        max_fee_per_gas: u128,
+        /// The input of the transaction.
+        input: Bytes,
+        /// The access list of the transaction.
+        access_list: AccessList,
+    },
+    /// An EIP-7702 transaction: `0x04 ++ rlp([value, max_priority_fee_per_gas, max_fee_per_gas,
+    /// data, access_list, authorization_list])`.
+    Eip7702 {
+        /// The value of the transaction.
+        value: U256,
+        /// The max priority fee per gas of the transaction.
+        max_priority_fee_per_gas: u128,
+        /// The max fee per gas of the transaction.
+        max_fee_per_gas: u128,
+        /// The input of the transaction.
+        input: Bytes,
+        /// The access list of the transaction.
+        access_list: AccessList,
+        /// The authorization list of the transaction.
+        authorization_list: Vec<SignedAuthorization>,
+    },
+}
+
+impl SpanBatchTransactionData {
+    /// Returns true for legacy transactions.
+    pub const fn is_legacy(&self) -> bool {
+        matches!(self, Self::Legacy { .. })
+    }
+
+    /// Returns true if the transaction is EIP-155 replay protected.
+    ///
+    /// Typed transactions are always protected.
+    pub const fn is_protected(&self) -> bool {
+        match self {
+            Self::Legacy { protected, .. } => *protected,
+            _ => true,
+        }
+    }
+
+    /// Appends the encoding of the `tx_data` to `out`.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        let fields: Vec<&dyn Encodable> = match self {
+            Self::Legacy { value, gas_price, input, .. } => vec![value, gas_price, input],
+            Self::Eip2930 { value, gas_price, input, access_list } => {
+                out.push(1);
+                vec![value, gas_price, input, access_list]
+            }
+            Self::Eip1559 {
+                value,
+                max_priority_fee_per_gas,
+                max_fee_per_gas,
+                input,
+                access_list,
+            } => {
+                out.push(2);
+                vec![value, max_priority_fee_per_gas, max_fee_per_gas, input, access_list]
+            }
+            Self::Eip7702 {
+                value,
+                max_priority_fee_per_gas,
+                max_fee_per_gas,
+                input,
+                access_list,
+                authorization_list,
+            } => {
+                out.push(4);
+                vec![
+                    value,
+                    max_priority_fee_per_gas,
+                    max_fee_per_gas,
+                    input,
+                    access_list,
+                    authorization_list,
+                ]
+            }
+        };
+        alloy_rlp::encode_list::<_, dyn Encodable>(&fields, out);
+    }
+
+    /// Decodes a `tx_data` from the start of `buf`.
+    ///
+    /// The decoded transaction is not [protected](Self::is_protected) if it is a legacy
+    /// transaction, the protection is set from the `protected_bits` of the span batch.
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, SpanBatchError> {
+        let &first = buf.first().ok_or(SpanBatchError::Truncated)?;
+        let ty = if first >= alloy_rlp::EMPTY_LIST_CODE {
+            0
+        } else {
+            *buf = &buf[1..];
+            first
+        };
+        let mut body = Header::decode_bytes(buf, true)?;
+        let data = match ty {
+            0 => Self::Legacy {
+                value: Decodable::decode(&mut body)?,
+                gas_price: Decodable::decode(&mut body)?,
+                input: Decodable::decode(&mut body)?,
+                protected: false,
+            },
+            1 => Self::Eip2930 {
+                value: Decodable::decode(&mut body)?,
+                gas_price: Decodable::decode(&mut body)?,
+                input: Decodable::decode(&mut body)?,
+                access_list: Decodable::decode(&mut body)?,
+            },
+            2 => Self::Eip1559 {
+                value: Decodable::decode(&mut body)?,
+                max_priority_fee_per_gas: Decodable::decode(&mut body)?,
+                max_fee_per_gas: Decodable::decode(&mut body)?,
+                input: Decodable::decode(&mut body)?,
+                access_list: Decodable::decode(&mut body)?,
+            },
+            4 => Self::Eip7702 {
+                value: Decodable::decode(&mut body)?,
+                max_priority_fee_per_gas: Decodable::decode(&mut body)?,
+                max_fee_per_gas: Decodable::decode(&mut body)?,
+                input: Decodable::decode(&mut body)?,
+                access_list: Decodable::decode(&mut body)?,
+                authorization_list: Decodable::decode(&mut body)?,
+            },
+            ty => return Err(SpanBatchError::InvalidTransactionType(ty)),
+        };
+        if !body.is_empty() {
+            return Err(alloy_rlp::Error::UnexpectedLength.into());
+        }
+        Ok(data)
+    }
+}
+
+impl SpanBatchTransaction {
+    /// Converts an [`OpTxEnvelope`] of the chain with the given id into a span batch
+    /// transaction.
+    pub fn from_envelope(tx: &OpTxEnvelope, chain_id: ChainId) -> Result<Self, SpanBatchError> {
+        if let Some(actual) = tx.chain_id().filter(|id| *id != chain_id) {
+            return Err(SpanBatchError::ChainIdMismatch { expected: chain_id, actual });
+        }
+        let (signature, data) = match tx {
+            OpTxEnvelope::Legacy(tx) => (
+                *tx.signature(),
+                SpanBatchTransactionData::Legacy {
+                    value: tx.tx().value,
+                    gas_price: tx.tx().gas_price,
+                    input: tx.tx().input.clone(),
+                    protected: tx.tx().chain_id.is_some(),
+                },
+            ),
+            OpTxEnvelope::Eip2930(tx) => (
+                *tx.signature(),
+                SpanBatchTransactionData::Eip2930 {
+                    value: tx.tx().value,
+                    gas_price: tx.tx().gas_price,
+                    input: tx.tx().input.clone(),
+                    access_list: tx.tx().access_list.clone(),
+                },
+            ),
+            OpTxEnvelope::Eip1559(tx) => (
+                *tx.signature(),
+                SpanBatchTransactionData::Eip1559 {
+                    value: tx.tx().value,
+                    max_priority_fee_per_gas: tx.tx().max_priority_fee_per_gas,
+                    max_fee_per_gas: tx.tx().max_fee_per_gas,
+                    input: tx.tx().input.clone(),
+                    access_list: tx.tx().access_list.clone(),
+                },
+            ),
+            OpTxEnvelope::Eip7702(tx) => (
+                *tx.signature(),
+                SpanBatchTransactionData::Eip7702 {
+                    value: tx.tx().value,
+                    max_priority_fee_per_gas: tx.tx().max_priority_fee_per_gas,
+                    max_fee_per_gas: tx.tx().max_fee_per_gas,
+                    input: tx.tx().input.clone(),
+                    access_list: tx.tx().access_list.clone(),
+                    authorization_list: tx.tx().authorization_list.clone(),
+                },
+            ),
+            OpTxEnvelope::Deposit(_) => {
+                return Err(SpanBatchError::InvalidTransactionType(tx.tx_type() as u8));
+            }
+        };
+        Ok(Self { signature, to: tx.to(), nonce: tx.nonce(), gas_limit: tx.gas_limit(), data })
+    }
+
+    /// Converts the span batch transaction into an [`OpTxEnvelope`] of the chain with the given
+    /// id.
+    pub fn to_envelope(&self, chain_id: ChainId) -> Result<OpTxEnvelope, SpanBatchError> {
+        let to = self.to.map_or(TxKind::Create, TxKind::Call);
+        let tx = match self.data.clone() {
+            SpanBatchTransactionData::Legacy { value, gas_price, input, protected } => TxLegacy {
+                chain_id: protected.then_some(chain_id),
+                nonce: self.nonce,
+                gas_price,
+                gas_limit: self.gas_limit,
+                to,
+                value,
+                input,
+            }
+            .into_signed(self.signature)
+            .into(),
+            SpanBatchTransactionData::Eip2930 { value, gas_price, input, access_list } => {
+                TxEip2930 {
+                    chain_id,
+                    nonce: self.nonce,
+                    gas_price,
+                    gas_limit: self.gas_limit,
+                    to,
+                    value,
+                    access_list,
+                    input,
+                }
+                .into_signed(self.signature)
+                .into()
+            }
+            SpanBatchTransactionData::Eip1559 {
+                value,
+                max_priority_fee_per_gas,
+                max_fee_per_gas,
+                input,
+                access_list,
+            } => TxEip1559 {
+                chain_id,
+                nonce: self.nonce,
+                gas_limit: self.gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                to,
+                value,
+                access_list,
+                input,
+            }
+            .into_signed(self.signature)
+            .into(),
+            SpanBatchTransactionData::Eip7702 {
+                value,
+                max_priority_fee_per_gas,
+                max_fee_per_gas,
+                input,
+                access_list,
+                authorization_list,
+            } => TxEip7702 {
+                chain_id,
+                nonce: self.nonce,
+                gas_limit: self.gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                to: self.to.ok_or(SpanBatchError::MissingRecipient)?,
+                value,
+                access_list,
+                authorization_list,
+                input,
+            }
+            .into_signed(self.signature)
+            .into(),
+        };
+        Ok(tx)
+    }
+}
@@ -0,0 +1,591 @@
+//! Encoding of L2 blocks into batcher transactions, the inverse of derivation.
+//!
+//! The [`BatcherTxBuilder`] turns L2 blocks into [`Batch`]es, fills channels with them up to a
+//! target compressed size, splits the compressed channels into [`Frame`]s, and wraps the frames
+//! into calldata or blob [`BatcherTransaction`]s to the batch inbox. The output only depends on
+//! the input blocks and the [`BatcherConfig`].
+
+use super::{
+    Batch, BlobData, BlobDecodingError, BlobEncodingError, CHANNEL_ID_LENGTH, ChannelId,
+    CompressionAlgo, CompressionError, FRAME_OVERHEAD, Frame, MAX_BLOB_DATA_SIZE,
+    MAX_RLP_BYTES_PER_CHANNEL, SingularBatch, SpanBatch, SpanBatchError, compress_channel_data,
+    compression::ChannelCompressor,
+};
+use crate::OpBlock;
+use alloc::vec::Vec;
+use alloy_consensus::TxEip1559;
+use alloy_eips::{BlockNumHash, eip4844::Blob};
+use alloy_primitives::{Address, Bytes, ChainId, TxKind, keccak256};
+use alloy_rlp::Encodable;
+
+/// The maximum size of a frame posted in a blob, leaving room for the derivation version byte.
+pub const MAX_BLOB_FRAME_SIZE: usize = MAX_BLOB_DATA_SIZE - 1;
+
+/// The format of the batches posted by the batcher.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BatchFormat {
+    /// A [`SingularBatch`] per block.
+    #[default]
+    Singular,
+    /// A [`SpanBatch`] per channel, since Delta.
+    Span,
+}
+
+/// The way frames are posted to L1.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DataAvailabilityType {
+    /// The frames are posted in the calldata of transactions.
+    #[default]
+    Calldata,
+    /// The frames are posted in blobs, one frame per blob, since Ecotone.
+    Blobs,
+}
+
+/// The configuration of a [`BatcherTxBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatcherConfig {
+    /// The L2 chain id.
+    pub chain_id: ChainId,
+    /// The timestamp of the L2 genesis block.
+    pub genesis_timestamp: u64,
+    /// The batch inbox address, the recipient of batcher transactions.
+    pub batch_inbox: Address,
+    /// The format of the batches.
+    pub batch_format: BatchFormat,
+    /// The compression algorithm of the channels.
+    pub compression: CompressionAlgo,
+    /// The target size of the compressed data of a channel.
+    ///
+    /// Blocks are added to a channel as long as its compressed data stays within the target. A
+    /// channel holds at least one block. For singular batches, the compressed size is estimated
+    /// by flushing the compressor after each block, like op-batcher does.
+    pub target_channel_size: usize,
+    /// The maximum size of an encoded frame, including the [`FRAME_OVERHEAD`].
+    pub max_frame_size: usize,
+    /// The way frames are posted to L1.
+    pub da_type: DataAvailabilityType,
+    /// The maximum number of frames of a transaction, i.e. of blobs of a blob transaction.
+    pub max_frames_per_tx: usize,
+}
+
+impl BatcherConfig {
+    /// Returns the maximum size of the data of a frame.
+    const fn max_frame_data_size(&self) -> Result<usize, BatcherError> {
+        let max_size = match self.da_type {
+            DataAvailabilityType::Calldata => usize::MAX,
+            DataAvailabilityType::Blobs => MAX_BLOB_FRAME_SIZE,
+        };
+        if self.max_frame_size <= FRAME_OVERHEAD || self.max_frame_size > max_size {
+            return Err(BatcherError::InvalidFrameSize(self.max_frame_size));
+        }
+        Ok(self.max_frame_size - FRAME_OVERHEAD)
+    }
+
+    /// Returns the maximum number of frames of a transaction.
+    const fn max_frames_per_tx(&self) -> Result<usize, BatcherError> {
+        if self.max_frames_per_tx == 0 {
+            return Err(BatcherError::InvalidFramesPerTx(self.max_frames_per_tx));
+        }
+        Ok(self.max_frames_per_tx)
+    }
+}
+
+/// A batcher transaction to the batch inbox, before its nonce, gas and fees are set.
+///
+/// The frames are either in the calldata, or in the blobs of a blob transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatcherTransaction {
+    /// The batch inbox address.
+    pub to: Address,
+    /// The frames posted in calldata, empty for blob transactions.
+    pub input: Bytes,
+    /// The blobs of a blob transaction, each containing the data of a frame.
+    pub blobs: Vec<Blob>,
+}
+
+impl BatcherTransaction {
+    /// Returns true if the frames are posted in blobs.
+    pub const fn is_blob_tx(&self) -> bool {
+        !self.blobs.is_empty()
+    }
+
+    /// Returns the batcher transaction data, from the calldata or from each blob.
+    pub fn frames_data(&self) -> Result<Vec<Bytes>, BlobDecodingError> {
+        if !self.is_blob_tx() {
+            return Ok(alloc::vec![self.input.clone()]);
+        }
+        self.blobs.iter().map(BlobData::decode_blob).collect()
+    }
+
+    /// Returns the calldata transaction, with the other fields taken from `tx`.
+    pub fn to_eip1559(&self, tx: TxEip1559) -> TxEip1559 {
+        TxEip1559 { to: TxKind::Call(self.to), input: self.input.clone(), ..tx }
+    }
+
+    /// Returns the blob transaction with its sidecar, with the other fields taken from `tx`.
+    #[cfg(feature = "kzg")]
+    pub fn to_eip4844(
+        &self,
+        tx: alloy_consensus::TxEip4844,
+    ) -> Result<
+        alloy_consensus::TxEip4844WithSidecar,
+        alloy_eips::eip4844::BlobTransactionValidationError,
+    > {
+        let sidecar = super::blob::blob_sidecar(self.blobs.clone())?;
+        let tx = alloy_consensus::TxEip4844 {
+            to: self.to,
+            input: self.input.clone(),
+            blob_versioned_hashes: sidecar.versioned_hashes().collect(),
+            ..tx
+        };
+        Ok(alloy_consensus::TxEip4844WithSidecar { tx, sidecar })
+    }
+}
+
+/// Builds batcher transactions from L2 blocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatcherTxBuilder {
+    /// The configuration.
+    config: BatcherConfig,
+    /// The singular batches of the added blocks.
+    batches: Vec<SingularBatch>,
+    /// Whether each added block is the first block of its epoch.
+    epoch_starts: Vec<bool>,
+}
+
+impl BatcherTxBuilder {
+    /// Creates a new [`BatcherTxBuilder`] with the given configuration.
+    pub const fn new(config: BatcherConfig) -> Self {
+        Self { config, batches: Vec::new(), epoch_starts: Vec::new() }
+    }
+
+    /// Returns the configuration.
+    pub const fn config(&self) -> &BatcherConfig {
+        &self.config
+    }
+
+    /// Returns the singular batches of the added blocks.
+    pub fn batches(&self) -> &[SingularBatch] {
+        &self.batches
+    }
+
+    /// Adds the next L2 block, with its L1 origin and sequence number.
+    ///
+    /// The deposits of the block are not part of its batch.
+    pub fn add_block(&mut self, block: &OpBlock, l1_origin: BlockNumHash, seq_num: u64) {
+        self.batches.push(SingularBatch::from_transactions(
+            block.header.parent_hash,
+            l1_origin,
+            block.header.timestamp,
+            block.body.transactions.iter().filter(|tx| !tx.is_deposit()),
+        ));
+        self.epoch_starts.push(seq_num == 0);
+    }
+
+    /// Returns the compressed data of the channels.
+    pub fn channels(&self) -> Result<Vec<Bytes>, BatcherError> {
+        match self.config.batch_format {
+            BatchFormat::Singular => self.singular_channels(),
+            BatchFormat::Span => self.span_channels(),
+        }
+    }
+
+    /// Fills channels with singular batches, compressing each batch once.
+    ///
+    /// A batch is added to the current channel if the estimated compressed size of the channel
+    /// with it stays within the target, otherwise the channel is finished.
+    fn singular_channels(&self) -> Result<Vec<Bytes>, BatcherError> {
+        let mut channels = Vec::new();
+        let mut compressor = ChannelCompressor::new(self.config.compression)?;
+        let mut rlp_len = 0;
+        for batch in &self.batches {
+            let mut data = Vec::new();
+            Batch::Single(batch.clone()).encode().as_slice().encode(&mut data);
+            if data.len() > MAX_RLP_BYTES_PER_CHANNEL {
+                return Err(BatcherError::ChannelTooLarge(data.len()));
+            }
+            let fits = rlp_len + data.len() <= MAX_RLP_BYTES_PER_CHANNEL
+                && compressor.estimate_with(&data)? <= self.config.target_channel_size;
+            if !fits && rlp_len > 0 {
+                let next = ChannelCompressor::new(self.config.compression)?;
+                channels.push(core::mem::replace(&mut compressor, next).finish()?.into());
+                rlp_len = 0;
+                compressor.estimate_with(&data)?;
+            }
+            compressor.write(&data)?;
+            rlp_len += data.len();
+        }
+        if rlp_len > 0 {
+            channels.push(compressor.finish()?.into());
+        }
+        Ok(channels)
+    }
+
+    /// Fills channels with a span batch each.
+    ///
+    /// A span batch cannot be extended by a block without encoding it again, so the number of
+    /// blocks of a channel is found by doubling it while the channel stays within the target, and
+    /// then bisecting, instead of trying each block.
+    fn span_channels(&self) -> Result<Vec<Bytes>, BatcherError> {
+        let mut channels = Vec::new();
+        let mut start = 0;
+        while start < self.batches.len() {
+            let remaining = self.batches.len() - start;
+            let mut channel = self.compress_channel(start, start + 1)?;
+            // The largest count of blocks known to fit, and the smallest known not to.
+            let (mut fits, mut exceeds) = (1, remaining + 1);
+            while fits < remaining {
+                let count = (fits * 2).min(remaining);
+                match self.fitting_channel(start, start + count)? {
+                    Some(next) => (channel, fits) = (next, count),
+                    None => {
+                        exceeds = count;
+                        break;
+                    }
+                }
+            }
+            while exceeds - fits > 1 {
+                let count = fits + (exceeds - fits) / 2;
+                match self.fitting_channel(start, start + count)? {
+                    Some(next) => (channel, fits) = (next, count),
+                    None => exceeds = count,
+                }
+            }
+            channels.push(channel.into());
+            start += fits;
+        }
+        Ok(channels)
+    }
+
+    /// Returns the frames of the channels, in order.
+    ///
+    /// The id of a channel is derived from the hash of its compressed data.
+    pub fn frames(&self) -> Result<Vec<Vec<Frame>>, BatcherError> {
+        let max_data_size = self.config.max_frame_data_size()?;
+        self.channels()?
+            .into_iter()
+            .map(|channel| {
+                let mut id = ChannelId::default();
+                id.copy_from_slice(&keccak256(&channel)[..CHANNEL_ID_LENGTH]);
+                let count = channel.len().div_ceil(max_data_size);
+                if count > u16::MAX as usize + 1 {
+                    return Err(BatcherError::TooManyFrames(count));
+                }
+                Ok(channel
+                    .chunks(max_data_size)
+                    .enumerate()
+                    .map(|(number, data)| {
+                        Frame::new(
+                            id,
+                            number as u16,
+                            Bytes::copy_from_slice(data),
+                            number + 1 == count,
+                        )
+                    })
+                    .collect())
+            })
+            .collect()
+    }
+
+    /// Returns the batcher transactions.
+    ///
+    /// A transaction carries at most [`max_frames_per_tx`](BatcherConfig::max_frames_per_tx)
+    /// frames, all of the same channel.
+    pub fn build(&self) -> Result<Vec<BatcherTransaction>, BatcherError> {
+        let max_frames_per_tx = self.config.max_frames_per_tx()?;
+        let mut txs = Vec::new();
+        for frames in self.frames()? {
+            for frames in frames.chunks(max_frames_per_tx) {
+                let tx = match self.config.da_type {
+                    DataAvailabilityType::Calldata => BatcherTransaction {
+                        to: self.config.batch_inbox,
+                        input: Frame::encode_frames(frames),
+                        blobs: Vec::new(),
+                    },
+                    DataAvailabilityType::Blobs => {
+                        let mut blobs = alloc::vec![Blob::ZERO; frames.len()];
+                        for (frame, blob) in frames.iter().zip(&mut blobs) {
+                            BlobData::encode_blob_into(&Frame::encode_frames([frame]), blob)?;
+                        }
+                        BatcherTransaction {
+                            to: self.config.batch_inbox,
+                            input: Bytes::new(),
+                            blobs,
+                        }
+                    }
+                };
+                txs.push(tx);
+            }
+        }
+        Ok(txs)
+    }
+
+    /// Returns the compressed channel of the batches in `start..end`, if it stays within the
+    /// target size.
+    fn fitting_channel(&self, start: usize, end: usize) -> Result<Option<Vec<u8>>, BatcherError> {
+        match self.compress_channel(start, end) {
+            Ok(channel) if channel.len() <= self.config.target_channel_size => Ok(Some(channel)),
+            Ok(_) | Err(BatcherError::ChannelTooLarge(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns the compressed channel of the batches in `start..end`.
+    fn compress_channel(&self, start: usize, end: usize) -> Result<Vec<u8>, BatcherError> {
+        let batches = &self.batches[start..end];
+        let mut stream = Vec::new();
+        let mut push = |batch: Batch| batch.encode().as_slice().encode(&mut stream);
+        match self.config.batch_format {
+            BatchFormat::Singular => batches.iter().cloned().map(Batch::Single).for_each(push),
+            BatchFormat::Span => push(Batch::Span(SpanBatch::from_singular_batches(
+                batches,
+                self.epoch_starts[start],
+                self.config.genesis_timestamp,
+                self.config.chain_id,
+            )?)),
+        }
+        if stream.len() > MAX_RLP_BYTES_PER_CHANNEL {
+            return Err(BatcherError::ChannelTooLarge(stream.len()));
+        }
+        Ok(compress_channel_data(&stream, self.config.compression)?)
+    }
+}
+
+/// An error building batcher transactions.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum BatcherError {
+    /// The maximum frame size leaves no room for frame data, or does not fit into a blob.
+    #[error("Invalid maximum frame size: {0}")]
+    InvalidFrameSize(usize),
+    /// The maximum number of frames per transaction is zero.
+    #[error("Invalid maximum frames per transaction: {0}")]
+    InvalidFramesPerTx(usize),
+    /// The RLP batch stream of a channel exceeds [`MAX_RLP_BYTES_PER_CHANNEL`].
+    #[error("Channel too large: {0}")]
+    ChannelTooLarge(usize),
+    /// A channel needs more frames than frame numbers are available.
+    #[error("Too many frames in channel: {0}")]
+    TooManyFrames(usize),
+    /// The batches cannot be encoded into a span batch.
+    #[error(transparent)]
+    SpanBatch(#[from] SpanBatchError),
+    /// The channel cannot be compressed.
+    #[error(transparent)]
+    Compression(#[from] CompressionError),
+    /// A frame cannot be encoded into a blob.
+    #[error(transparent)]
+    BlobEncoding(#[from] BlobEncodingError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        OpTxEnvelope, TxDeposit,
+        derivation::{BatchStream, Channel, decompress_channel_data},
+    };
+    use alloc::vec;
+    use alloy_consensus::{BlockBody, Header, Sealable, SignableTransaction};
+    use alloy_primitives::{B256, Signature, U256};
+
+    const CHAIN_ID: ChainId = 5000;
+
+    fn config() -> BatcherConfig {
+        BatcherConfig {
+            chain_id: CHAIN_ID,
+            genesis_timestamp: 0,
+            batch_inbox: Address::repeat_byte(0xff),
+            batch_format: BatchFormat::Singular,
+            compression: CompressionAlgo::Zlib,
+            target_channel_size: 1000,
+            max_frame_size: 200,
+            da_type: DataAvailabilityType::Calldata,
+            max_frames_per_tx: 1,
+        }
+    }
+
+    /// Returns a builder with `count` blocks, two per epoch, each with `tx_count` transactions.
+    fn builder(config: BatcherConfig, count: u64, tx_count: u64) -> BatcherTxBuilder {
+        let mut builder = BatcherTxBuilder::new(config);
+        for number in 1..=count {
+            let mut transactions = vec![OpTxEnvelope::Deposit(TxDeposit::default().seal_slow())];
+            transactions.extend((0..tx_count).map(|nonce| {
+                let tx = TxEip1559 {
+                    chain_id: CHAIN_ID,
+                    nonce,
+                    to: TxKind::Call(Address::with_last_byte(number as u8)),
+                    value: U256::from(number),
+                    input: (0..3u8)
+                        .flat_map(|i| keccak256([number as u8, nonce as u8, i]))
+                        .collect(),
+                    ..Default::default()
+                };
+                OpTxEnvelope::from(tx.into_signed(Signature::test_signature()))
+            }));
+            let block = OpBlock {
+                header: Header {
+                    number,
+                    parent_hash: B256::with_last_byte(number as u8 - 1),
+                    timestamp: number * 2,
+                    ..Default::default()
+                },
+                body: BlockBody { transactions, ..Default::default() },
+            };
+            let epoch = (number - 1) / 2;
+            builder.add_block(
+                &block,
+                BlockNumHash::new(epoch, B256::repeat_byte(epoch as u8)),
+                (number - 1) % 2,
+            );
+        }
+        builder
+    }
+
+    /// Decodes the batches of the batcher transactions, like the derivation pipeline does.
+    fn decode(txs: &[BatcherTransaction]) -> Vec<Batch> {
+        let mut channels: Vec<Channel> = Vec::new();
+        for tx in txs {
+            for data in tx.frames_data().unwrap() {
+                for frame in Frame::parse_frames(&data).unwrap() {
+                    if channels.last().is_none_or(|channel| channel.id() != frame.id) {
+                        channels.push(Channel::new(frame.id, BlockNumHash::default()));
+                    }
+                    channels.last_mut().unwrap().add_frame(frame, BlockNumHash::default()).unwrap();
+                }
+            }
+        }
+        channels
+            .iter()
+            .flat_map(|channel| {
+                let data = channel.frame_data().unwrap();
+                let stream =
+                    decompress_channel_data(&data, MAX_RLP_BYTES_PER_CHANNEL, true).unwrap();
+                BatchStream::new(&stream)
+                    .map(|batch| Batch::decode(&batch.unwrap()).unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_build_singular_batches() {
+        let builder = builder(config(), 10, 2);
+        let txs = builder.build().unwrap();
+        let frames = builder.frames().unwrap();
+
+        assert!(frames.len() > 1);
+        assert_eq!(txs.len(), frames.iter().map(Vec::len).sum::<usize>());
+        assert!(txs.iter().all(|tx| tx.to == config().batch_inbox && !tx.is_blob_tx()));
+        assert!(txs.iter().all(|tx| tx.input.len() <= 1 + config().max_frame_size));
+        assert_eq!(
+            decode(&txs),
+            builder.batches().iter().cloned().map(Batch::Single).collect::<Vec<_>>()
+        );
+        assert!(builder.batches().iter().all(|batch| !batch.has_deposits()));
+        assert_eq!(builder.build().unwrap(), txs);
+    }
+
+    #[test]
+    fn test_build_span_batches() {
+        let config = BatcherConfig {
+            batch_format: BatchFormat::Span,
+            max_frames_per_tx: 3,
+            target_channel_size: 10_000,
+            ..config()
+        };
+        let builder = builder(config, 10, 2);
+        let txs = builder.build().unwrap();
+
+        let origins: Vec<_> = (0..5)
+            .map(|epoch| crate::BlockInfo {
+                number: epoch,
+                hash: B256::repeat_byte(epoch as u8),
+                ..Default::default()
+            })
+            .collect();
+        let batches: Vec<_> = decode(&txs)
+            .into_iter()
+            .flat_map(|batch| match batch {
+                Batch::Span(span) => span.to_singular_batches(&origins, 0, 2, CHAIN_ID).unwrap(),
+                Batch::Single(_) => panic!("expected span batch"),
+            })
+            .collect();
+        let expected: Vec<_> = builder
+            .batches()
+            .iter()
+            .map(|batch| SingularBatch { parent_hash: B256::ZERO, ..batch.clone() })
+            .collect();
+        assert_eq!(batches, expected);
+    }
+
+    #[test]
+    fn test_build_blob_transactions() {
+        let config = BatcherConfig {
+            da_type: DataAvailabilityType::Blobs,
+            max_frames_per_tx: 2,
+            ..config()
+        };
+        let builder = builder(config, 10, 2);
+        let txs = builder.build().unwrap();
+
+        assert!(txs.iter().all(|tx| tx.is_blob_tx() && tx.input.is_empty()));
+        assert!(txs.iter().all(|tx| tx.blobs.len() <= 2));
+        assert_eq!(
+            decode(&txs),
+            builder.batches().iter().cloned().map(Batch::Single).collect::<Vec<_>>()
+        );
+
+        let invalid = BatcherConfig { max_frame_size: MAX_BLOB_FRAME_SIZE + 1, ..config };
+        assert_eq!(
+            BatcherTxBuilder::new(invalid).build(),
+            Err(BatcherError::InvalidFrameSize(MAX_BLOB_FRAME_SIZE + 1))
+        );
+    }
+
+    #[test]
+    fn test_channels_target_size() {
+        for batch_format in [BatchFormat::Singular, BatchFormat::Span] {
+            #[cfg(feature = "std")]
+            let algos = [CompressionAlgo::Zlib, CompressionAlgo::Brotli];
+            #[cfg(not(feature = "std"))]
+            let algos = [CompressionAlgo::Zlib];
+            for compression in algos {
+                let config = BatcherConfig { batch_format, compression, ..config() };
+                let builder = builder(config, 20, 2);
+                let channels = builder.channels().unwrap();
+
+                assert!(channels.len() > 1);
+                assert!(channels.iter().all(|channel| channel.len() <= config.target_channel_size));
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_frames_per_tx() {
+        let config = BatcherConfig { max_frames_per_tx: 0, ..config() };
+        assert_eq!(builder(config, 1, 1).build(), Err(BatcherError::InvalidFramesPerTx(0)));
+    }
+
+    #[test]
+    fn test_calldata_transaction() {
+        let builder = builder(config(), 1, 1);
+        let tx = &builder.build().unwrap()[0];
+        let eip1559 = tx.to_eip1559(TxEip1559 { chain_id: 1, nonce: 7, ..Default::default() });
+
+        assert_eq!(eip1559.to, TxKind::Call(config().batch_inbox));
+        assert_eq!(eip1559.input, tx.input);
+        assert_eq!((eip1559.chain_id, eip1559.nonce), (1, 7));
+    }
+
+    #[test]
+    #[cfg(feature = "kzg")]
+    fn test_blob_transaction() {
+        let config = BatcherConfig { da_type: DataAvailabilityType::Blobs, ..config() };
+        let tx = &builder(config, 1, 1).build().unwrap()[0];
+        let eip4844 = tx.to_eip4844(Default::default()).unwrap();
+
+        assert_eq!(eip4844.tx.to, config.batch_inbox);
+        assert_eq!(eip4844.sidecar.blobs, tx.blobs);
+        assert_eq!(eip4844.tx.blob_versioned_hashes.len(), tx.blobs.len());
+    }
+}
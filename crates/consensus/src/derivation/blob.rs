@@ -0,0 +1,297 @@
+//! The encoding of batcher data into EIP-4844 blobs.
+//!
+//! Blob field elements must be smaller than the BLS modulus, so only 254 bits of each 32-byte
+//! field element are used: the low 6 bits of the first byte, and the remaining 31 bytes. Each
+//! round of 4 field elements encodes 127 bytes of data. The first round starts with a version byte
+//! and the 3-byte big-endian length of the data.
+//!
+//! Spec: <https://specs.optimism.io/protocol/derivation.html#blob-encoding>
+
+use alloc::vec::Vec;
+use alloy_eips::eip4844::Blob;
+use alloy_primitives::Bytes;
+
+/// The version of the blob encoding.
+pub const BLOB_ENCODING_VERSION: u8 = 0;
+
+/// The number of rounds of 4 field elements in a blob.
+pub const BLOB_ENCODING_ROUNDS: usize = 1024;
+
+/// The maximum size of the data encoded in a single blob.
+pub const MAX_BLOB_DATA_SIZE: usize = (4 * 31 + 3) * BLOB_ENCODING_ROUNDS - 4;
+
+/// The number of data bytes encoded in a round of 4 field elements.
+const ROUND_SIZE: usize = 127;
+
+/// The size of the version and length header of the encoded data.
+const HEADER_SIZE: usize = 4;
+
+/// Data encoded in one or more blobs.
+///
+/// The derivation pipeline decodes every blob of a batcher transaction on its own, so each blob
+/// must hold whole batcher transaction data, i.e. the derivation version byte followed by frames.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BlobData {
+    /// The data.
+    pub data: Bytes,
+}
+
+impl BlobData {
+    /// Creates new [`BlobData`].
+    pub const fn new(data: Bytes) -> Self {
+        Self { data }
+    }
+
+    /// Returns the number of blobs needed to encode the data.
+    pub fn blob_count(&self) -> usize {
+        self.data.len().div_ceil(MAX_BLOB_DATA_SIZE)
+    }
+
+    /// Encodes the data into blobs, splitting it into chunks of [`MAX_BLOB_DATA_SIZE`] bytes.
+    ///
+    /// Empty data is encoded into no blobs.
+    pub fn to_blobs(&self) -> Vec<Blob> {
+        let mut blobs = alloc::vec![Blob::ZERO; self.blob_count()];
+        for (chunk, blob) in self.data.chunks(MAX_BLOB_DATA_SIZE).zip(&mut blobs) {
+            encode_chunk(chunk, blob);
+        }
+        blobs
+    }
+
+    /// Decodes the data of the given blobs and concatenates it.
+    pub fn from_blobs<'a>(
+        blobs: impl IntoIterator<Item = &'a Blob>,
+    ) -> Result<Self, BlobDecodingError> {
+        let mut data = Vec::new();
+        for blob in blobs {
+            data.extend_from_slice(&Self::decode_blob(blob)?);
+        }
+        Ok(Self::new(data.into()))
+    }
+
+    /// Encodes data of at most [`MAX_BLOB_DATA_SIZE`] bytes into a single blob.
+    pub fn encode_blob(data: &[u8]) -> Result<Blob, BlobEncodingError> {
+        let mut blob = Blob::ZERO;
+        Self::encode_blob_into(data, &mut blob)?;
+        Ok(blob)
+    }
+
+    /// Encodes data of at most [`MAX_BLOB_DATA_SIZE`] bytes into the given zeroed blob.
+    pub(crate) fn encode_blob_into(data: &[u8], blob: &mut Blob) -> Result<(), BlobEncodingError> {
+        if data.len() > MAX_BLOB_DATA_SIZE {
+            return Err(BlobEncodingError::DataTooLarge(data.len()));
+        }
+        encode_chunk(data, blob);
+        Ok(())
+    }
+
+    /// Decodes the data of a single blob.
+    pub fn decode_blob(blob: &Blob) -> Result<Bytes, BlobDecodingError> {
+        if let Some(index) = blob.chunks_exact(32).position(|fe| fe[0] & 0b1100_0000 != 0) {
+            return Err(BlobDecodingError::InvalidFieldElement(index));
+        }
+
+        let mut out = Vec::with_capacity(BLOB_ENCODING_ROUNDS * ROUND_SIZE);
+        for fes in blob.chunks_exact(128) {
+            let x = (fes[0] & 0b0011_1111) | ((fes[32] & 0b0011_0000) << 2);
+            let y = (fes[32] & 0b0000_1111) | ((fes[96] & 0b0000_1111) << 4);
+            let z = (fes[64] & 0b0011_1111) | ((fes[96] & 0b0011_0000) << 2);
+            out.extend_from_slice(&fes[1..32]);
+            out.push(x);
+            out.extend_from_slice(&fes[33..64]);
+            out.push(y);
+            out.extend_from_slice(&fes[65..96]);
+            out.push(z);
+            out.extend_from_slice(&fes[97..128]);
+        }
+
+        if out[0] != BLOB_ENCODING_VERSION {
+            return Err(BlobDecodingError::UnsupportedVersion(out[0]));
+        }
+        let len = u32::from_be_bytes([0, out[1], out[2], out[3]]) as usize;
+        if len > MAX_BLOB_DATA_SIZE {
+            return Err(BlobDecodingError::InvalidLength(len));
+        }
+        let end = HEADER_SIZE + len;
+        if let Some(pos) = out[end..].iter().position(|byte| *byte != 0) {
+            return Err(BlobDecodingError::NonZeroPadding(len + pos));
+        }
+        Ok(Bytes::copy_from_slice(&out[HEADER_SIZE..end]))
+    }
+
+    /// Encodes the data into blobs and builds the sidecar of a blob transaction with the KZG
+    /// commitments and proofs of the blobs.
+    #[cfg(feature = "kzg")]
+    pub fn to_sidecar(
+        &self,
+    ) -> Result<
+        alloy_eips::eip4844::BlobTransactionSidecar,
+        alloy_eips::eip4844::BlobTransactionValidationError,
+    > {
+        blob_sidecar(self.to_blobs())
+    }
+}
+
+impl From<Bytes> for BlobData {
+    fn from(data: Bytes) -> Self {
+        Self::new(data)
+    }
+}
+
+/// Builds the sidecar of a blob transaction with the KZG commitments and proofs of the blobs.
+#[cfg(feature = "kzg")]
+pub(crate) fn blob_sidecar(
+    blobs: Vec<Blob>,
+) -> Result<
+    alloy_eips::eip4844::BlobTransactionSidecar,
+    alloy_eips::eip4844::BlobTransactionValidationError,
+> {
+    use alloy_eips::eip4844::{Bytes48, env_settings::EnvKzgSettings};
+
+    let settings = EnvKzgSettings::Default.get();
+    let mut commitments = Vec::with_capacity(blobs.len());
+    let mut proofs = Vec::with_capacity(blobs.len());
+    for blob in &blobs {
+        let blob = blob.0.into();
+        let commitment = settings.blob_to_kzg_commitment(&blob)?.to_bytes();
+        let proof = settings.compute_blob_kzg_proof(&blob, &commitment)?.to_bytes();
+        commitments.push(Bytes48::from(*commitment));
+        proofs.push(Bytes48::from(*proof));
+    }
+    Ok(alloy_eips::eip4844::BlobTransactionSidecar::new(blobs, commitments, proofs))
+}
+
+/// Encodes data of at most [`MAX_BLOB_DATA_SIZE`] bytes into a zeroed blob.
+fn encode_chunk(data: &[u8], blob: &mut Blob) {
+    debug_assert!(data.len() <= MAX_BLOB_DATA_SIZE);
+
+    let mut input = Vec::with_capacity(HEADER_SIZE + data.len());
+    input.push(BLOB_ENCODING_VERSION);
+    input.extend_from_slice(&(data.len() as u32).to_be_bytes()[1..]);
+    input.extend_from_slice(data);
+
+    for (chunk, fes) in input.chunks(ROUND_SIZE).zip(blob.chunks_exact_mut(128)) {
+        let mut round = [0u8; ROUND_SIZE];
+        round[..chunk.len()].copy_from_slice(chunk);
+        let (x, y, z) = (round[31], round[63], round[95]);
+
+        fes[0] = x & 0b0011_1111;
+        fes[1..32].copy_from_slice(&round[..31]);
+        fes[32] = (y & 0b0000_1111) | ((x & 0b1100_0000) >> 2);
+        fes[33..64].copy_from_slice(&round[32..63]);
+        fes[64] = z & 0b0011_1111;
+        fes[65..96].copy_from_slice(&round[64..95]);
+        fes[96] = ((z & 0b1100_0000) >> 2) | ((y & 0b1111_0000) >> 4);
+        fes[97..128].copy_from_slice(&round[96..]);
+    }
+}
+
+/// An error encoding data into a blob.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum BlobEncodingError {
+    /// The data is too large to fit into a blob.
+    #[error("Blob data too large: {0}")]
+    DataTooLarge(usize),
+}
+
+/// An error decoding the data of a blob.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum BlobDecodingError {
+    /// The two highest bits of a field element are set.
+    #[error("Invalid field element: {0}")]
+    InvalidFieldElement(usize),
+    /// The encoding version is not supported.
+    #[error("Unsupported blob encoding version: {0}")]
+    UnsupportedVersion(u8),
+    /// The encoded length exceeds [`MAX_BLOB_DATA_SIZE`].
+    #[error("Invalid blob data length: {0}")]
+    InvalidLength(usize),
+    /// The blob contains non-zero data after the encoded length, at the given data offset.
+    #[error("Non-zero blob padding at offset {0}")]
+    NonZeroPadding(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_eips::eip4844::BYTES_PER_BLOB;
+
+    #[test]
+    fn test_blob_roundtrip() {
+        for len in [0, 1, 27, 28, 127, 128, 1000, MAX_BLOB_DATA_SIZE] {
+            let data: Vec<u8> = (0..len).map(|i| (i * 7 + 3) as u8).collect();
+            let blob = BlobData::encode_blob(&data).unwrap();
+            assert_eq!(BlobData::decode_blob(&blob).unwrap(), data);
+        }
+        assert_eq!(BlobData::decode_blob(&Blob::ZERO).unwrap(), Bytes::new());
+        assert_eq!(
+            BlobData::encode_blob(&[0; MAX_BLOB_DATA_SIZE + 1]),
+            Err(BlobEncodingError::DataTooLarge(MAX_BLOB_DATA_SIZE + 1))
+        );
+    }
+
+    #[test]
+    fn test_blob_layout() {
+        let blob = BlobData::encode_blob(&[0xff; 28]).unwrap();
+
+        assert_eq!(blob[0], 0b0011_1111);
+        assert_eq!(blob[1..5], [BLOB_ENCODING_VERSION, 0, 0, 28]);
+        assert_eq!(blob[5..32], [0xff; 27]);
+        assert_eq!(blob[32], 0b0011_0000);
+        assert!(blob[33..].iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn test_decode_invalid_blob() {
+        let blob = BlobData::encode_blob(&[1, 2, 3]).unwrap();
+
+        let mut invalid = blob;
+        invalid[64] = 0b1000_0000;
+        assert_eq!(BlobData::decode_blob(&invalid), Err(BlobDecodingError::InvalidFieldElement(2)));
+
+        let mut invalid = blob;
+        invalid[1] = 1;
+        assert_eq!(BlobData::decode_blob(&invalid), Err(BlobDecodingError::UnsupportedVersion(1)));
+
+        let mut invalid = blob;
+        invalid[2] = 0xff;
+        assert_eq!(
+            BlobData::decode_blob(&invalid),
+            Err(BlobDecodingError::InvalidLength(0xff0003))
+        );
+
+        let mut invalid = blob;
+        invalid[BYTES_PER_BLOB - 1] = 1;
+        assert!(matches!(
+            BlobData::decode_blob(&invalid),
+            Err(BlobDecodingError::NonZeroPadding(_))
+        ));
+        let mut invalid = blob;
+        invalid[8] = 1;
+        assert_eq!(BlobData::decode_blob(&invalid), Err(BlobDecodingError::NonZeroPadding(3)));
+    }
+
+    #[test]
+    fn test_blob_data_roundtrip() {
+        let data = BlobData::new(vec![0xab; MAX_BLOB_DATA_SIZE + 10].into());
+        let blobs = data.to_blobs();
+
+        assert_eq!(blobs.len(), data.blob_count());
+        assert_eq!(blobs.len(), 2);
+        assert_eq!(BlobData::from_blobs(&blobs), Ok(data));
+        assert!(BlobData::default().to_blobs().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "kzg")]
+    fn test_blob_data_sidecar() {
+        let data = BlobData::new(vec![1; 100].into());
+        let sidecar = data.to_sidecar().unwrap();
+
+        assert_eq!(sidecar.blobs, data.to_blobs());
+        let hashes = sidecar.versioned_hashes().collect::<Vec<_>>();
+        let settings = alloy_eips::eip4844::env_settings::EnvKzgSettings::Default;
+        assert!(sidecar.validate(&hashes, settings.get()).is_ok());
+    }
+}
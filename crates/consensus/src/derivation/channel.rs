@@ -0,0 +1,235 @@
+//! Assembly of [`Frame`]s into channels.
+//!
+//! Spec: <https://specs.optimism.io/protocol/derivation.html#channel-format>
+
+use super::{ChannelId, Frame};
+use alloc::{collections::BTreeMap, vec::Vec};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::Bytes;
+
+/// A channel being assembled from its [`Frame`]s.
+///
+/// Frames may arrive out of order. The channel is [ready](Self::is_ready) once the last frame and
+/// all frames before it were added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Channel {
+    /// The id of the channel.
+    id: ChannelId,
+    /// The L1 block in which the first frame of the channel was included.
+    open_block: BlockNumHash,
+    /// The highest L1 block in which a frame of the channel was included.
+    highest_l1_inclusion_block: BlockNumHash,
+    /// The number of the last frame, once it was added.
+    last_frame_number: Option<u16>,
+    /// The frames of the channel, by frame number.
+    frames: BTreeMap<u16, Frame>,
+    /// The total [size](Frame::size) of the frames.
+    size: usize,
+}
+
+impl Channel {
+    /// Creates a new, empty [`Channel`] opened in the given L1 block.
+    pub const fn new(id: ChannelId, open_block: BlockNumHash) -> Self {
+        Self {
+            id,
+            open_block,
+            highest_l1_inclusion_block: open_block,
+            last_frame_number: None,
+            frames: BTreeMap::new(),
+            size: 0,
+        }
+    }
+
+    /// Returns the id of the channel.
+    pub const fn id(&self) -> ChannelId {
+        self.id
+    }
+
+    /// Returns the L1 block in which the channel was opened.
+    pub const fn open_block(&self) -> BlockNumHash {
+        self.open_block
+    }
+
+    /// Returns the highest L1 block in which a frame of the channel was included.
+    pub const fn highest_l1_inclusion_block(&self) -> BlockNumHash {
+        self.highest_l1_inclusion_block
+    }
+
+    /// Returns the total size of the frames of the channel.
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of frames in the channel.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns true if the channel has no frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Returns true if the last frame of the channel was added.
+    pub const fn is_closed(&self) -> bool {
+        self.last_frame_number.is_some()
+    }
+
+    /// Returns true if the channel timed out at the given L1 block number, i.e. more than
+    /// `channel_timeout` L1 blocks passed since the channel was opened.
+    pub const fn is_timed_out(&self, l1_block_number: u64, channel_timeout: u64) -> bool {
+        l1_block_number > self.open_block.number + channel_timeout
+    }
+
+    /// Returns true if all frames of the channel were added.
+    pub fn is_ready(&self) -> bool {
+        self.last_frame_number.is_some_and(|last| self.frames.len() == last as usize + 1)
+    }
+
+    /// Adds a frame, included in the given L1 block, to the channel.
+    ///
+    /// When the last frame is added, frames with a higher number are dropped.
+    pub fn add_frame(&mut self, frame: Frame, l1_block: BlockNumHash) -> Result<(), ChannelError> {
+        if frame.id != self.id {
+            return Err(ChannelError::IdMismatch { expected: self.id, actual: frame.id });
+        }
+        if self.frames.contains_key(&frame.number) {
+            return Err(ChannelError::DuplicateFrame(frame.number));
+        }
+        if let Some(last) = self.last_frame_number {
+            if frame.is_last {
+                return Err(ChannelError::AlreadyClosed);
+            }
+            if frame.number > last {
+                return Err(ChannelError::FrameBeyondEnd { number: frame.number, last });
+            }
+        }
+
+        if frame.is_last {
+            self.last_frame_number = Some(frame.number);
+            let pruned = self.frames.split_off(&frame.number);
+            self.size -= pruned.values().map(Frame::size).sum::<usize>();
+        }
+        if l1_block.number > self.highest_l1_inclusion_block.number {
+            self.highest_l1_inclusion_block = l1_block;
+        }
+        self.size += frame.size();
+        self.frames.insert(frame.number, frame);
+        Ok(())
+    }
+
+    /// Returns the concatenated frame data of the channel, if it is [ready](Self::is_ready).
+    pub fn frame_data(&self) -> Option<Bytes> {
+        if !self.is_ready() {
+            return None;
+        }
+        let mut data = Vec::with_capacity(self.size);
+        for frame in self.frames.values() {
+            data.extend_from_slice(&frame.data);
+        }
+        Some(data.into())
+    }
+}
+
+/// An error adding a [`Frame`] to a [`Channel`].
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelError {
+    /// The frame belongs to another channel.
+    #[error("Frame of channel {actual:?} added to channel {expected:?}")]
+    IdMismatch {
+        /// The id of the channel.
+        expected: ChannelId,
+        /// The channel id of the frame.
+        actual: ChannelId,
+    },
+    /// A frame with the same number was already added.
+    #[error("Duplicate frame {0}")]
+    DuplicateFrame(u16),
+    /// A last frame was added to a closed channel.
+    #[error("Channel already closed")]
+    AlreadyClosed,
+    /// The frame number is beyond the last frame of the channel.
+    #[error("Frame {number} beyond last frame {last}")]
+    FrameBeyondEnd {
+        /// The frame number.
+        number: u16,
+        /// The number of the last frame.
+        last: u16,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derivation::FRAME_OVERHEAD;
+    use alloy_primitives::B256;
+
+    const ID: ChannelId = [7; 16];
+
+    fn block(number: u64) -> BlockNumHash {
+        BlockNumHash { number, hash: B256::with_last_byte(number as u8) }
+    }
+
+    fn frame(number: u16, data: &'static [u8], is_last: bool) -> Frame {
+        Frame::new(ID, number, Bytes::from_static(data), is_last)
+    }
+
+    #[test]
+    fn test_out_of_order_frames() {
+        let mut channel = Channel::new(ID, block(10));
+        channel.add_frame(frame(2, b"c", true), block(10)).unwrap();
+        assert!(channel.is_closed());
+        assert!(!channel.is_ready());
+        assert_eq!(channel.frame_data(), None);
+
+        channel.add_frame(frame(0, b"a", false), block(12)).unwrap();
+        channel.add_frame(frame(1, b"b", false), block(11)).unwrap();
+        assert!(channel.is_ready());
+        assert_eq!(channel.frame_data(), Some(Bytes::from_static(b"abc")));
+        assert_eq!(channel.highest_l1_inclusion_block(), block(12));
+        assert_eq!(channel.size(), 3 + 3 * FRAME_OVERHEAD);
+    }
+
+    #[test]
+    fn test_invalid_frames() {
+        let mut channel = Channel::new(ID, block(10));
+        channel.add_frame(frame(0, b"a", false), block(10)).unwrap();
+
+        assert_eq!(
+            channel.add_frame(frame(0, b"a", false), block(10)),
+            Err(ChannelError::DuplicateFrame(0))
+        );
+        assert_eq!(
+            channel.add_frame(Frame::new([8; 16], 1, Bytes::new(), false), block(10)),
+            Err(ChannelError::IdMismatch { expected: ID, actual: [8; 16] })
+        );
+
+        channel.add_frame(frame(1, b"b", true), block(10)).unwrap();
+        assert_eq!(
+            channel.add_frame(frame(2, b"c", false), block(10)),
+            Err(ChannelError::FrameBeyondEnd { number: 2, last: 1 })
+        );
+        assert_eq!(
+            channel.add_frame(frame(3, b"d", true), block(10)),
+            Err(ChannelError::AlreadyClosed)
+        );
+    }
+
+    #[test]
+    fn test_last_frame_prunes_higher_frames() {
+        let mut channel = Channel::new(ID, block(10));
+        channel.add_frame(frame(3, b"d", false), block(10)).unwrap();
+        channel.add_frame(frame(0, b"a", true), block(10)).unwrap();
+
+        assert_eq!(channel.len(), 1);
+        assert_eq!(channel.size(), 1 + FRAME_OVERHEAD);
+        assert_eq!(channel.frame_data(), Some(Bytes::from_static(b"a")));
+    }
+
+    #[test]
+    fn test_timeout() {
+        let channel = Channel::new(ID, block(10));
+        assert!(!channel.is_timed_out(60, 50));
+        assert!(channel.is_timed_out(61, 50));
+    }
+}
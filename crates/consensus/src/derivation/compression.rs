@@ -0,0 +1,491 @@
+//! Compression of the RLP batch stream into channel data, and decompression back.
+//!
+//! Channel data is compressed with zlib, or since Fjord optionally with brotli, in which case it
+//! is prefixed with the [`CHANNEL_VERSION_BROTLI`] byte.
+//!
+//! Spec: <https://specs.optimism.io/fjord/derivation.html#brotli-channel-compression>
+
+use alloc::vec::Vec;
+use alloy_primitives::Bytes;
+use alloy_rlp::Decodable;
+
+/// The maximum size of the decompressed data of a channel before Fjord.
+pub const MAX_RLP_BYTES_PER_CHANNEL: usize = 10_000_000;
+
+/// The maximum size of the decompressed data of a channel since Fjord.
+pub const FJORD_MAX_RLP_BYTES_PER_CHANNEL: usize = 100_000_000;
+
+/// The version byte of brotli compressed channel data.
+pub const CHANNEL_VERSION_BROTLI: u8 = 0x01;
+
+/// The zlib compression level used to compress channels.
+const ZLIB_BEST_COMPRESSION: u8 = 9;
+
+/// The brotli quality used to compress channels.
+#[cfg(feature = "std")]
+const BROTLI_QUALITY: u32 = 10;
+
+/// The base 2 logarithm of the brotli window size used to compress channels.
+#[cfg(feature = "std")]
+const BROTLI_LGWIN: u32 = 22;
+
+/// The zlib compression method (CM) of deflate.
+const ZLIB_DEFLATE_COMPRESSION_METHOD: u8 = 8;
+
+/// The reserved zlib compression method (CM).
+const ZLIB_RESERVED_COMPRESSION_METHOD: u8 = 15;
+
+/// The compression algorithm of channel data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompressionAlgo {
+    /// Zlib compression.
+    Zlib,
+    /// Brotli compression, with the [`CHANNEL_VERSION_BROTLI`] prefix.
+    Brotli,
+}
+
+impl CompressionAlgo {
+    /// Detects the compression algorithm from the first byte of channel data.
+    pub fn detect(data: &[u8]) -> Result<Self, CompressionError> {
+        let &first = data.first().ok_or(CompressionError::Empty)?;
+        match first & 0x0f {
+            ZLIB_DEFLATE_COMPRESSION_METHOD | ZLIB_RESERVED_COMPRESSION_METHOD => Ok(Self::Zlib),
+            _ if first == CHANNEL_VERSION_BROTLI => Ok(Self::Brotli),
+            _ => Err(CompressionError::UnsupportedVersion(first)),
+        }
+    }
+}
+
+/// Decompresses channel data, returning at most `max_len` bytes of the RLP batch stream.
+///
+/// Data beyond `max_len` is discarded, like the derivation pipeline does, so the stream may end in
+/// a truncated batch. Likewise, truncated or corrupt compressed data yields the stream decoded up
+/// to the error, since the derivation pipeline reads the channel lazily and keeps the batches
+/// read before it. Brotli data is rejected before Fjord.
+pub fn decompress_channel_data(
+    data: &[u8],
+    max_len: usize,
+    is_fjord_active: bool,
+) -> Result<Vec<u8>, CompressionError> {
+    match CompressionAlgo::detect(data)? {
+        CompressionAlgo::Zlib => Ok(decompress_zlib(data, max_len)),
+        CompressionAlgo::Brotli if !is_fjord_active => Err(CompressionError::BrotliBeforeFjord),
+        CompressionAlgo::Brotli => Ok(decompress_brotli(&data[1..], max_len)),
+    }
+}
+
+/// Inflates zlib data into at most `max_len` bytes, stopping at the first error.
+fn decompress_zlib(data: &[u8], max_len: usize) -> Vec<u8> {
+    use miniz_oxide::{
+        DataFormat, MZFlush, MZStatus,
+        inflate::stream::{InflateState, inflate},
+    };
+
+    let mut state = InflateState::new_boxed(DataFormat::Zlib);
+    let mut input = data;
+    let mut out = Vec::new();
+    let mut buf = alloc::vec![0; 32 * 1024];
+    loop {
+        let result = inflate(&mut state, input, &mut buf, MZFlush::None);
+        input = &input[result.bytes_consumed..];
+        out.extend_from_slice(&buf[..result.bytes_written]);
+        if out.len() >= max_len {
+            out.truncate(max_len);
+            return out;
+        }
+        let progress = result.bytes_consumed > 0 || result.bytes_written > 0;
+        match result.status {
+            Ok(MZStatus::Ok) if progress => {}
+            _ => return out,
+        }
+    }
+}
+
+/// Compresses the RLP batch stream of a channel with the given algorithm.
+///
+/// Zlib data is compressed at the best compression level, brotli data at quality 10 and prefixed
+/// with the [`CHANNEL_VERSION_BROTLI`] byte. The output is deterministic. Brotli compression
+/// requires the `std` feature, unlike decompression.
+pub fn compress_channel_data(
+    data: &[u8],
+    algo: CompressionAlgo,
+) -> Result<Vec<u8>, CompressionError> {
+    match algo {
+        CompressionAlgo::Zlib => {
+            Ok(miniz_oxide::deflate::compress_to_vec_zlib(data, ZLIB_BEST_COMPRESSION))
+        }
+        CompressionAlgo::Brotli => compress_brotli(data),
+    }
+}
+
+#[cfg(feature = "std")]
+fn compress_brotli(data: &[u8]) -> Result<Vec<u8>, CompressionError> {
+    use std::io::Write;
+
+    let mut out = alloc::vec![CHANNEL_VERSION_BROTLI];
+    let mut writer = brotli::CompressorWriter::new(&mut out, 4096, BROTLI_QUALITY, BROTLI_LGWIN);
+    writer.write_all(data).map_err(|_| CompressionError::Brotli)?;
+    drop(writer);
+    Ok(out)
+}
+
+#[cfg(not(feature = "std"))]
+const fn compress_brotli(_data: &[u8]) -> Result<Vec<u8>, CompressionError> {
+    Err(CompressionError::BrotliUnsupported)
+}
+
+/// Decompresses brotli data into at most `max_len` bytes, stopping at the first error.
+///
+/// Uses the streaming decoder with [`BrotliAlloc`], as the decoder only provides an allocator
+/// with `std`.
+fn decompress_brotli(data: &[u8], max_len: usize) -> Vec<u8> {
+    use brotli::{BrotliDecompressStream, BrotliResult, BrotliState};
+
+    let mut state = BrotliState::new(BrotliAlloc, BrotliAlloc, BrotliAlloc);
+    let (mut available_in, mut input_offset, mut total_out) = (data.len(), 0, 0);
+    let mut out = Vec::new();
+    let mut buf = alloc::vec![0; 32 * 1024];
+    loop {
+        let (mut available_out, mut output_offset) = (buf.len(), 0);
+        let result = BrotliDecompressStream(
+            &mut available_in,
+            &mut input_offset,
+            data,
+            &mut available_out,
+            &mut output_offset,
+            &mut buf,
+            &mut total_out,
+            &mut state,
+        );
+        out.extend_from_slice(&buf[..output_offset]);
+        if out.len() >= max_len {
+            out.truncate(max_len);
+            return out;
+        }
+        if !matches!(result, BrotliResult::NeedsMoreOutput) {
+            return out;
+        }
+    }
+}
+
+/// A heap allocator for the brotli decoder.
+#[derive(Debug, Clone, Copy)]
+struct BrotliAlloc;
+
+/// Memory allocated by [`BrotliAlloc`].
+#[derive(Debug, Default)]
+struct BrotliMemory<T>(alloc::boxed::Box<[T]>);
+
+impl<T> brotli::SliceWrapper<T> for BrotliMemory<T> {
+    fn slice(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> brotli::SliceWrapperMut<T> for BrotliMemory<T> {
+    fn slice_mut(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+}
+
+impl<T: Clone + Default> brotli::Allocator<T> for BrotliAlloc {
+    type AllocatedMemory = BrotliMemory<T>;
+
+    fn alloc_cell(&mut self, len: usize) -> Self::AllocatedMemory {
+        BrotliMemory(alloc::vec![T::default(); len].into_boxed_slice())
+    }
+
+    fn free_cell(&mut self, _data: Self::AllocatedMemory) {}
+}
+
+/// Compresses a channel incrementally, estimating its compressed size before each write.
+///
+/// Like the shadow compressor of op-batcher, data is first written to a shadow compressor that is
+/// flushed after every write to measure the compressed size, and only then to the main
+/// compressor. Once an estimate exceeds the target, the shadow contains data that the main
+/// compressor does not, so the channel must be finished.
+pub(super) struct ChannelCompressor {
+    main: StreamCompressor,
+    shadow: StreamCompressor,
+}
+
+impl ChannelCompressor {
+    /// Creates a new [`ChannelCompressor`] with the given algorithm.
+    pub(super) fn new(algo: CompressionAlgo) -> Result<Self, CompressionError> {
+        Ok(Self { main: StreamCompressor::new(algo)?, shadow: StreamCompressor::new(algo)? })
+    }
+
+    /// Writes `data` to the shadow compressor and returns the estimated compressed size of the
+    /// channel with it.
+    pub(super) fn estimate_with(&mut self, data: &[u8]) -> Result<usize, CompressionError> {
+        self.shadow.write(data, true)?;
+        Ok(self.shadow.len())
+    }
+
+    /// Writes `data` to the channel.
+    pub(super) fn write(&mut self, data: &[u8]) -> Result<(), CompressionError> {
+        self.main.write(data, false)
+    }
+
+    /// Finishes the channel, returning its compressed data.
+    pub(super) fn finish(self) -> Result<Vec<u8>, CompressionError> {
+        self.main.finish()
+    }
+}
+
+/// A streaming zlib or brotli compressor.
+enum StreamCompressor {
+    Zlib {
+        compressor: alloc::boxed::Box<miniz_oxide::deflate::core::CompressorOxide>,
+        out: Vec<u8>,
+    },
+    #[cfg(feature = "std")]
+    Brotli(alloc::boxed::Box<brotli::CompressorWriter<Vec<u8>>>),
+}
+
+impl StreamCompressor {
+    fn new(algo: CompressionAlgo) -> Result<Self, CompressionError> {
+        match algo {
+            CompressionAlgo::Zlib => {
+                let flags = miniz_oxide::deflate::core::create_comp_flags_from_zip_params(
+                    ZLIB_BEST_COMPRESSION.into(),
+                    1,
+                    0,
+                );
+                Ok(Self::Zlib {
+                    compressor: alloc::boxed::Box::new(
+                        miniz_oxide::deflate::core::CompressorOxide::new(flags),
+                    ),
+                    out: Vec::new(),
+                })
+            }
+            #[cfg(feature = "std")]
+            CompressionAlgo::Brotli => {
+                Ok(Self::Brotli(alloc::boxed::Box::new(brotli::CompressorWriter::new(
+                    alloc::vec![CHANNEL_VERSION_BROTLI],
+                    4096,
+                    BROTLI_QUALITY,
+                    BROTLI_LGWIN,
+                ))))
+            }
+            #[cfg(not(feature = "std"))]
+            CompressionAlgo::Brotli => Err(CompressionError::BrotliUnsupported),
+        }
+    }
+
+    /// Returns the size of the compressed data output so far.
+    fn len(&self) -> usize {
+        match self {
+            Self::Zlib { out, .. } => out.len(),
+            #[cfg(feature = "std")]
+            Self::Brotli(writer) => writer.get_ref().len(),
+        }
+    }
+
+    /// Writes `data`, flushing the compressed data of everything written so far if `flush`.
+    fn write(&mut self, data: &[u8], flush: bool) -> Result<(), CompressionError> {
+        match self {
+            Self::Zlib { compressor, out } => {
+                let flush =
+                    if flush { miniz_oxide::MZFlush::Sync } else { miniz_oxide::MZFlush::None };
+                deflate_into(compressor, data, out, flush);
+                Ok(())
+            }
+            #[cfg(feature = "std")]
+            Self::Brotli(writer) => {
+                use std::io::Write;
+
+                writer.write_all(data).map_err(|_| CompressionError::Brotli)?;
+                if flush {
+                    writer.flush().map_err(|_| CompressionError::Brotli)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Finishes the stream, returning the compressed data.
+    fn finish(self) -> Result<Vec<u8>, CompressionError> {
+        match self {
+            Self::Zlib { mut compressor, mut out } => {
+                deflate_into(&mut compressor, &[], &mut out, miniz_oxide::MZFlush::Finish);
+                Ok(out)
+            }
+            #[cfg(feature = "std")]
+            Self::Brotli(writer) => Ok(writer.into_inner()),
+        }
+    }
+}
+
+/// Deflates all of `input` into `out` with the given flush mode.
+fn deflate_into(
+    compressor: &mut miniz_oxide::deflate::core::CompressorOxide,
+    mut input: &[u8],
+    out: &mut Vec<u8>,
+    flush: miniz_oxide::MZFlush,
+) {
+    let mut buf = alloc::vec![0; 32 * 1024];
+    loop {
+        let result = miniz_oxide::deflate::stream::deflate(compressor, input, &mut buf, flush);
+        input = &input[result.bytes_consumed..];
+        out.extend_from_slice(&buf[..result.bytes_written]);
+        match result.status {
+            Ok(miniz_oxide::MZStatus::Ok)
+                if !input.is_empty() || result.bytes_written == buf.len() => {}
+            _ => return,
+        }
+    }
+}
+
+/// An iterator over the RLP encoded batches of a decompressed channel.
+///
+/// Each item of the stream is an RLP byte string containing a batch. The iterator ends after the
+/// first item that fails to decode.
+#[derive(Debug, Clone)]
+pub struct BatchStream<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> BatchStream<'a> {
+    /// Creates a new [`BatchStream`] over the decompressed channel data.
+    pub const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+}
+
+impl Iterator for BatchStream<'_> {
+    type Item = Result<Bytes, alloy_rlp::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let item = Bytes::decode(&mut self.data);
+        if item.is_err() {
+            self.data = &[];
+        }
+        Some(item)
+    }
+}
+
+/// An error compressing or decompressing channel data.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionError {
+    /// The channel data is empty.
+    #[error("Empty channel data")]
+    Empty,
+    /// The channel data starts with an unsupported version byte.
+    #[error("Unsupported channel compression version: {0}")]
+    UnsupportedVersion(u8),
+    /// The brotli data failed to compress.
+    #[error("Failed to compress brotli channel data")]
+    Brotli,
+    /// Brotli channel data before Fjord.
+    #[error("Brotli channel data before Fjord")]
+    BrotliBeforeFjord,
+    /// Brotli compression requires the `std` feature.
+    #[error("Brotli compression requires the std feature")]
+    BrotliUnsupported,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_primitives::hex;
+    use alloy_rlp::Encodable;
+
+    fn batch_stream() -> Vec<u8> {
+        let mut stream = Vec::new();
+        Bytes::from_static(b"first batch").encode(&mut stream);
+        Bytes::from_static(b"second batch").encode(&mut stream);
+        stream
+    }
+
+    #[test]
+    fn test_decompress_zlib() {
+        let stream = batch_stream();
+        let compressed = miniz_oxide::deflate::compress_to_vec_zlib(&stream, 9);
+
+        assert_eq!(CompressionAlgo::detect(&compressed), Ok(CompressionAlgo::Zlib));
+        assert_eq!(decompress_channel_data(&compressed, usize::MAX, false), Ok(stream.clone()));
+        assert_eq!(decompress_channel_data(&compressed, 5, false), Ok(stream[..5].to_vec()));
+    }
+
+    #[test]
+    fn test_decompress_truncated_zlib() {
+        let mut stream = batch_stream();
+        // Pad the stream with a large incompressible batch, so that truncating the compressed data
+        // keeps the first batches intact.
+        Bytes::from((0..4096u32).map(|i| (i * 7919 % 251) as u8).collect::<Vec<_>>())
+            .encode(&mut stream);
+        let compressed = miniz_oxide::deflate::compress_to_vec_zlib(&stream, 9);
+
+        let partial =
+            decompress_channel_data(&compressed[..compressed.len() / 2], usize::MAX, false)
+                .unwrap();
+        assert!(!partial.is_empty() && partial.len() < stream.len());
+        assert_eq!(partial, stream[..partial.len()]);
+
+        let batches: Vec<_> = BatchStream::new(&partial).collect();
+        assert_eq!(batches[0], Ok(Bytes::from_static(b"first batch")));
+        assert_eq!(batches[1], Ok(Bytes::from_static(b"second batch")));
+        assert!(batches[2].is_err());
+    }
+
+    #[test]
+    fn test_decompress_brotli() {
+        let stream = batch_stream();
+        // The batch stream compressed at quality 6, with the version byte.
+        let compressed = hex!("011b180000c4636c5dfbe2e83b8f2464115ab281b4d643acf32f00");
+
+        assert_eq!(CompressionAlgo::detect(&compressed), Ok(CompressionAlgo::Brotli));
+        assert_eq!(
+            decompress_channel_data(&compressed, usize::MAX, false),
+            Err(CompressionError::BrotliBeforeFjord)
+        );
+        let partial =
+            decompress_channel_data(&compressed[..compressed.len() - 2], usize::MAX, true).unwrap();
+        assert_eq!(partial, stream[..partial.len()]);
+        assert_eq!(decompress_channel_data(&compressed, usize::MAX, true), Ok(stream));
+    }
+
+    #[test]
+    fn test_compress_roundtrip() {
+        let stream = batch_stream();
+        let zlib = compress_channel_data(&stream, CompressionAlgo::Zlib).unwrap();
+
+        assert_eq!(CompressionAlgo::detect(&zlib), Ok(CompressionAlgo::Zlib));
+        assert_eq!(decompress_channel_data(&zlib, usize::MAX, false), Ok(stream.clone()));
+
+        #[cfg(feature = "std")]
+        {
+            let brotli = compress_channel_data(&stream, CompressionAlgo::Brotli).unwrap();
+            assert_eq!(CompressionAlgo::detect(&brotli), Ok(CompressionAlgo::Brotli));
+            assert_eq!(decompress_channel_data(&brotli, usize::MAX, true), Ok(stream));
+        }
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        assert_eq!(decompress_channel_data(&[], 1, true), Err(CompressionError::Empty));
+        assert_eq!(
+            decompress_channel_data(&[2], 1, true),
+            Err(CompressionError::UnsupportedVersion(2))
+        );
+    }
+
+    #[test]
+    fn test_batch_stream() {
+        let stream = batch_stream();
+        let batches: Vec<_> = BatchStream::new(&stream).collect();
+        assert_eq!(
+            batches,
+            vec![Ok(Bytes::from_static(b"first batch")), Ok(Bytes::from_static(b"second batch"))]
+        );
+
+        let truncated: Vec<_> = BatchStream::new(&stream[..stream.len() - 1]).collect();
+        assert_eq!(truncated.len(), 2);
+        assert!(truncated[1].is_err());
+    }
+}
@@ -0,0 +1,180 @@
+//! Channel frames, the unit of batch data posted to L1.
+//!
+//! Spec: <https://specs.optimism.io/protocol/derivation.html#frame-format>
+
+use alloc::vec::Vec;
+use alloy_primitives::Bytes;
+
+/// The version byte of batcher transaction data carrying frames.
+pub const DERIVATION_VERSION_0: u8 = 0;
+
+/// The encoding overhead of a frame: channel id, frame number, data length and `is_last` flag.
+pub const FRAME_OVERHEAD: usize = 23;
+
+/// The maximum length of the data of a frame.
+pub const MAX_FRAME_LEN: usize = 1_000_000;
+
+/// The length of a [`ChannelId`].
+pub const CHANNEL_ID_LENGTH: usize = 16;
+
+/// The id of a channel, chosen randomly by the batcher.
+pub type ChannelId = [u8; CHANNEL_ID_LENGTH];
+
+/// A frame of a channel:
+/// `channel_id ++ frame_number (u16) ++ frame_data_length (u32) ++ frame_data ++ is_last (u8)`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Frame {
+    /// The id of the channel the frame belongs to.
+    pub id: ChannelId,
+    /// The index of the frame within the channel.
+    pub number: u16,
+    /// The channel data in the frame.
+    pub data: Bytes,
+    /// Whether this is the last frame of the channel.
+    pub is_last: bool,
+}
+
+impl Frame {
+    /// Creates a new [`Frame`].
+    pub const fn new(id: ChannelId, number: u16, data: Bytes, is_last: bool) -> Self {
+        Self { id, number, data, is_last }
+    }
+
+    /// Returns the length of the encoded frame.
+    pub fn size(&self) -> usize {
+        self.data.len() + FRAME_OVERHEAD
+    }
+
+    /// Encodes the frame, appending it to `out`.
+    pub fn encode_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.id);
+        out.extend_from_slice(&self.number.to_be_bytes());
+        out.extend_from_slice(&(self.data.len() as u32).to_be_bytes());
+        out.extend_from_slice(&self.data);
+        out.push(self.is_last as u8);
+    }
+
+    /// Returns the encoded frame.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.size());
+        self.encode_to(&mut out);
+        out
+    }
+
+    /// Decodes a single frame from the start of `buf`, returning it with the number of bytes
+    /// read.
+    pub fn decode(buf: &[u8]) -> Result<(usize, Self), FrameError> {
+        if buf.len() < FRAME_OVERHEAD {
+            return Err(FrameError::Truncated);
+        }
+        let id = buf[..CHANNEL_ID_LENGTH].try_into().expect("sufficient length");
+        let number = u16::from_be_bytes(buf[16..18].try_into().expect("sufficient length"));
+        let data_len =
+            u32::from_be_bytes(buf[18..22].try_into().expect("sufficient length")) as usize;
+        if data_len > MAX_FRAME_LEN {
+            return Err(FrameError::DataTooLarge(data_len));
+        }
+
+        let end = 22 + data_len;
+        if buf.len() <= end {
+            return Err(FrameError::Truncated);
+        }
+        let is_last = match buf[end] {
+            0 => false,
+            1 => true,
+            flag => return Err(FrameError::InvalidIsLast(flag)),
+        };
+        let data = Bytes::copy_from_slice(&buf[22..end]);
+        Ok((end + 1, Self { id, number, data, is_last }))
+    }
+
+    /// Parses the frames of the data of a batcher transaction: `version ++ frame+`.
+    ///
+    /// The data must contain at least one frame and no trailing bytes.
+    pub fn parse_frames(data: &[u8]) -> Result<Vec<Self>, FrameError> {
+        let (&version, mut buf) = data.split_first().ok_or(FrameError::NoFrames)?;
+        if version != DERIVATION_VERSION_0 {
+            return Err(FrameError::UnsupportedVersion(version));
+        }
+        if buf.is_empty() {
+            return Err(FrameError::NoFrames);
+        }
+
+        let mut frames = Vec::new();
+        while !buf.is_empty() {
+            let (read, frame) = Self::decode(buf)?;
+            frames.push(frame);
+            buf = &buf[read..];
+        }
+        Ok(frames)
+    }
+
+    /// Encodes frames as batcher transaction data: `version ++ frame+`.
+    pub fn encode_frames<'a>(frames: impl IntoIterator<Item = &'a Self>) -> Bytes {
+        let mut out = alloc::vec![DERIVATION_VERSION_0];
+        for frame in frames {
+            frame.encode_to(&mut out);
+        }
+        out.into()
+    }
+}
+
+/// An error parsing [`Frame`]s.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum FrameError {
+    /// The batcher transaction data contains no frames.
+    #[error("No frames in batcher transaction data")]
+    NoFrames,
+    /// The batcher transaction data has an unsupported version.
+    #[error("Unsupported derivation version: {0}")]
+    UnsupportedVersion(u8),
+    /// The frame is shorter than its header or declared data length.
+    #[error("Truncated frame")]
+    Truncated,
+    /// The frame data exceeds [`MAX_FRAME_LEN`].
+    #[error("Frame data too large: {0}")]
+    DataTooLarge(usize),
+    /// The `is_last` flag is neither 0 nor 1.
+    #[error("Invalid frame is_last flag: {0}")]
+    InvalidIsLast(u8),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn test_frame_roundtrip() {
+        let frame = Frame::new([0xff; 16], 0xabcd, Bytes::from_static(&[1, 2, 3]), true);
+        let encoded = frame.encode();
+
+        assert_eq!(encoded, hex!("ffffffffffffffffffffffffffffffffabcd0000000301020301").to_vec());
+        assert_eq!(Frame::decode(&encoded), Ok((encoded.len(), frame)));
+    }
+
+    #[test]
+    fn test_parse_frames() {
+        let frames = vec![
+            Frame::new([1; 16], 0, Bytes::from_static(b"hello"), false),
+            Frame::new([1; 16], 1, Bytes::new(), true),
+        ];
+        let data = Frame::encode_frames(&frames);
+
+        assert_eq!(Frame::parse_frames(&data), Ok(frames));
+        assert_eq!(Frame::parse_frames(&data[..data.len() - 1]), Err(FrameError::Truncated));
+        assert_eq!(Frame::parse_frames(&[DERIVATION_VERSION_0]), Err(FrameError::NoFrames));
+        assert_eq!(Frame::parse_frames(&[1, 0]), Err(FrameError::UnsupportedVersion(1)));
+    }
+
+    #[test]
+    fn test_decode_invalid_frame() {
+        let mut encoded = Frame::new([1; 16], 0, Bytes::new(), false).encode();
+        encoded[22] = 2;
+        assert_eq!(Frame::decode(&encoded), Err(FrameError::InvalidIsLast(2)));
+
+        encoded[18..22].copy_from_slice(&(MAX_FRAME_LEN as u32 + 1).to_be_bytes());
+        assert_eq!(Frame::decode(&encoded), Err(FrameError::DataTooLarge(MAX_FRAME_LEN + 1)));
+    }
+}
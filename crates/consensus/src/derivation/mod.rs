@@ -0,0 +1,52 @@
+//! Data formats of the derivation pipeline, used to read L2 batch data from L1.
+//!
+//! Batcher transactions carry [`Frame`]s, which are assembled into [`Channel`]s. The channel
+//! data is compressed, and decompresses into a stream of RLP encoded [`Batch`]es. With Alt-DA,
+//! batcher transactions carry an [`AltDaCommitment`] to the frames instead.
+//!
+//! Spec: <https://specs.optimism.io/protocol/derivation.html#batch-submission-wire-format>
+
+mod frame;
+pub use frame::{
+    CHANNEL_ID_LENGTH, ChannelId, DERIVATION_VERSION_0, FRAME_OVERHEAD, Frame, FrameError,
+    MAX_FRAME_LEN,
+};
+
+mod channel;
+pub use channel::{Channel, ChannelError};
+
+mod batch;
+pub use batch::{
+    Batch, BatchDecodingError, MAX_SPAN_BATCH_ELEMENT_COUNT, SINGULAR_BATCH_TYPE, SPAN_BATCH_TYPE,
+    SingularBatch, SpanBatch, SpanBatchError, SpanBatchTransaction, SpanBatchTransactionData,
+};
+
+mod compression;
+pub use compression::{
+    BatchStream, CHANNEL_VERSION_BROTLI, CompressionAlgo, CompressionError,
+    FJORD_MAX_RLP_BYTES_PER_CHANNEL, MAX_RLP_BYTES_PER_CHANNEL, compress_channel_data,
+    decompress_channel_data,
+};
+
+mod alt_da;
+pub use alt_da::{
+    AltDaCommitment, AltDaCommitmentError, BatcherTxData, BatcherTxDataError, CommitmentType,
+    DERIVATION_VERSION_1, EIGENDA_DA_LAYER,
+};
+
+mod blob;
+pub use blob::{
+    BLOB_ENCODING_ROUNDS, BLOB_ENCODING_VERSION, BlobData, BlobDecodingError, BlobEncodingError,
+    MAX_BLOB_DATA_SIZE,
+};
+
+mod batcher;
+pub use batcher::{
+    BatchFormat, BatcherConfig, BatcherError, BatcherTransaction, BatcherTxBuilder,
+    DataAvailabilityType, MAX_BLOB_FRAME_SIZE,
+};
+
+mod validity;
+pub use validity::{
+    BatchDropReason, BatchValidity, BatchValidityConfig, FJORD_MAX_SEQUENCER_DRIFT, SafeL2Block,
+};
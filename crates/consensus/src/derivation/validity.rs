@@ -0,0 +1,715 @@
+//! Validity checks of batches against the L2 safe head and the L1 origins.
+//!
+//! Spec: <https://specs.optimism.io/protocol/derivation.html#batch-queue>
+
+use super::{Batch, SingularBatch, SpanBatch, SpanBatchTransactionData};
+use crate::{BlockInfo, DEPOSIT_TX_TYPE_ID, L2BlockInfo, OpHardforkSchedule};
+use alloc::vec::Vec;
+use alloy_eips::eip2718::{EIP7702_TX_TYPE_ID, Encodable2718};
+use alloy_primitives::{Bytes, ChainId};
+
+/// The maximum sequencer drift since Fjord, in seconds.
+pub const FJORD_MAX_SEQUENCER_DRIFT: u64 = 1800;
+
+/// The parameters of the rollup config used to check batches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchValidityConfig {
+    /// The timestamp of the L2 genesis block.
+    pub genesis_timestamp: u64,
+    /// The L2 block time in seconds.
+    pub block_time: u64,
+    /// The maximum sequencer drift before Fjord, in seconds.
+    pub max_sequencer_drift: u64,
+    /// The sequencing window size, in L1 blocks.
+    pub seq_window_size: u64,
+    /// The L2 chain id.
+    pub chain_id: ChainId,
+    /// The hardfork activation timestamps.
+    pub hardforks: OpHardforkSchedule,
+}
+
+impl BatchValidityConfig {
+    /// Returns the maximum sequencer drift for a block with an L1 origin with the given
+    /// timestamp.
+    pub const fn max_sequencer_drift(&self, l1_origin_timestamp: u64) -> u64 {
+        if self.hardforks.is_fjord_active(l1_origin_timestamp) {
+            FJORD_MAX_SEQUENCER_DRIFT
+        } else {
+            self.max_sequencer_drift
+        }
+    }
+}
+
+/// A block of the L2 safe chain, checked against span batches that overlap it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SafeL2Block {
+    /// The block reference.
+    pub info: L2BlockInfo,
+    /// The EIP-2718 encoded transactions of the block, including deposits.
+    pub transactions: Vec<Bytes>,
+}
+
+/// The result of checking a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchValidity {
+    /// The batch is valid and can be applied on top of the safe head.
+    Accept,
+    /// The batch is invalid and must be dropped.
+    Drop(BatchDropReason),
+    /// More L1 or L2 data is needed to check the batch.
+    Undecided,
+    /// The batch may be valid, but cannot be applied yet.
+    Future,
+    /// The batch is older than the safe head and ignored, since Holocene.
+    Past,
+}
+
+impl BatchValidity {
+    /// Returns true if the batch is accepted.
+    pub const fn is_accept(&self) -> bool {
+        matches!(self, Self::Accept)
+    }
+
+    /// Returns true if the batch is dropped.
+    pub const fn is_drop(&self) -> bool {
+        matches!(self, Self::Drop(_))
+    }
+}
+
+/// The reason a batch is dropped.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum BatchDropReason {
+    /// The batch is newer than the next block, since Holocene.
+    #[error("Batch timestamp is after the next expected timestamp")]
+    FutureTimestamp,
+    /// The batch is older than the next block, before Holocene.
+    #[error("Batch timestamp is before the next expected timestamp")]
+    PastTimestamp,
+    /// The timestamp of an overlapping span batch is not aligned with the L2 blocks.
+    #[error("Span batch timestamp is not aligned with the L2 block time")]
+    MisalignedTimestamp,
+    /// The batch does not build on the expected parent block.
+    #[error("Batch parent hash does not match the parent block")]
+    ParentHashMismatch,
+    /// The batch was included after its sequencing window expired.
+    #[error("Batch was included after the sequencing window expired")]
+    IncludedTooLate,
+    /// The L1 origin of the batch is older than the current epoch.
+    #[error("Batch epoch is too old")]
+    EpochTooOld,
+    /// The L1 origin of the batch is more than one block ahead of the current epoch.
+    #[error("Batch epoch is too far ahead")]
+    EpochTooFarAhead,
+    /// The L1 origin hash of the batch does not match the L1 chain.
+    #[error("Batch epoch hash does not match the L1 origin")]
+    EpochHashMismatch,
+    /// A block is older than its L1 origin.
+    #[error("Block timestamp is before its L1 origin timestamp")]
+    TimestampBeforeL1Origin,
+    /// A non-empty block exceeds the maximum sequencer drift.
+    #[error("Non-empty block exceeds the sequencer time drift")]
+    SequencerDriftExceeded,
+    /// An empty block exceeds the maximum sequencer drift, although the next L1 origin could
+    /// have been adopted.
+    #[error("Empty block exceeds the sequencer time drift without adopting the next L1 origin")]
+    SequencerDriftNextOriginNotAdopted,
+    /// A transaction of the batch is empty.
+    #[error("Empty transaction at index {0}")]
+    EmptyTransaction(usize),
+    /// A transaction of the batch is a deposit.
+    #[error("Deposit transaction at index {0}")]
+    DepositTransaction(usize),
+    /// A transaction of the batch is an EIP-7702 set code transaction before Isthmus.
+    #[error("Set code transaction before Isthmus at index {0}")]
+    SetCodeTransactionBeforeIsthmus(usize),
+    /// A transaction of a span batch is invalid.
+    #[error("Invalid span batch transaction at index {0}")]
+    InvalidTransaction(usize),
+    /// The L1 origin of a span batch is before Delta.
+    #[error("Span batch with L1 origin before Delta")]
+    SpanBatchBeforeDelta,
+    /// An overlapping block of a span batch has a different number of transactions than the
+    /// safe block.
+    #[error("Overlapped block {0} has a different transaction count")]
+    OverlappedTxCountMismatch(u64),
+    /// An overlapping block of a span batch has different transactions than the safe block.
+    #[error("Overlapped block {0} has different transactions")]
+    OverlappedTxMismatch(u64),
+    /// An overlapping block of a span batch has a different L1 origin than the safe block.
+    #[error("Overlapped block {0} has a different L1 origin")]
+    OverlappedL1OriginMismatch(u64),
+}
+
+impl Batch {
+    /// Checks the batch, included in the `inclusion_block`, against the `l2_safe_head`.
+    ///
+    /// See [`SingularBatch::check`] and [`SpanBatch::check`].
+    pub fn check(
+        &self,
+        cfg: &BatchValidityConfig,
+        l1_blocks: &[BlockInfo],
+        l2_safe_head: &L2BlockInfo,
+        inclusion_block: &BlockInfo,
+        l2_safe_blocks: &[SafeL2Block],
+    ) -> BatchValidity {
+        match self {
+            Self::Single(batch) => batch.check(cfg, l1_blocks, l2_safe_head, inclusion_block),
+            Self::Span(batch) => {
+                batch.check(cfg, l1_blocks, l2_safe_head, inclusion_block, l2_safe_blocks)
+            }
+        }
+    }
+}
+
+impl SingularBatch {
+    /// Checks the batch, included in the `inclusion_block`, against the `l2_safe_head`.
+    ///
+    /// `l1_blocks` are the L1 blocks starting at the current epoch, the L1 origin of the safe
+    /// head.
+    pub fn check(
+        &self,
+        cfg: &BatchValidityConfig,
+        l1_blocks: &[BlockInfo],
+        l2_safe_head: &L2BlockInfo,
+        inclusion_block: &BlockInfo,
+    ) -> BatchValidity {
+        let Some(epoch) = l1_blocks.first() else {
+            return BatchValidity::Undecided;
+        };
+        let holocene = cfg.hardforks.is_holocene_active(inclusion_block.timestamp);
+
+        let next_timestamp = l2_safe_head.block_info.timestamp + cfg.block_time;
+        if self.timestamp > next_timestamp {
+            if holocene {
+                return BatchValidity::Drop(BatchDropReason::FutureTimestamp);
+            }
+            return BatchValidity::Future;
+        }
+        if self.timestamp < next_timestamp {
+            if holocene {
+                return BatchValidity::Past;
+            }
+            return BatchValidity::Drop(BatchDropReason::PastTimestamp);
+        }
+
+        if self.parent_hash != l2_safe_head.block_info.hash {
+            return BatchValidity::Drop(BatchDropReason::ParentHashMismatch);
+        }
+        if self.epoch_num.saturating_add(cfg.seq_window_size) < inclusion_block.number {
+            return BatchValidity::Drop(BatchDropReason::IncludedTooLate);
+        }
+
+        let batch_origin = if self.epoch_num < epoch.number {
+            return BatchValidity::Drop(BatchDropReason::EpochTooOld);
+        } else if self.epoch_num == epoch.number {
+            epoch
+        } else if self.epoch_num == epoch.number + 1 {
+            match l1_blocks.get(1) {
+                Some(next) => next,
+                None => return BatchValidity::Undecided,
+            }
+        } else {
+            return BatchValidity::Drop(BatchDropReason::EpochTooFarAhead);
+        };
+        if self.epoch_hash != batch_origin.hash {
+            return BatchValidity::Drop(BatchDropReason::EpochHashMismatch);
+        }
+
+        if let Err(validity) = check_block(
+            cfg,
+            l1_blocks,
+            0,
+            self.timestamp,
+            batch_origin,
+            epoch.number != batch_origin.number,
+            self.transactions.is_empty(),
+        ) {
+            return validity;
+        }
+        if let Err(reason) = check_transactions(cfg, self.timestamp, &self.transactions) {
+            return BatchValidity::Drop(reason);
+        }
+        BatchValidity::Accept
+    }
+}
+
+impl SpanBatch {
+    /// Checks the batch, included in the `inclusion_block`, against the `l2_safe_head`.
+    ///
+    /// `l1_blocks` are the L1 blocks starting at the current epoch, the L1 origin of the safe
+    /// head. A span batch may overlap the safe chain, the overlapped blocks are checked against
+    /// the `l2_safe_blocks`, which must contain the parent of the batch and all safe blocks after
+    /// it.
+    pub fn check(
+        &self,
+        cfg: &BatchValidityConfig,
+        l1_blocks: &[BlockInfo],
+        l2_safe_head: &L2BlockInfo,
+        inclusion_block: &BlockInfo,
+        l2_safe_blocks: &[SafeL2Block],
+    ) -> BatchValidity {
+        match self.check_batch(cfg, l1_blocks, l2_safe_head, inclusion_block, l2_safe_blocks) {
+            Ok(()) => BatchValidity::Accept,
+            Err(validity) => validity,
+        }
+    }
+
+    fn check_batch(
+        &self,
+        cfg: &BatchValidityConfig,
+        l1_blocks: &[BlockInfo],
+        l2_safe_head: &L2BlockInfo,
+        inclusion_block: &BlockInfo,
+        l2_safe_blocks: &[SafeL2Block],
+    ) -> Result<(), BatchValidity> {
+        use BatchValidity::{Drop, Future, Past, Undecided};
+
+        let epoch = l1_blocks.first().ok_or(Undecided)?;
+        let epoch_nums = self.epoch_nums();
+        let (&start_epoch_num, &end_epoch_num) =
+            epoch_nums.first().zip(epoch_nums.last()).ok_or(Undecided)?;
+        let batch_origin = if start_epoch_num == epoch.number + 1 {
+            l1_blocks.get(1).ok_or(Undecided)?
+        } else {
+            epoch
+        };
+        if !cfg.hardforks.is_delta_active(batch_origin.timestamp) {
+            return Err(Drop(BatchDropReason::SpanBatchBeforeDelta));
+        }
+
+        let holocene = cfg.hardforks.is_holocene_active(inclusion_block.timestamp);
+        let safe_timestamp = l2_safe_head.block_info.timestamp;
+        let next_timestamp = safe_timestamp + cfg.block_time;
+        // The span timestamps are untrusted, a span ending past `u64::MAX` is never valid.
+        let (start_timestamp, final_timestamp) = cfg
+            .genesis_timestamp
+            .checked_add(self.rel_timestamp)
+            .and_then(|start| {
+                let span =
+                    cfg.block_time.checked_mul(self.block_count().saturating_sub(1) as u64)?;
+                Some((start, start.checked_add(span)?))
+            })
+            .ok_or(Drop(BatchDropReason::FutureTimestamp))?;
+        if start_timestamp > next_timestamp {
+            return Err(if holocene { Drop(BatchDropReason::FutureTimestamp) } else { Future });
+        }
+        if final_timestamp < next_timestamp {
+            return Err(if holocene { Past } else { Drop(BatchDropReason::PastTimestamp) });
+        }
+
+        let mut parent = *l2_safe_head;
+        if start_timestamp < next_timestamp {
+            if start_timestamp > safe_timestamp {
+                return Err(Drop(BatchDropReason::MisalignedTimestamp));
+            }
+            if !(safe_timestamp - start_timestamp).is_multiple_of(cfg.block_time) {
+                return Err(Drop(BatchDropReason::MisalignedTimestamp));
+            }
+            // A batch overlapping back past the L2 genesis has no parent.
+            let parent_num = l2_safe_head
+                .block_info
+                .number
+                .checked_sub((safe_timestamp - start_timestamp) / cfg.block_time + 1)
+                .ok_or(Drop(BatchDropReason::MisalignedTimestamp))?;
+            parent = safe_block(l2_safe_blocks, parent_num).ok_or(Undecided)?.info;
+        }
+        if self.parent_check[..] != parent.block_info.hash[..20] {
+            return Err(Drop(BatchDropReason::ParentHashMismatch));
+        }
+
+        if start_epoch_num.saturating_add(cfg.seq_window_size) < inclusion_block.number {
+            return Err(Drop(BatchDropReason::IncludedTooLate));
+        }
+        if start_epoch_num > parent.l1_origin.number + 1 {
+            return Err(Drop(BatchDropReason::EpochTooFarAhead));
+        }
+        let end_origin =
+            l1_blocks.iter().find(|block| block.number == end_epoch_num).ok_or(Undecided)?;
+        if self.l1_origin_check[..] != end_origin.hash[..20] {
+            return Err(Drop(BatchDropReason::EpochHashMismatch));
+        }
+        if start_epoch_num < parent.l1_origin.number {
+            return Err(Drop(BatchDropReason::EpochTooOld));
+        }
+
+        let mut origin_index = 0;
+        let mut encoded_blocks = Vec::with_capacity(self.block_count());
+        let blocks = self
+            .block_timestamps(cfg.genesis_timestamp, cfg.block_time)
+            .zip(self.block_transactions())
+            .enumerate();
+        for (i, (timestamp, transactions)) in blocks {
+            let encoded = transactions
+                .iter()
+                .enumerate()
+                .map(|(index, tx)| {
+                    if matches!(tx.data, SpanBatchTransactionData::Eip7702 { .. })
+                        && !cfg.hardforks.is_isthmus_active(timestamp)
+                    {
+                        return Err(Drop(BatchDropReason::SetCodeTransactionBeforeIsthmus(index)));
+                    }
+                    tx.to_envelope(cfg.chain_id)
+                        .map(|tx| Bytes::from(tx.encoded_2718()))
+                        .map_err(|_| Drop(BatchDropReason::InvalidTransaction(index)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if timestamp > safe_timestamp {
+                origin_index += l1_blocks[origin_index..]
+                    .iter()
+                    .position(|block| block.number == epoch_nums[i])
+                    .ok_or(Undecided)?;
+                let origin_advanced = if i == 0 {
+                    start_epoch_num == parent.l1_origin.number + 1
+                } else {
+                    epoch_nums[i] > epoch_nums[i - 1]
+                };
+                check_block(
+                    cfg,
+                    l1_blocks,
+                    origin_index,
+                    timestamp,
+                    &l1_blocks[origin_index],
+                    origin_advanced,
+                    encoded.is_empty(),
+                )?;
+            }
+            encoded_blocks.push(encoded);
+        }
+
+        let parent_num = parent.block_info.number;
+        for (i, number) in (parent_num + 1..=l2_safe_head.block_info.number).enumerate() {
+            let safe_block = safe_block(l2_safe_blocks, number).ok_or(Undecided)?;
+            let safe_txs: Vec<_> = safe_block
+                .transactions
+                .iter()
+                .filter(|tx| tx.first() != Some(&DEPOSIT_TX_TYPE_ID))
+                .collect();
+            let batch_txs = encoded_blocks.get(i).map(Vec::as_slice).unwrap_or_default();
+            if safe_txs.len() != batch_txs.len() {
+                return Err(Drop(BatchDropReason::OverlappedTxCountMismatch(number)));
+            }
+            if safe_txs.into_iter().zip(batch_txs).any(|(safe, batch)| safe != batch) {
+                return Err(Drop(BatchDropReason::OverlappedTxMismatch(number)));
+            }
+            if safe_block.info.l1_origin.number != epoch_nums[i] {
+                return Err(Drop(BatchDropReason::OverlappedL1OriginMismatch(number)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks the timestamp of a block against its L1 origin and the sequencer drift.
+///
+/// `origin_advanced` is whether the block adopts a new L1 origin. An empty block may exceed the
+/// sequencer drift, as long as it could not have adopted the next L1 origin, the one after
+/// `l1_blocks[origin_index]`.
+fn check_block(
+    cfg: &BatchValidityConfig,
+    l1_blocks: &[BlockInfo],
+    origin_index: usize,
+    timestamp: u64,
+    l1_origin: &BlockInfo,
+    origin_advanced: bool,
+    is_empty: bool,
+) -> Result<(), BatchValidity> {
+    if timestamp < l1_origin.timestamp {
+        return Err(BatchValidity::Drop(BatchDropReason::TimestampBeforeL1Origin));
+    }
+    if timestamp <= l1_origin.timestamp + cfg.max_sequencer_drift(l1_origin.timestamp) {
+        return Ok(());
+    }
+    if !is_empty {
+        return Err(BatchValidity::Drop(BatchDropReason::SequencerDriftExceeded));
+    }
+    if !origin_advanced {
+        let next_origin = l1_blocks.get(origin_index + 1).ok_or(BatchValidity::Undecided)?;
+        if timestamp >= next_origin.timestamp {
+            return Err(BatchValidity::Drop(BatchDropReason::SequencerDriftNextOriginNotAdopted));
+        }
+    }
+    Ok(())
+}
+
+/// Checks the encoded transactions of a block with the given timestamp.
+fn check_transactions(
+    cfg: &BatchValidityConfig,
+    timestamp: u64,
+    transactions: &[Bytes],
+) -> Result<(), BatchDropReason> {
+    for (index, tx) in transactions.iter().enumerate() {
+        match tx.first() {
+            None => return Err(BatchDropReason::EmptyTransaction(index)),
+            Some(&DEPOSIT_TX_TYPE_ID) => return Err(BatchDropReason::DepositTransaction(index)),
+            Some(&EIP7702_TX_TYPE_ID) if !cfg.hardforks.is_isthmus_active(timestamp) => {
+                return Err(BatchDropReason::SetCodeTransactionBeforeIsthmus(index));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Returns the safe block with the given number.
+fn safe_block(blocks: &[SafeL2Block], number: u64) -> Option<&SafeL2Block> {
+    blocks.iter().find(|block| block.info.block_info.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_primitives::B256;
+
+    const BLOCK_TIME: u64 = 2;
+
+    fn cfg() -> BatchValidityConfig {
+        BatchValidityConfig {
+            genesis_timestamp: 0,
+            block_time: BLOCK_TIME,
+            max_sequencer_drift: 600,
+            seq_window_size: 100,
+            chain_id: 5000,
+            hardforks: OpHardforkSchedule { delta_time: Some(0), ..Default::default() },
+        }
+    }
+
+    fn l1_block(number: u64) -> BlockInfo {
+        BlockInfo::new(
+            B256::with_last_byte(number as u8),
+            number,
+            B256::with_last_byte(number as u8 - 1),
+            number * 10,
+        )
+    }
+
+    fn l2_block(number: u64, l1_origin: u64) -> L2BlockInfo {
+        L2BlockInfo::new(
+            BlockInfo::new(
+                B256::repeat_byte(number as u8),
+                number,
+                B256::repeat_byte(number as u8 - 1),
+                number * BLOCK_TIME,
+            ),
+            l1_block(l1_origin).id(),
+            0,
+        )
+    }
+
+    fn singular(parent: &L2BlockInfo, epoch: u64, transactions: Vec<Bytes>) -> SingularBatch {
+        SingularBatch {
+            parent_hash: parent.block_info.hash,
+            epoch_num: epoch,
+            epoch_hash: l1_block(epoch).hash,
+            timestamp: parent.block_info.timestamp + BLOCK_TIME,
+            transactions,
+        }
+    }
+
+    #[test]
+    fn test_check_singular_batch() {
+        let cfg = cfg();
+        let safe_head = l2_block(60, 10);
+        let l1_blocks = [l1_block(10), l1_block(11)];
+        let inclusion = l1_block(12);
+        let check = |batch: &SingularBatch| batch.check(&cfg, &l1_blocks, &safe_head, &inclusion);
+
+        assert_eq!(check(&singular(&safe_head, 10, vec![])), BatchValidity::Accept);
+        assert_eq!(check(&singular(&safe_head, 11, vec![])), BatchValidity::Accept);
+        assert_eq!(
+            check(&singular(&safe_head, 9, vec![])),
+            BatchValidity::Drop(BatchDropReason::EpochTooOld)
+        );
+        assert_eq!(
+            check(&singular(&safe_head, 12, vec![])),
+            BatchValidity::Drop(BatchDropReason::EpochTooFarAhead)
+        );
+        assert_eq!(
+            check(&SingularBatch { parent_hash: B256::ZERO, ..singular(&safe_head, 10, vec![]) }),
+            BatchValidity::Drop(BatchDropReason::ParentHashMismatch)
+        );
+        assert_eq!(
+            check(&singular(&safe_head, 10, vec![Bytes::from_static(&[DEPOSIT_TX_TYPE_ID])])),
+            BatchValidity::Drop(BatchDropReason::DepositTransaction(0))
+        );
+        assert_eq!(
+            check(&singular(&safe_head, 10, vec![Bytes::from_static(&[2]), Bytes::new()])),
+            BatchValidity::Drop(BatchDropReason::EmptyTransaction(1))
+        );
+        assert_eq!(
+            check(&singular(&safe_head, 10, vec![Bytes::from_static(&[EIP7702_TX_TYPE_ID])])),
+            BatchValidity::Drop(BatchDropReason::SetCodeTransactionBeforeIsthmus(0))
+        );
+        assert_eq!(
+            singular(&safe_head, 10, vec![]).check(&cfg, &[], &safe_head, &inclusion),
+            BatchValidity::Undecided
+        );
+    }
+
+    #[test]
+    fn test_check_singular_batch_timestamp() {
+        let mut cfg = cfg();
+        let safe_head = l2_block(60, 10);
+        let l1_blocks = [l1_block(10), l1_block(11)];
+        let inclusion = l1_block(12);
+        let future = SingularBatch { timestamp: 124, ..singular(&safe_head, 10, vec![]) };
+        let past = SingularBatch { timestamp: 120, ..singular(&safe_head, 10, vec![]) };
+
+        assert_eq!(future.check(&cfg, &l1_blocks, &safe_head, &inclusion), BatchValidity::Future);
+        assert_eq!(
+            past.check(&cfg, &l1_blocks, &safe_head, &inclusion),
+            BatchValidity::Drop(BatchDropReason::PastTimestamp)
+        );
+
+        cfg.hardforks.holocene_time = Some(0);
+        assert_eq!(
+            future.check(&cfg, &l1_blocks, &safe_head, &inclusion),
+            BatchValidity::Drop(BatchDropReason::FutureTimestamp)
+        );
+        assert_eq!(past.check(&cfg, &l1_blocks, &safe_head, &inclusion), BatchValidity::Past);
+        assert_eq!(
+            singular(&safe_head, 10, vec![]).check(&cfg, &l1_blocks, &safe_head, &l1_block(111)),
+            BatchValidity::Drop(BatchDropReason::IncludedTooLate)
+        );
+    }
+
+    #[test]
+    fn test_check_singular_batch_sequencer_drift() {
+        let cfg = cfg();
+        // The safe head is 600 seconds after its L1 origin, at the maximum sequencer drift.
+        let safe_head = l2_block(350, 10);
+        let inclusion = l1_block(70);
+
+        // The next L1 origin is known and could have been adopted.
+        let l1_blocks = [l1_block(10), BlockInfo { timestamp: 702, ..l1_block(11) }];
+        assert_eq!(
+            singular(&safe_head, 10, vec![]).check(&cfg, &l1_blocks, &safe_head, &inclusion),
+            BatchValidity::Drop(BatchDropReason::SequencerDriftNextOriginNotAdopted)
+        );
+        assert_eq!(
+            singular(&safe_head, 10, vec![Bytes::from_static(&[2])])
+                .check(&cfg, &l1_blocks, &safe_head, &inclusion),
+            BatchValidity::Drop(BatchDropReason::SequencerDriftExceeded)
+        );
+
+        // The next L1 origin is too new to be adopted, so an empty batch may exceed the drift.
+        let l1_blocks = [l1_block(10), BlockInfo { timestamp: 800, ..l1_block(11) }];
+        assert_eq!(
+            singular(&safe_head, 10, vec![]).check(&cfg, &l1_blocks, &safe_head, &inclusion),
+            BatchValidity::Accept
+        );
+        assert_eq!(
+            singular(&safe_head, 10, vec![]).check(&cfg, &l1_blocks[..1], &safe_head, &inclusion),
+            BatchValidity::Undecided
+        );
+    }
+
+    fn safe_chain() -> Vec<SafeL2Block> {
+        (58..=60)
+            .map(|number| SafeL2Block {
+                info: l2_block(number, 10),
+                transactions: vec![Bytes::from_static(&[DEPOSIT_TX_TYPE_ID, 0xc0])],
+            })
+            .collect()
+    }
+
+    fn span(parent: &L2BlockInfo, epochs: &[u64]) -> SpanBatch {
+        let singular = epochs
+            .iter()
+            .enumerate()
+            .map(|(i, epoch)| SingularBatch {
+                timestamp: parent.block_info.timestamp + BLOCK_TIME * (i as u64 + 1),
+                ..singular(parent, *epoch, vec![])
+            })
+            .collect::<Vec<_>>();
+        SpanBatch::from_singular_batches(&singular, false, 0, 5000).unwrap()
+    }
+
+    #[test]
+    fn test_check_span_batch() {
+        let cfg = cfg();
+        let safe_head = l2_block(60, 10);
+        let l1_blocks = [l1_block(10), l1_block(11)];
+        let inclusion = l1_block(12);
+        let safe_chain = safe_chain();
+        let check =
+            |batch: &SpanBatch| batch.check(&cfg, &l1_blocks, &safe_head, &inclusion, &safe_chain);
+
+        assert_eq!(check(&span(&safe_head, &[10, 11])), BatchValidity::Accept);
+        // Overlaps the two safe blocks after 58.
+        assert_eq!(check(&span(&safe_chain[0].info, &[10, 10, 10])), BatchValidity::Accept);
+        assert_eq!(check(&span(&safe_chain[0].info, &[10, 10, 11])), BatchValidity::Accept);
+        assert_eq!(
+            check(&span(&safe_chain[0].info, &[10, 11, 11])),
+            BatchValidity::Drop(BatchDropReason::OverlappedL1OriginMismatch(60))
+        );
+        assert_eq!(
+            check(&span(&safe_chain[0].info, &[10])),
+            BatchValidity::Drop(BatchDropReason::PastTimestamp)
+        );
+        assert_eq!(
+            check(&span(&l2_block(56, 10), &[10, 10, 10, 10, 10])),
+            BatchValidity::Undecided
+        );
+        assert_eq!(
+            check(&SpanBatch { parent_check: Default::default(), ..span(&safe_head, &[10]) }),
+            BatchValidity::Drop(BatchDropReason::ParentHashMismatch)
+        );
+
+        let pre_delta = BatchValidityConfig { hardforks: Default::default(), ..cfg };
+        assert_eq!(
+            span(&safe_head, &[10]).check(&pre_delta, &l1_blocks, &safe_head, &inclusion, &[]),
+            BatchValidity::Drop(BatchDropReason::SpanBatchBeforeDelta)
+        );
+    }
+
+    #[test]
+    fn test_check_span_batch_before_genesis() {
+        let cfg = cfg();
+        let genesis = L2BlockInfo::new(
+            BlockInfo::new(B256::repeat_byte(0xff), 0, B256::ZERO, 0),
+            l1_block(10).id(),
+            0,
+        );
+        let l1_blocks = [l1_block(10), l1_block(11)];
+        let safe_chain = [SafeL2Block { info: genesis, transactions: vec![] }];
+        // The batch starts at the genesis timestamp, so its parent would precede genesis.
+        let batch = SpanBatch { rel_timestamp: 0, ..span(&genesis, &[10, 10]) };
+
+        assert_eq!(
+            batch.check(&cfg, &l1_blocks, &genesis, &l1_block(12), &safe_chain),
+            BatchValidity::Drop(BatchDropReason::MisalignedTimestamp)
+        );
+    }
+
+    #[test]
+    fn test_check_batch_epoch_overflow() {
+        let cfg = cfg();
+        let safe_head = l2_block(60, 10);
+        let l1_blocks = [l1_block(10), l1_block(11)];
+        let inclusion = l1_block(12);
+
+        let batch = SingularBatch {
+            epoch_num: u64::MAX,
+            epoch_hash: B256::ZERO,
+            ..singular(&safe_head, 10, vec![])
+        };
+
+        assert_eq!(
+            batch.check(&cfg, &l1_blocks, &safe_head, &inclusion),
+            BatchValidity::Drop(BatchDropReason::EpochTooFarAhead)
+        );
+
+        let cfg = BatchValidityConfig { genesis_timestamp: 1, ..cfg };
+        let safe_chain = safe_chain();
+        for rel_timestamp in [u64::MAX, u64::MAX - 2] {
+            let span = SpanBatch { rel_timestamp, ..span(&safe_head, &[10, 10]) };
+            let span = SpanBatch::decode(&span.encoded()).unwrap();
+            assert_eq!(
+                span.check(&cfg, &l1_blocks, &safe_head, &inclusion, &safe_chain),
+                BatchValidity::Drop(BatchDropReason::FutureTimestamp)
+            );
+        }
+    }
+}
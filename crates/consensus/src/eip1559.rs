@@ -132,6 +132,9 @@ pub enum EIP1559ParamError {
     /// Extra data is not the correct length.
     #[error("Extra data is not the correct length")]
     InvalidExtraDataLength,
+    /// EIP-1559 parameters must be None before Holocene.
+    #[error("EIP1559 parameters must be None before Holocene")]
+    EIP1559ParamsMustBeNone,
     /// Minimum base fee must be None before Jovian.
     #[error("Minimum base fee must be None before Jovian")]
     MinBaseFeeMustBeNone,
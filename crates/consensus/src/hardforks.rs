@@ -0,0 +1,235 @@
+//! OP Stack hardforks and their activation schedule.
+
+use derive_more::Display;
+
+/// The timestamp-activated OP Stack hardforks, in activation order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+pub enum OpHardfork {
+    /// Regolith: <https://specs.optimism.io/protocol/regolith/overview.html>
+    Regolith,
+    /// Canyon: <https://specs.optimism.io/protocol/canyon/overview.html>
+    Canyon,
+    /// Delta: <https://specs.optimism.io/protocol/delta/overview.html>
+    Delta,
+    /// Ecotone: <https://specs.optimism.io/protocol/ecotone/overview.html>
+    Ecotone,
+    /// Fjord: <https://specs.optimism.io/protocol/fjord/overview.html>
+    Fjord,
+    /// Granite: <https://specs.optimism.io/protocol/granite/overview.html>
+    Granite,
+    /// Holocene: <https://specs.optimism.io/protocol/holocene/overview.html>
+    Holocene,
+    /// Isthmus: <https://specs.optimism.io/protocol/isthmus/overview.html>
+    Isthmus,
+    /// Jovian: <https://specs.optimism.io/protocol/jovian/overview.html>
+    Jovian,
+    /// Interop: <https://specs.optimism.io/interop/overview.html>
+    Interop,
+}
+
+impl OpHardfork {
+    /// All hardforks, in activation order.
+    pub const ALL: [Self; 10] = [
+        Self::Regolith,
+        Self::Canyon,
+        Self::Delta,
+        Self::Ecotone,
+        Self::Fjord,
+        Self::Granite,
+        Self::Holocene,
+        Self::Isthmus,
+        Self::Jovian,
+        Self::Interop,
+    ];
+}
+
+/// The activation timestamps of the OP Stack hardforks.
+///
+/// A hardfork without an activation timestamp is never active. The field names match the ones
+/// used by op-node's rollup config.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpHardforkSchedule {
+    /// Regolith hardfork timestamp.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub regolith_time: Option<u64>,
+    /// Canyon hardfork timestamp.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub canyon_time: Option<u64>,
+    /// Delta hardfork timestamp.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub delta_time: Option<u64>,
+    /// Ecotone hardfork timestamp.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub ecotone_time: Option<u64>,
+    /// Fjord hardfork timestamp.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub fjord_time: Option<u64>,
+    /// Granite hardfork timestamp.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub granite_time: Option<u64>,
+    /// Holocene hardfork timestamp.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub holocene_time: Option<u64>,
+    /// Isthmus hardfork timestamp.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub isthmus_time: Option<u64>,
+    /// Jovian hardfork timestamp.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub jovian_time: Option<u64>,
+    /// Interop hardfork timestamp.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub interop_time: Option<u64>,
+}
+
+impl OpHardforkSchedule {
+    /// Returns the activation timestamp of the given hardfork, if it is scheduled.
+    pub const fn activation_time(&self, fork: OpHardfork) -> Option<u64> {
+        match fork {
+            OpHardfork::Regolith => self.regolith_time,
+            OpHardfork::Canyon => self.canyon_time,
+            OpHardfork::Delta => self.delta_time,
+            OpHardfork::Ecotone => self.ecotone_time,
+            OpHardfork::Fjord => self.fjord_time,
+            OpHardfork::Granite => self.granite_time,
+            OpHardfork::Holocene => self.holocene_time,
+            OpHardfork::Isthmus => self.isthmus_time,
+            OpHardfork::Jovian => self.jovian_time,
+            OpHardfork::Interop => self.interop_time,
+        }
+    }
+
+    /// Returns true if the given hardfork is active at the given timestamp.
+    pub const fn is_active(&self, fork: OpHardfork, timestamp: u64) -> bool {
+        matches!(self.activation_time(fork), Some(time) if timestamp >= time)
+    }
+
+    /// Returns true if the block with the given `timestamp`, built on top of a parent with
+    /// `parent_timestamp`, is the first block in which the given hardfork is active.
+    pub const fn is_activation_block(
+        &self,
+        fork: OpHardfork,
+        parent_timestamp: u64,
+        timestamp: u64,
+    ) -> bool {
+        self.is_active(fork, timestamp) && !self.is_active(fork, parent_timestamp)
+    }
+
+    /// Returns an iterator over the hardforks activated by the block with the given `timestamp`,
+    /// built on top of a parent with `parent_timestamp`.
+    pub fn activated_forks(
+        &self,
+        parent_timestamp: u64,
+        timestamp: u64,
+    ) -> impl Iterator<Item = OpHardfork> + '_ {
+        OpHardfork::ALL
+            .into_iter()
+            .filter(move |fork| self.is_activation_block(*fork, parent_timestamp, timestamp))
+    }
+
+    /// Returns true if Regolith is active at the given timestamp.
+    pub const fn is_regolith_active(&self, timestamp: u64) -> bool {
+        self.is_active(OpHardfork::Regolith, timestamp)
+    }
+
+    /// Returns true if Canyon is active at the given timestamp.
+    pub const fn is_canyon_active(&self, timestamp: u64) -> bool {
+        self.is_active(OpHardfork::Canyon, timestamp)
+    }
+
+    /// Returns true if Delta is active at the given timestamp.
+    pub const fn is_delta_active(&self, timestamp: u64) -> bool {
+        self.is_active(OpHardfork::Delta, timestamp)
+    }
+
+    /// Returns true if Ecotone is active at the given timestamp.
+    pub const fn is_ecotone_active(&self, timestamp: u64) -> bool {
+        self.is_active(OpHardfork::Ecotone, timestamp)
+    }
+
+    /// Returns true if Fjord is active at the given timestamp.
+    pub const fn is_fjord_active(&self, timestamp: u64) -> bool {
+        self.is_active(OpHardfork::Fjord, timestamp)
+    }
+
+    /// Returns true if Granite is active at the given timestamp.
+    pub const fn is_granite_active(&self, timestamp: u64) -> bool {
+        self.is_active(OpHardfork::Granite, timestamp)
+    }
+
+    /// Returns true if Holocene is active at the given timestamp.
+    pub const fn is_holocene_active(&self, timestamp: u64) -> bool {
+        self.is_active(OpHardfork::Holocene, timestamp)
+    }
+
+    /// Returns true if Isthmus is active at the given timestamp.
+    pub const fn is_isthmus_active(&self, timestamp: u64) -> bool {
+        self.is_active(OpHardfork::Isthmus, timestamp)
+    }
+
+    /// Returns true if Jovian is active at the given timestamp.
+    pub const fn is_jovian_active(&self, timestamp: u64) -> bool {
+        self.is_active(OpHardfork::Jovian, timestamp)
+    }
+
+    /// Returns true if Interop is active at the given timestamp.
+    pub const fn is_interop_active(&self, timestamp: u64) -> bool {
+        self.is_active(OpHardfork::Interop, timestamp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_is_active() {
+        let schedule = OpHardforkSchedule {
+            canyon_time: Some(0),
+            holocene_time: Some(10),
+            ..Default::default()
+        };
+
+        assert!(schedule.is_canyon_active(0));
+        assert!(!schedule.is_holocene_active(9));
+        assert!(schedule.is_holocene_active(10));
+        assert!(!schedule.is_jovian_active(u64::MAX));
+    }
+
+    #[test]
+    fn test_activated_forks() {
+        let schedule = OpHardforkSchedule {
+            ecotone_time: Some(10),
+            fjord_time: Some(12),
+            granite_time: Some(12),
+            ..Default::default()
+        };
+
+        assert!(schedule.is_activation_block(OpHardfork::Ecotone, 8, 10));
+        assert!(!schedule.is_activation_block(OpHardfork::Ecotone, 10, 12));
+        assert_eq!(
+            schedule.activated_forks(10, 12).collect::<Vec<_>>(),
+            [OpHardfork::Fjord, OpHardfork::Granite]
+        );
+        assert_eq!(schedule.activated_forks(12, 14).count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_schedule() {
+        let json = r#"{"regolith_time":0,"canyon_time":0,"holocene_time":1732633200}"#;
+        let schedule: OpHardforkSchedule = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            schedule,
+            OpHardforkSchedule {
+                regolith_time: Some(0),
+                canyon_time: Some(0),
+                holocene_time: Some(1732633200),
+                ..Default::default()
+            }
+        );
+        assert_eq!(serde_json::to_string(&schedule).unwrap(), json);
+    }
+}
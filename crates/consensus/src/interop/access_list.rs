@@ -0,0 +1,307 @@
+//! Access-list encoding of executing messages.
+//!
+//! Transactions that execute interop messages declare them in their access list, as storage keys
+//! of the `CrossL2Inbox`. Each message is encoded as a lookup identity entry, an optional chain-id
+//! extension entry and a checksum entry.
+//!
+//! Spec: <https://specs.optimism.io/interop/predeploys.html#access-list>
+
+use super::{CROSS_L2_INBOX_ADDRESS, ExecutingMessage};
+use crate::OpTxEnvelope;
+use alloc::vec::Vec;
+use alloy_consensus::Transaction;
+use alloy_eips::eip2930::{AccessList, AccessListItem};
+use alloy_primitives::{B256, U256, keccak256};
+
+/// The type byte of the lookup identity entry.
+pub const PREFIX_LOOKUP: u8 = 0x01;
+
+/// The type byte of the chain-id extension entry.
+pub const PREFIX_CHAIN_ID_EXTENSION: u8 = 0x02;
+
+/// The type byte of the checksum entry.
+pub const PREFIX_CHECKSUM: u8 = 0x03;
+
+/// The access of an executing message, as declared in the access list.
+///
+/// Contains everything of the [`Identifier`](super::Identifier) except the origin, which is
+/// committed to by the checksum together with the payload hash.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct MessageAccess {
+    /// The chain id of the initiating message.
+    pub chain_id: U256,
+    /// The block number of the initiating message.
+    pub block_number: u64,
+    /// The timestamp of the initiating message.
+    pub timestamp: u64,
+    /// The log index of the initiating message.
+    pub log_index: u32,
+    /// The checksum entry, committing to the full executing message.
+    pub checksum: B256,
+}
+
+impl MessageAccess {
+    /// Returns the lookup identity entry:
+    /// `0x01 ++ bytes3(0) ++ chain_id (u64) ++ block_number ++ timestamp ++ log_index`.
+    ///
+    /// Only the low 8 bytes of the chain id are included, the rest is in the
+    /// [chain-id extension](Self::chain_id_extension_entry).
+    pub fn lookup_entry(&self) -> B256 {
+        let mut entry = B256::ZERO;
+        entry[0] = PREFIX_LOOKUP;
+        entry[4..12].copy_from_slice(&self.chain_id.to_be_bytes::<32>()[24..]);
+        entry[12..20].copy_from_slice(&self.block_number.to_be_bytes());
+        entry[20..28].copy_from_slice(&self.timestamp.to_be_bytes());
+        entry[28..].copy_from_slice(&self.log_index.to_be_bytes());
+        entry
+    }
+
+    /// Returns the chain-id extension entry, `0x02 ++ bytes7(0) ++ chain_id[..24]`, if the chain
+    /// id doesn't fit in 8 bytes.
+    pub fn chain_id_extension_entry(&self) -> Option<B256> {
+        if self.chain_id <= U256::from(u64::MAX) {
+            return None;
+        }
+        let mut entry = B256::ZERO;
+        entry[0] = PREFIX_CHAIN_ID_EXTENSION;
+        entry[8..].copy_from_slice(&self.chain_id.to_be_bytes::<32>()[..24]);
+        Some(entry)
+    }
+
+    /// Returns the access-list storage keys of this access, in order.
+    pub fn storage_keys(&self) -> impl Iterator<Item = B256> + use<> {
+        core::iter::once(self.lookup_entry())
+            .chain(self.chain_id_extension_entry())
+            .chain(core::iter::once(self.checksum))
+    }
+}
+
+impl From<&ExecutingMessage> for MessageAccess {
+    fn from(message: &ExecutingMessage) -> Self {
+        let id = &message.identifier;
+        Self {
+            chain_id: id.chain_id,
+            block_number: id.block_number,
+            timestamp: id.timestamp,
+            log_index: id.log_index,
+            checksum: message.checksum(),
+        }
+    }
+}
+
+impl ExecutingMessage {
+    /// Returns the checksum entry of the executing message:
+    ///
+    /// ```text
+    /// log_hash      = keccak256(origin ++ payload_hash)
+    /// id_packed     = bytes12(0) ++ block_number ++ timestamp ++ log_index
+    /// id_log_hash   = keccak256(log_hash ++ id_packed)
+    /// bare_checksum = keccak256(id_log_hash ++ chain_id)
+    /// checksum      = 0x03 ++ bare_checksum[1..]
+    /// ```
+    pub fn checksum(&self) -> B256 {
+        let id = &self.identifier;
+        let mut id_packed = [0u8; 32];
+        id_packed[12..20].copy_from_slice(&id.block_number.to_be_bytes());
+        id_packed[20..28].copy_from_slice(&id.timestamp.to_be_bytes());
+        id_packed[28..].copy_from_slice(&id.log_index.to_be_bytes());
+        let id_log_hash = keccak256([self.log_hash().as_slice(), &id_packed].concat());
+
+        let mut checksum =
+            keccak256([id_log_hash.as_slice(), &id.chain_id.to_be_bytes::<32>()].concat());
+        checksum[0] = PREFIX_CHECKSUM;
+        checksum
+    }
+
+    /// Returns the access-list storage keys declaring this executing message.
+    pub fn access_list_storage_keys(&self) -> impl Iterator<Item = B256> + use<> {
+        MessageAccess::from(self).storage_keys()
+    }
+}
+
+/// Returns the [`AccessListItem`] of the `CrossL2Inbox` that declares the given executing
+/// messages.
+pub fn encode_access_list<'a>(
+    messages: impl IntoIterator<Item = &'a ExecutingMessage>,
+) -> AccessListItem {
+    AccessListItem {
+        address: CROSS_L2_INBOX_ADDRESS,
+        storage_keys: messages.into_iter().flat_map(|m| m.access_list_storage_keys()).collect(),
+    }
+}
+
+/// Decodes the executing message accesses from the storage keys of a `CrossL2Inbox` access-list
+/// item.
+pub fn decode_access_list_entries(
+    storage_keys: &[B256],
+) -> Result<Vec<MessageAccess>, AccessListEntryError> {
+    let mut accesses = Vec::new();
+    let mut keys = storage_keys.iter();
+    while let Some(lookup) = keys.next() {
+        if lookup[0] != PREFIX_LOOKUP {
+            return Err(AccessListEntryError::UnexpectedEntry(lookup[0]));
+        }
+        if lookup[1..4] != [0u8; 3] {
+            return Err(AccessListEntryError::InvalidPadding(PREFIX_LOOKUP));
+        }
+        let mut chain_id = [0u8; 32];
+        chain_id[24..].copy_from_slice(&lookup[4..12]);
+
+        let mut next = keys.next().ok_or(AccessListEntryError::MissingChecksum)?;
+        if next[0] == PREFIX_CHAIN_ID_EXTENSION {
+            if next[1..8] != [0u8; 7] {
+                return Err(AccessListEntryError::InvalidPadding(PREFIX_CHAIN_ID_EXTENSION));
+            }
+            chain_id[..24].copy_from_slice(&next[8..]);
+            next = keys.next().ok_or(AccessListEntryError::MissingChecksum)?;
+        }
+        if next[0] != PREFIX_CHECKSUM {
+            return Err(AccessListEntryError::UnexpectedEntry(next[0]));
+        }
+
+        accesses.push(MessageAccess {
+            chain_id: U256::from_be_bytes(chain_id),
+            block_number: u64::from_be_bytes(lookup[12..20].try_into().expect("sufficient length")),
+            timestamp: u64::from_be_bytes(lookup[20..28].try_into().expect("sufficient length")),
+            log_index: u32::from_be_bytes(lookup[28..].try_into().expect("sufficient length")),
+            checksum: *next,
+        });
+    }
+    Ok(accesses)
+}
+
+/// Decodes all executing message accesses declared for the `CrossL2Inbox` in the access list.
+pub fn decode_access_list(
+    access_list: &AccessList,
+) -> Result<Vec<MessageAccess>, AccessListEntryError> {
+    let mut accesses = Vec::new();
+    for item in access_list.iter().filter(|item| item.address == CROSS_L2_INBOX_ADDRESS) {
+        accesses.extend(decode_access_list_entries(&item.storage_keys)?);
+    }
+    Ok(accesses)
+}
+
+impl OpTxEnvelope {
+    /// Returns the executing message accesses declared in the access list of the transaction.
+    ///
+    /// Returns an empty list for transactions without an access list.
+    pub fn executing_message_accesses(&self) -> Result<Vec<MessageAccess>, AccessListEntryError> {
+        self.access_list().map_or_else(|| Ok(Vec::new()), decode_access_list)
+    }
+}
+
+/// An error decoding executing message accesses from access-list storage keys.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum AccessListEntryError {
+    /// An entry has an unexpected type byte at its position.
+    #[error("Unexpected access-list entry type: {0}")]
+    UnexpectedEntry(u8),
+    /// The zero padding of an entry with the given type byte is not zero.
+    #[error("Invalid padding in access-list entry of type {0}")]
+    InvalidPadding(u8),
+    /// A lookup entry is not followed by a checksum entry.
+    #[error("Missing access-list checksum entry")]
+    MissingChecksum,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interop::Identifier;
+    use alloc::vec;
+    use alloy_primitives::{address, b256, hex};
+
+    fn message(chain_id: U256) -> ExecutingMessage {
+        ExecutingMessage::new(
+            B256::repeat_byte(0xaa),
+            Identifier::new(
+                address!("0x4200000000000000000000000000000000000023"),
+                0x0102,
+                0x05,
+                0x0304,
+                chain_id,
+            ),
+        )
+    }
+
+    #[test]
+    fn test_lookup_entry() {
+        let access = MessageAccess::from(&message(U256::from(901)));
+        assert_eq!(
+            access.lookup_entry(),
+            b256!("0x0100000000000000000003850000000000000102000000000000030400000005")
+        );
+        assert_eq!(access.chain_id_extension_entry(), None);
+        assert_eq!(access.checksum[0], PREFIX_CHECKSUM);
+    }
+
+    #[test]
+    fn test_checksum() {
+        // The preimages are spelled out byte by byte, following the layout of the spec.
+        let log_hash = keccak256(hex!(
+            "4200000000000000000000000000000000000023"
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+        ));
+        let id_packed = hex!("0000000000000000000000000000000000000102000000000000030400000005");
+        let id_log_hash = keccak256([log_hash.as_slice(), &id_packed].concat());
+        let chain_id = hex!("0000000000000000000000000000000000000000000000000000000000000385");
+        let mut expected = keccak256([id_log_hash.as_slice(), &chain_id].concat());
+        expected[0] = PREFIX_CHECKSUM;
+
+        assert_eq!(message(U256::from(901)).checksum(), expected);
+    }
+
+    #[test]
+    fn test_access_list_roundtrip() {
+        let small = message(U256::from(10));
+        let large = message(U256::MAX - U256::from(1));
+        let item = encode_access_list([&small, &large]);
+        assert_eq!(item.storage_keys.len(), 5);
+
+        let access_list = AccessList(vec![
+            AccessListItem {
+                address: address!("0x4200000000000000000000000000000000000023"),
+                storage_keys: vec![B256::ZERO],
+            },
+            item,
+        ]);
+        assert_eq!(
+            decode_access_list(&access_list).unwrap(),
+            vec![MessageAccess::from(&small), MessageAccess::from(&large)]
+        );
+    }
+
+    #[test]
+    fn test_tx_envelope_accesses() {
+        use alloy_consensus::{Signed, TxEip1559};
+        use alloy_primitives::Signature;
+
+        let message = message(U256::from(10));
+        let tx = TxEip1559 {
+            access_list: AccessList(vec![encode_access_list([&message])]),
+            ..Default::default()
+        };
+        let envelope: OpTxEnvelope =
+            Signed::new_unchecked(tx, Signature::test_signature(), B256::ZERO).into();
+
+        assert_eq!(envelope.executing_message_accesses().unwrap(), vec![(&message).into()]);
+    }
+
+    #[test]
+    fn test_decode_invalid_entries() {
+        let keys: Vec<_> = message(U256::from(10)).access_list_storage_keys().collect();
+
+        assert_eq!(
+            decode_access_list_entries(&keys[..1]).unwrap_err(),
+            AccessListEntryError::MissingChecksum
+        );
+        assert_eq!(
+            decode_access_list_entries(&keys[1..]).unwrap_err(),
+            AccessListEntryError::UnexpectedEntry(PREFIX_CHECKSUM)
+        );
+        assert_eq!(
+            decode_access_list_entries(&[keys[0], keys[0]]).unwrap_err(),
+            AccessListEntryError::UnexpectedEntry(PREFIX_LOOKUP)
+        );
+    }
+}
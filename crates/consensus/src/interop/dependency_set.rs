@@ -0,0 +1,282 @@
+//! The interop dependency set, and validation of executing messages against it.
+//!
+//! Spec: <https://specs.optimism.io/interop/dependency-set.html>
+
+use super::ExecutingMessage;
+use alloc::collections::BTreeMap;
+use alloy_primitives::{ChainId, U256};
+
+/// The default time in seconds after which an initiating message can no longer be executed.
+///
+/// Spec: <https://specs.optimism.io/interop/messaging.html#message-expiry-invariant>
+pub const MESSAGE_EXPIRY_WINDOW: u64 = 604_800;
+
+/// The configuration of a chain in the [`DependencySet`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ChainDependency {
+    /// The timestamp at which interop activates on the chain.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub activation_time: u64,
+    /// The timestamp of the oldest block of the chain that messages can be executed from.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub history_min_time: u64,
+}
+
+/// The set of chains that can exchange interop messages with each other.
+///
+/// The JSON format matches the static dependency set config of op-supervisor:
+///
+/// ```json
+/// {
+///   "dependencies": { "901": { "activationTime": 0 }, "902": {} },
+///   "overrideMessageExpiryWindow": 3600
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct DependencySet {
+    /// The chains in the dependency set, by chain id.
+    pub dependencies: BTreeMap<ChainId, ChainDependency>,
+    /// Overrides the [`MESSAGE_EXPIRY_WINDOW`], for testing.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub override_message_expiry_window: Option<u64>,
+}
+
+impl DependencySet {
+    /// Returns the message expiry window in seconds.
+    pub fn message_expiry_window(&self) -> u64 {
+        self.override_message_expiry_window.unwrap_or(MESSAGE_EXPIRY_WINDOW)
+    }
+
+    /// Returns the configuration of the given chain, if it is in the dependency set.
+    pub fn chain(&self, chain_id: ChainId) -> Option<&ChainDependency> {
+        self.dependencies.get(&chain_id)
+    }
+
+    /// Returns true if the given chain is in the dependency set.
+    pub fn contains(&self, chain_id: ChainId) -> bool {
+        self.dependencies.contains_key(&chain_id)
+    }
+
+    /// Returns true if interop is active on the given chain at the given timestamp.
+    pub fn is_interop_active(&self, chain_id: ChainId, timestamp: u64) -> bool {
+        self.chain(chain_id).is_some_and(|chain| timestamp >= chain.activation_time)
+    }
+
+    /// Validates an executing message, included on the `executing_chain_id` in a block with the
+    /// `executing_timestamp`.
+    ///
+    /// This only checks the invariants that don't depend on the state of the initiating chain:
+    /// - Both chains are in the dependency set and have interop active.
+    /// - The initiating message is not older than the history of the initiating chain.
+    /// - The initiating message is not newer than the executing block.
+    /// - The initiating message did not expire before the executing block.
+    ///
+    /// Whether the initiating message actually exists must be checked separately, e.g. with
+    /// `supervisor_checkAccessList`.
+    pub fn validate_message(
+        &self,
+        message: &ExecutingMessage,
+        executing_chain_id: ChainId,
+        executing_timestamp: u64,
+    ) -> Result<(), MessageValidationError> {
+        let id = &message.identifier;
+        let initiating_chain_id = ChainId::try_from(id.chain_id)
+            .ok()
+            .filter(|chain_id| self.contains(*chain_id))
+            .ok_or(MessageValidationError::UnknownInitiatingChain(id.chain_id))?;
+        if !self.contains(executing_chain_id) {
+            return Err(MessageValidationError::UnknownExecutingChain(executing_chain_id));
+        }
+
+        if !self.is_interop_active(executing_chain_id, executing_timestamp) {
+            return Err(MessageValidationError::InteropNotActive {
+                chain_id: executing_chain_id,
+                timestamp: executing_timestamp,
+            });
+        }
+        let initiating_chain = &self.dependencies[&initiating_chain_id];
+        if id.timestamp < initiating_chain.activation_time {
+            return Err(MessageValidationError::InteropNotActive {
+                chain_id: initiating_chain_id,
+                timestamp: id.timestamp,
+            });
+        }
+        if id.timestamp < initiating_chain.history_min_time {
+            return Err(MessageValidationError::BeforeHistory {
+                chain_id: initiating_chain_id,
+                timestamp: id.timestamp,
+                history_min_time: initiating_chain.history_min_time,
+            });
+        }
+
+        if id.timestamp > executing_timestamp {
+            return Err(MessageValidationError::FutureMessage {
+                initiating: id.timestamp,
+                executing: executing_timestamp,
+            });
+        }
+        if id.timestamp.saturating_add(self.message_expiry_window()) < executing_timestamp {
+            return Err(MessageValidationError::ExpiredMessage {
+                initiating: id.timestamp,
+                executing: executing_timestamp,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// An invalid [`ExecutingMessage`], see [`DependencySet::validate_message`].
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum MessageValidationError {
+    /// The initiating chain is not in the dependency set.
+    #[error("Initiating chain {0} not in the dependency set")]
+    UnknownInitiatingChain(U256),
+    /// The executing chain is not in the dependency set.
+    #[error("Executing chain {0} not in the dependency set")]
+    UnknownExecutingChain(ChainId),
+    /// Interop is not active on a chain at the relevant timestamp.
+    #[error("Interop not active on chain {chain_id} at timestamp {timestamp}")]
+    InteropNotActive {
+        /// The chain id.
+        chain_id: ChainId,
+        /// The timestamp at which interop must be active.
+        timestamp: u64,
+    },
+    /// The initiating message is older than the history of the initiating chain.
+    #[error(
+        "Initiating timestamp {timestamp} on chain {chain_id} is before the history minimum time \
+         {history_min_time}"
+    )]
+    BeforeHistory {
+        /// The initiating chain id.
+        chain_id: ChainId,
+        /// The timestamp of the initiating message.
+        timestamp: u64,
+        /// The timestamp of the oldest block of the initiating chain.
+        history_min_time: u64,
+    },
+    /// The initiating message is newer than the executing block.
+    #[error("Initiating timestamp {initiating} is after executing timestamp {executing}")]
+    FutureMessage {
+        /// The timestamp of the initiating message.
+        initiating: u64,
+        /// The timestamp of the executing block.
+        executing: u64,
+    },
+    /// The initiating message expired before the executing block.
+    #[error("Initiating message at {initiating} expired at executing timestamp {executing}")]
+    ExpiredMessage {
+        /// The timestamp of the initiating message.
+        initiating: u64,
+        /// The timestamp of the executing block.
+        executing: u64,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interop::Identifier;
+    use alloy_primitives::{Address, B256};
+
+    fn dependency_set() -> DependencySet {
+        DependencySet {
+            dependencies: BTreeMap::from([
+                (901, ChainDependency::default()),
+                (902, ChainDependency { activation_time: 1_000, history_min_time: 0 }),
+            ]),
+            override_message_expiry_window: None,
+        }
+    }
+
+    fn message(chain_id: u64, timestamp: u64) -> ExecutingMessage {
+        ExecutingMessage::new(
+            B256::ZERO,
+            Identifier::new(Address::ZERO, 1, 0, timestamp, U256::from(chain_id)),
+        )
+    }
+
+    #[test]
+    fn test_validate_message() {
+        let set = dependency_set();
+
+        assert_eq!(set.validate_message(&message(902, 2_000), 901, 2_000), Ok(()));
+        assert_eq!(
+            set.validate_message(&message(902, 2_000), 901, 2_000 + MESSAGE_EXPIRY_WINDOW),
+            Ok(())
+        );
+        assert_eq!(
+            set.validate_message(&message(902, 2_000), 901, 2_001 + MESSAGE_EXPIRY_WINDOW),
+            Err(MessageValidationError::ExpiredMessage {
+                initiating: 2_000,
+                executing: 2_001 + MESSAGE_EXPIRY_WINDOW
+            })
+        );
+        assert_eq!(
+            set.validate_message(&message(902, 2_001), 901, 2_000),
+            Err(MessageValidationError::FutureMessage { initiating: 2_001, executing: 2_000 })
+        );
+    }
+
+    #[test]
+    fn test_validate_message_chains() {
+        let set = dependency_set();
+
+        assert_eq!(
+            set.validate_message(&message(903, 2_000), 901, 2_000),
+            Err(MessageValidationError::UnknownInitiatingChain(U256::from(903)))
+        );
+        assert_eq!(
+            set.validate_message(&message(901, 2_000), 903, 2_000),
+            Err(MessageValidationError::UnknownExecutingChain(903))
+        );
+        assert_eq!(
+            set.validate_message(&message(902, 999), 901, 2_000),
+            Err(MessageValidationError::InteropNotActive { chain_id: 902, timestamp: 999 })
+        );
+        assert_eq!(
+            set.validate_message(&message(901, 500), 902, 500),
+            Err(MessageValidationError::InteropNotActive { chain_id: 902, timestamp: 500 })
+        );
+    }
+
+    #[test]
+    fn test_validate_message_history() {
+        let mut set = dependency_set();
+        set.dependencies
+            .insert(903, ChainDependency { activation_time: 0, history_min_time: 1_500 });
+
+        assert_eq!(set.validate_message(&message(903, 1_500), 901, 2_000), Ok(()));
+        assert_eq!(
+            set.validate_message(&message(903, 1_499), 901, 2_000),
+            Err(MessageValidationError::BeforeHistory {
+                chain_id: 903,
+                timestamp: 1_499,
+                history_min_time: 1_500
+            })
+        );
+        // The history of the executing chain does not matter.
+        assert_eq!(set.validate_message(&message(901, 1_000), 903, 2_000), Ok(()));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_dependency_set() {
+        let json = r#"{"dependencies":{"901":{},"902":{"activationTime":1000}},"overrideMessageExpiryWindow":3600}"#;
+        let set: DependencySet = serde_json::from_str(json).unwrap();
+
+        assert_eq!(set.message_expiry_window(), 3600);
+        assert_eq!(set.dependencies, dependency_set().dependencies);
+
+        let json = r#"{"activationTime":1000,"historyMinTime":1500}"#;
+        assert_eq!(
+            serde_json::from_str::<ChainDependency>(json).unwrap(),
+            ChainDependency { activation_time: 1_000, history_min_time: 1_500 }
+        );
+    }
+}
@@ -0,0 +1,303 @@
+//! Interop messages, and their decoding from `CrossL2Inbox` logs.
+//!
+//! Spec: <https://specs.optimism.io/interop/messaging.html>
+
+use super::CROSS_L2_INBOX_ADDRESS;
+use crate::OpReceiptEnvelope;
+use alloc::vec::Vec;
+use alloy_primitives::{Address, B256, Bytes, Log, U256, b256, keccak256};
+
+/// The topic of the `ExecutingMessage(bytes32,(address,uint256,uint256,uint256,uint256))` event
+/// emitted by the `CrossL2Inbox` predeploy.
+pub const EXECUTING_MESSAGE_EVENT_TOPIC: B256 =
+    b256!("0x5c37832d2e8d10e346e55ad62071a6a2f9fa5130614ef2ec6617555c6f467ba7");
+
+/// The length of the ABI encoded [`Identifier`] in the `ExecutingMessage` event data.
+const IDENTIFIER_ABI_LEN: usize = 32 * 5;
+
+/// The identifier of an initiating message: the log that is being executed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Identifier {
+    /// The account that emitted the log.
+    pub origin: Address,
+    /// The number of the block that contains the log.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub block_number: u64,
+    /// The index of the log in the block.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub log_index: u32,
+    /// The timestamp of the block that contains the log.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub timestamp: u64,
+    /// The chain id of the chain that emitted the log.
+    #[cfg_attr(feature = "serde", serde(rename = "chainID"))]
+    pub chain_id: U256,
+}
+
+impl Identifier {
+    /// Creates a new [`Identifier`].
+    pub const fn new(
+        origin: Address,
+        block_number: u64,
+        log_index: u32,
+        timestamp: u64,
+        chain_id: U256,
+    ) -> Self {
+        Self { origin, block_number, log_index, timestamp, chain_id }
+    }
+
+    /// Decodes the ABI encoded `(address,uint256,uint256,uint256,uint256)` identifier tuple.
+    pub fn abi_decode(data: &[u8]) -> Result<Self, ExecutingMessageError> {
+        if data.len() != IDENTIFIER_ABI_LEN {
+            return Err(ExecutingMessageError::InvalidDataLength(data.len()));
+        }
+        let word = |i: usize| U256::from_be_slice(&data[i * 32..(i + 1) * 32]);
+        let origin: [u8; 32] = data[..32].try_into().expect("sufficient length");
+        if origin[..12] != [0u8; 12] {
+            return Err(ExecutingMessageError::InvalidOrigin);
+        }
+
+        Ok(Self {
+            origin: Address::from_word(origin.into()),
+            block_number: word(1).try_into().map_err(|_| ExecutingMessageError::ValueOverflow)?,
+            log_index: word(2).try_into().map_err(|_| ExecutingMessageError::ValueOverflow)?,
+            timestamp: word(3).try_into().map_err(|_| ExecutingMessageError::ValueOverflow)?,
+            chain_id: word(4),
+        })
+    }
+
+    /// ABI encodes the identifier as a `(address,uint256,uint256,uint256,uint256)` tuple.
+    pub fn abi_encode(&self) -> Bytes {
+        let mut data = Vec::with_capacity(IDENTIFIER_ABI_LEN);
+        data.extend_from_slice(self.origin.into_word().as_slice());
+        data.extend_from_slice(&U256::from(self.block_number).to_be_bytes::<32>());
+        data.extend_from_slice(&U256::from(self.log_index).to_be_bytes::<32>());
+        data.extend_from_slice(&U256::from(self.timestamp).to_be_bytes::<32>());
+        data.extend_from_slice(&self.chain_id.to_be_bytes::<32>());
+        data.into()
+    }
+}
+
+/// The payload of an initiating message: the topics of the log, followed by its data.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, derive_more::Deref, derive_more::From)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MessagePayload(pub Bytes);
+
+impl MessagePayload {
+    /// Returns the message payload of the given initiating log.
+    pub fn from_log(log: &Log) -> Self {
+        let mut payload = Vec::with_capacity(log.topics().len() * 32 + log.data.data.len());
+        for topic in log.topics() {
+            payload.extend_from_slice(topic.as_slice());
+        }
+        payload.extend_from_slice(&log.data.data);
+        Self(payload.into())
+    }
+
+    /// Returns the hash of the message payload, the `msgHash` of the `ExecutingMessage` event.
+    pub fn hash(&self) -> B256 {
+        keccak256(&self.0)
+    }
+}
+
+/// An executing message, as emitted by the `CrossL2Inbox` when a message is validated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ExecutingMessage {
+    /// The hash of the [`MessagePayload`] of the initiating message.
+    pub payload_hash: B256,
+    /// The identifier of the initiating message.
+    pub identifier: Identifier,
+}
+
+impl ExecutingMessage {
+    /// Creates a new [`ExecutingMessage`].
+    pub const fn new(payload_hash: B256, identifier: Identifier) -> Self {
+        Self { payload_hash, identifier }
+    }
+
+    /// Decodes the [`ExecutingMessage`] from an `ExecutingMessage` event emitted by the
+    /// `CrossL2Inbox`.
+    pub fn decode_log(log: &Log) -> Result<Self, ExecutingMessageError> {
+        if log.address != CROSS_L2_INBOX_ADDRESS {
+            return Err(ExecutingMessageError::UnexpectedAddress(log.address));
+        }
+        let topics = log.topics();
+        if topics.len() != 2 {
+            return Err(ExecutingMessageError::UnexpectedTopicsLen(topics.len()));
+        }
+        if topics[0] != EXECUTING_MESSAGE_EVENT_TOPIC {
+            return Err(ExecutingMessageError::InvalidSelector(topics[0]));
+        }
+        Ok(Self { payload_hash: topics[1], identifier: Identifier::abi_decode(&log.data.data)? })
+    }
+
+    /// Encodes the [`ExecutingMessage`] into the event emitted by the `CrossL2Inbox`.
+    pub fn to_log(&self) -> Log {
+        Log::new_unchecked(
+            CROSS_L2_INBOX_ADDRESS,
+            alloc::vec![EXECUTING_MESSAGE_EVENT_TOPIC, self.payload_hash],
+            self.identifier.abi_encode(),
+        )
+    }
+
+    /// Returns the hash of the initiating log: `keccak256(origin ++ payload_hash)`.
+    pub fn log_hash(&self) -> B256 {
+        payload_hash_to_log_hash(self.payload_hash, self.identifier.origin)
+    }
+
+    /// Returns true if the given log is an `ExecutingMessage` event emitted by the
+    /// `CrossL2Inbox`.
+    pub fn is_executing_message_log(log: &Log) -> bool {
+        log.address == CROSS_L2_INBOX_ADDRESS
+            && log.topics().first() == Some(&EXECUTING_MESSAGE_EVENT_TOPIC)
+    }
+}
+
+/// Returns the log hash of an initiating message with the given payload hash, emitted by
+/// `origin`: `keccak256(origin ++ payload_hash)`.
+pub fn payload_hash_to_log_hash(payload_hash: B256, origin: Address) -> B256 {
+    let mut input = [0u8; 52];
+    input[..20].copy_from_slice(origin.as_slice());
+    input[20..].copy_from_slice(payload_hash.as_slice());
+    keccak256(input)
+}
+
+/// Decodes all executing messages from the receipts of a block, in log order.
+///
+/// Failed receipts don't contain logs, so only the messages of successful transactions are
+/// returned.
+pub fn executing_messages_from_receipts<'a>(
+    receipts: impl IntoIterator<Item = &'a OpReceiptEnvelope>,
+) -> Result<Vec<ExecutingMessage>, ExecutingMessageError> {
+    receipts
+        .into_iter()
+        .flat_map(|receipt| receipt.logs())
+        .filter(|log| ExecutingMessage::is_executing_message_log(log))
+        .map(ExecutingMessage::decode_log)
+        .collect()
+}
+
+/// An error decoding an [`ExecutingMessage`] from a log.
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+pub enum ExecutingMessageError {
+    /// The log was not emitted by the `CrossL2Inbox`.
+    #[error("Log not emitted by the CrossL2Inbox: {0}")]
+    UnexpectedAddress(Address),
+    /// The log does not have the expected number of topics.
+    #[error("Unexpected number of executing message topics: {0}")]
+    UnexpectedTopicsLen(usize),
+    /// The first topic is not the executing message topic.
+    #[error("Invalid executing message selector: {0}")]
+    InvalidSelector(B256),
+    /// The log data is not an ABI encoded identifier.
+    #[error("Unexpected identifier data length: {0}")]
+    InvalidDataLength(usize),
+    /// The origin word of the identifier is not a left-padded address.
+    #[error("Invalid identifier origin")]
+    InvalidOrigin,
+    /// A value in the identifier does not fit its target type.
+    #[error("Identifier value overflow")]
+    ValueOverflow,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_consensus::{Receipt, ReceiptWithBloom};
+    use alloy_primitives::{address, bytes};
+
+    fn initiating_log() -> Log {
+        Log::new_unchecked(
+            address!("0x4200000000000000000000000000000000000023"),
+            vec![B256::repeat_byte(1), B256::repeat_byte(2)],
+            bytes!("c0ffee"),
+        )
+    }
+
+    fn executing_message() -> ExecutingMessage {
+        ExecutingMessage::new(
+            MessagePayload::from_log(&initiating_log()).hash(),
+            Identifier::new(
+                address!("0x4200000000000000000000000000000000000023"),
+                100,
+                3,
+                1_700_000_000,
+                U256::from(901),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_event_topic() {
+        assert_eq!(
+            EXECUTING_MESSAGE_EVENT_TOPIC,
+            keccak256("ExecutingMessage(bytes32,(address,uint256,uint256,uint256,uint256))")
+        );
+    }
+
+    #[test]
+    fn test_message_payload() {
+        let payload = MessagePayload::from_log(&initiating_log());
+        assert_eq!(payload.len(), 67);
+        assert_eq!(&payload[64..], &[0xc0, 0xff, 0xee]);
+    }
+
+    #[test]
+    fn test_executing_message_log_roundtrip() {
+        let message = executing_message();
+        let log = message.to_log();
+
+        assert!(ExecutingMessage::is_executing_message_log(&log));
+        assert_eq!(ExecutingMessage::decode_log(&log).unwrap(), message);
+        assert_eq!(
+            message.log_hash(),
+            keccak256(
+                [message.identifier.origin.as_slice(), message.payload_hash.as_slice()].concat()
+            )
+        );
+    }
+
+    #[test]
+    fn test_decode_log_invalid() {
+        let mut log = executing_message().to_log();
+        log.data = alloy_primitives::LogData::new_unchecked(log.topics().to_vec(), bytes!("00"));
+        assert_eq!(
+            ExecutingMessage::decode_log(&log).unwrap_err(),
+            ExecutingMessageError::InvalidDataLength(1)
+        );
+        assert_eq!(
+            ExecutingMessage::decode_log(&initiating_log()).unwrap_err(),
+            ExecutingMessageError::UnexpectedAddress(initiating_log().address)
+        );
+    }
+
+    #[test]
+    fn test_executing_messages_from_receipts() {
+        let receipt = |logs| {
+            OpReceiptEnvelope::Eip1559(ReceiptWithBloom::new(
+                Receipt { status: true.into(), cumulative_gas_used: 0, logs },
+                Default::default(),
+            ))
+        };
+        let receipts = vec![
+            receipt(vec![initiating_log()]),
+            receipt(vec![executing_message().to_log(), initiating_log()]),
+        ];
+
+        assert_eq!(executing_messages_from_receipts(&receipts).unwrap(), vec![executing_message()]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_identifier_serde() {
+        let json = r#"{"origin":"0x4200000000000000000000000000000000000023","blockNumber":"0x64","logIndex":"0x3","timestamp":"0x6553f100","chainID":"0x385"}"#;
+        let identifier: Identifier = serde_json::from_str(json).unwrap();
+        assert_eq!(identifier, executing_message().identifier);
+        assert_eq!(serde_json::to_string(&identifier).unwrap(), json);
+    }
+}
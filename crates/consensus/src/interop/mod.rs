@@ -5,11 +5,44 @@ use alloy_primitives::{Address, address};
 use core::str::FromStr;
 use derive_more::Display;
 
+mod access_list;
+pub use access_list::{
+    AccessListEntryError, MessageAccess, PREFIX_CHAIN_ID_EXTENSION, PREFIX_CHECKSUM, PREFIX_LOOKUP,
+    decode_access_list, decode_access_list_entries, encode_access_list,
+};
+
+mod super_root;
+pub use super_root::{
+    OptimisticBlock, OutputRootWithChain, SUPER_ROOT_VERSION, SuperRoot, SuperRootError,
+    TRANSITION_STATE_VERSION, TransitionState,
+};
+
+mod dependency_set;
+pub use dependency_set::{
+    ChainDependency, DependencySet, MESSAGE_EXPIRY_WINDOW, MessageValidationError,
+};
+
+mod replacement;
+pub use replacement::{
+    BLOCK_REPLACEMENT_DEPOSIT_GAS_LIMIT, BLOCK_REPLACEMENT_DEPOSITOR_ADDRESS, replaced_output_root,
+};
+
+mod message;
+pub use message::{
+    EXECUTING_MESSAGE_EVENT_TOPIC, ExecutingMessage, ExecutingMessageError, Identifier,
+    MessagePayload, executing_messages_from_receipts, payload_hash_to_log_hash,
+};
+
 /// The address of the L2 cross chain inbox predeploy proxy.
 pub const CROSS_L2_INBOX_ADDRESS: Address = address!("0x4200000000000000000000000000000000000022");
 
 /// The safety level of a message.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+///
+/// Safety levels are ordered from least to most safe: [`Invalid`](Self::Invalid) <
+/// [`LocalUnsafe`](Self::LocalUnsafe) < [`CrossUnsafe`](Self::CrossUnsafe) <
+/// [`LocalSafe`](Self::LocalSafe) < [`CrossSafe`](Self::CrossSafe) <
+/// [`Finalized`](Self::Finalized).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum SafetyLevel {
@@ -29,6 +62,37 @@ pub enum SafetyLevel {
     Invalid,
 }
 
+impl SafetyLevel {
+    /// Returns the rank of the safety level, higher is safer.
+    const fn rank(&self) -> u8 {
+        match self {
+            Self::Invalid => 0,
+            Self::LocalUnsafe => 1,
+            Self::CrossUnsafe => 2,
+            Self::LocalSafe => 3,
+            Self::CrossSafe => 4,
+            Self::Finalized => 5,
+        }
+    }
+
+    /// Returns true if this safety level is at least as safe as the given one.
+    pub const fn is_at_least(&self, other: Self) -> bool {
+        self.rank() >= other.rank()
+    }
+}
+
+impl PartialOrd for SafetyLevel {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SafetyLevel {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
 impl FromStr for SafetyLevel {
     type Err = SafetyLevelParseError;
 
@@ -85,6 +149,17 @@ mod tests {
         assert_eq!(SafetyLevel::from_str("invalid").unwrap(), SafetyLevel::Invalid);
     }
 
+    #[test]
+    fn test_safety_level_ord() {
+        assert!(SafetyLevel::Finalized > SafetyLevel::CrossSafe);
+        assert!(SafetyLevel::CrossSafe > SafetyLevel::LocalSafe);
+        assert!(SafetyLevel::LocalSafe > SafetyLevel::CrossUnsafe);
+        assert!(SafetyLevel::CrossUnsafe > SafetyLevel::LocalUnsafe);
+        assert!(SafetyLevel::LocalUnsafe > SafetyLevel::Invalid);
+        assert!(SafetyLevel::CrossSafe.is_at_least(SafetyLevel::CrossSafe));
+        assert!(!SafetyLevel::LocalUnsafe.is_at_least(SafetyLevel::CrossUnsafe));
+    }
+
     #[test]
     fn test_safety_level_from_str_invalid() {
         assert!(SafetyLevel::from_str("unknown").is_err());
@@ -0,0 +1,96 @@
+//! Replacement of L2 blocks that were invalidated by interop.
+//!
+//! When a block contains an invalid executing message, it is replaced by a deposits-only block
+//! that carries an extra deposit, committing to the output root of the invalidated block.
+//!
+//! Spec: <https://specs.optimism.io/interop/derivation.html#replacing-invalid-blocks>
+
+use crate::{InteropBlockReplacementDepositSource, OpTxEnvelope, OutputRoot, TxDeposit};
+use alloy_primitives::{Address, TxKind, U256, address};
+
+/// The address of the block replacement depositor account, both the sender and the recipient of
+/// the block replacement deposit.
+pub const BLOCK_REPLACEMENT_DEPOSITOR_ADDRESS: Address =
+    address!("0xdeaddeaddeaddeaddeaddeaddeaddeaddead0002");
+
+/// The gas limit of the block replacement deposit.
+pub const BLOCK_REPLACEMENT_DEPOSIT_GAS_LIMIT: u64 = 36_000;
+
+impl TxDeposit {
+    /// Returns the deposit that is appended to the replacement of the block with the given
+    /// output root.
+    ///
+    /// The input of the deposit is the output root preimage of the invalidated block.
+    pub fn block_replacement(invalidated: &OutputRoot) -> Self {
+        Self {
+            source_hash: InteropBlockReplacementDepositSource::new(invalidated.hash())
+                .source_hash(),
+            from: BLOCK_REPLACEMENT_DEPOSITOR_ADDRESS,
+            to: TxKind::Call(BLOCK_REPLACEMENT_DEPOSITOR_ADDRESS),
+            mint: 0,
+            value: U256::ZERO,
+            gas_limit: BLOCK_REPLACEMENT_DEPOSIT_GAS_LIMIT,
+            is_system_transaction: false,
+            eth_value: 0,
+            input: invalidated.encoded(),
+            eth_tx_value: None,
+        }
+    }
+
+    /// Returns the output root of the invalidated block, if this is a block replacement deposit.
+    pub fn replaced_output_root(&self) -> Option<OutputRoot> {
+        if self.from != BLOCK_REPLACEMENT_DEPOSITOR_ADDRESS
+            || self.to != TxKind::Call(BLOCK_REPLACEMENT_DEPOSITOR_ADDRESS)
+        {
+            return None;
+        }
+        let output_root = OutputRoot::decode(&self.input).ok()?;
+        (InteropBlockReplacementDepositSource::new(output_root.hash()).source_hash()
+            == self.source_hash)
+            .then_some(output_root)
+    }
+}
+
+/// Returns the output root of the invalidated block, if the block with the given transactions is
+/// a replacement block.
+pub fn replaced_output_root<'a>(
+    transactions: impl IntoIterator<Item = &'a OpTxEnvelope>,
+) -> Option<OutputRoot> {
+    transactions
+        .into_iter()
+        .map_while(OpTxEnvelope::as_deposit)
+        .find_map(|deposit| deposit.replaced_output_root())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_consensus::Sealable;
+    use alloy_primitives::B256;
+
+    #[test]
+    fn test_block_replacement_deposit() {
+        let invalidated =
+            OutputRoot::new(B256::repeat_byte(1), B256::repeat_byte(2), B256::repeat_byte(3));
+        let deposit = TxDeposit::block_replacement(&invalidated);
+
+        assert_eq!(deposit.input.len(), OutputRoot::ENCODED_LENGTH);
+        assert_eq!(deposit.replaced_output_root(), Some(invalidated));
+
+        let mut forged = deposit;
+        forged.source_hash = B256::ZERO;
+        assert_eq!(forged.replaced_output_root(), None);
+    }
+
+    #[test]
+    fn test_replaced_output_root() {
+        let invalidated = OutputRoot::new(B256::ZERO, B256::ZERO, B256::repeat_byte(3));
+        let l1_info = OpTxEnvelope::Deposit(TxDeposit::default().seal_slow());
+        let replacement =
+            OpTxEnvelope::Deposit(TxDeposit::block_replacement(&invalidated).seal_slow());
+
+        assert_eq!(replaced_output_root(&vec![l1_info.clone(), replacement]), Some(invalidated));
+        assert_eq!(replaced_output_root(&vec![l1_info]), None);
+    }
+}
@@ -0,0 +1,395 @@
+//! Super roots and the transition states between them.
+//!
+//! Spec: <https://specs.optimism.io/interop/fault-proof.html>
+
+use alloc::vec::Vec;
+use alloy_primitives::{B256, ChainId, U256, keccak256};
+use alloy_rlp::{Buf, BufMut, Decodable, Encodable, Header};
+
+/// The version byte of the [`SuperRoot`] encoding.
+pub const SUPER_ROOT_VERSION: u8 = 1;
+
+/// The version byte of the [`TransitionState`] encoding.
+pub const TRANSITION_STATE_VERSION: u8 = 255;
+
+/// The output root of a single chain in a [`SuperRoot`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct OutputRootWithChain {
+    /// The chain id.
+    pub chain_id: ChainId,
+    /// The output root of the chain.
+    pub output_root: B256,
+}
+
+impl OutputRootWithChain {
+    /// Creates a new [`OutputRootWithChain`].
+    pub const fn new(chain_id: ChainId, output_root: B256) -> Self {
+        Self { chain_id, output_root }
+    }
+}
+
+/// A super root: the output roots of all chains in the dependency set at a timestamp.
+///
+/// The encoding is `version ++ timestamp ++ (chain_id ++ output_root)*`, with the timestamp as a
+/// big-endian `u64`, the chain ids as big-endian `uint256`, and the chains sorted by chain id.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SuperRoot {
+    /// The timestamp of the super root.
+    pub timestamp: u64,
+    /// The output roots of the chains, sorted by chain id.
+    pub output_roots: Vec<OutputRootWithChain>,
+}
+
+impl SuperRoot {
+    /// Creates a new [`SuperRoot`], sorting the output roots by chain id.
+    pub fn new(timestamp: u64, mut output_roots: Vec<OutputRootWithChain>) -> Self {
+        output_roots.sort_by_key(|root| root.chain_id);
+        Self { timestamp, output_roots }
+    }
+
+    /// Returns the length of the encoded super root.
+    pub const fn encoded_length(&self) -> usize {
+        1 + 8 + self.output_roots.len() * 64
+    }
+
+    /// Encodes the super root.
+    pub fn encode(&self, out: &mut dyn BufMut) {
+        out.put_u8(SUPER_ROOT_VERSION);
+        out.put_u64(self.timestamp);
+        for root in &self.output_roots {
+            out.put_slice(&U256::from(root.chain_id).to_be_bytes::<32>());
+            out.put_slice(root.output_root.as_slice());
+        }
+    }
+
+    /// Returns the encoded super root.
+    pub fn encoded(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.encoded_length());
+        self.encode(&mut out);
+        out
+    }
+
+    /// Decodes a super root, requiring the output roots to be sorted by chain id.
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, SuperRootError> {
+        if buf.len() < 9 || !(buf.len() - 9).is_multiple_of(64) {
+            return Err(SuperRootError::UnexpectedLength(buf.len()));
+        }
+        let version = buf.get_u8();
+        if version != SUPER_ROOT_VERSION {
+            return Err(SuperRootError::InvalidVersion(version));
+        }
+        let timestamp = buf.get_u64();
+
+        let mut output_roots = Vec::with_capacity(buf.len() / 64);
+        while !buf.is_empty() {
+            let chain_id = U256::from_be_slice(&buf[..32])
+                .try_into()
+                .map_err(|_| SuperRootError::ChainIdOverflow)?;
+            let output_root = B256::from_slice(&buf[32..64]);
+            buf.advance(64);
+
+            if output_roots
+                .last()
+                .is_some_and(|last: &OutputRootWithChain| last.chain_id >= chain_id)
+            {
+                return Err(SuperRootError::UnsortedChains);
+            }
+            output_roots.push(OutputRootWithChain { chain_id, output_root });
+        }
+        Ok(Self { timestamp, output_roots })
+    }
+
+    /// Returns the super root hash, the keccak256 hash of the encoded super root.
+    pub fn hash(&self) -> B256 {
+        keccak256(self.encoded())
+    }
+
+    /// Returns the output root of the given chain, if it is part of the super root.
+    pub fn output_root(&self, chain_id: ChainId) -> Option<B256> {
+        self.output_roots
+            .binary_search_by_key(&chain_id, |root| root.chain_id)
+            .ok()
+            .map(|index| self.output_roots[index].output_root)
+    }
+}
+
+/// An error decoding a [`SuperRoot`] or [`TransitionState`].
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+pub enum SuperRootError {
+    /// The encoding has an unexpected length.
+    #[error("Unexpected super root length: {0}")]
+    UnexpectedLength(usize),
+    /// The encoding has an unexpected version byte.
+    #[error("Invalid version byte: {0}")]
+    InvalidVersion(u8),
+    /// A chain id does not fit in a `u64`.
+    #[error("Chain id overflow")]
+    ChainIdOverflow,
+    /// The chains are not sorted by chain id, or contain duplicates.
+    #[error("Chains not sorted by chain id")]
+    UnsortedChains,
+    /// The RLP encoding of the transition state is invalid.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+}
+
+/// A block that was optimistically derived for a chain while transitioning between super roots.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct OptimisticBlock {
+    /// The block hash.
+    pub block_hash: B256,
+    /// The output root after the block.
+    pub output_root: B256,
+}
+
+impl OptimisticBlock {
+    /// Creates a new [`OptimisticBlock`].
+    pub const fn new(block_hash: B256, output_root: B256) -> Self {
+        Self { block_hash, output_root }
+    }
+
+    fn rlp_payload_length(&self) -> usize {
+        self.block_hash.length() + self.output_root.length()
+    }
+}
+
+impl Encodable for OptimisticBlock {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.rlp_payload_length() }.encode(out);
+        self.block_hash.encode(out);
+        self.output_root.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.rlp_payload_length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for OptimisticBlock {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        let started_len = buf.len();
+        let this =
+            Self { block_hash: Decodable::decode(buf)?, output_root: Decodable::decode(buf)? };
+        if started_len - buf.len() != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: started_len - buf.len(),
+            });
+        }
+        Ok(this)
+    }
+}
+
+/// The intermediate state of the interop fault proof between two super roots.
+///
+/// Starting from the `pre_state` super root, each step derives the next block of one chain,
+/// in chain id order, and appends it to the `pending_progress`. Once all chains progressed, the
+/// blocks are checked for interop validity, and the next super root is formed.
+///
+/// The encoding is `255 ++ rlp([pre_state, pending_progress, step])`, with the `pre_state` as its
+/// encoded super root bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TransitionState {
+    /// The super root the transition starts from.
+    pub pre_state: SuperRoot,
+    /// The blocks derived so far, one for each chain in chain id order.
+    pub pending_progress: Vec<OptimisticBlock>,
+    /// The number of steps taken since the `pre_state`.
+    pub step: u64,
+}
+
+impl TransitionState {
+    /// Creates a new [`TransitionState`].
+    pub const fn new(
+        pre_state: SuperRoot,
+        pending_progress: Vec<OptimisticBlock>,
+        step: u64,
+    ) -> Self {
+        Self { pre_state, pending_progress, step }
+    }
+
+    fn rlp_payload_length(&self) -> usize {
+        let pre_state_length = self.pre_state.encoded_length();
+        let pre_state_length = pre_state_length + alloy_rlp::length_of_length(pre_state_length);
+        pre_state_length + alloy_rlp::list_length(&self.pending_progress) + self.step.length()
+    }
+
+    /// Encodes the transition state.
+    pub fn encode(&self, out: &mut dyn BufMut) {
+        out.put_u8(TRANSITION_STATE_VERSION);
+        Header { list: true, payload_length: self.rlp_payload_length() }.encode(out);
+        self.pre_state.encoded().as_slice().encode(out);
+        alloy_rlp::encode_list(&self.pending_progress, out);
+        self.step.encode(out);
+    }
+
+    /// Returns the encoded transition state.
+    pub fn encoded(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode(&mut out);
+        out
+    }
+
+    /// Decodes a transition state.
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, SuperRootError> {
+        let version = *buf.first().ok_or(SuperRootError::UnexpectedLength(0))?;
+        if version != TRANSITION_STATE_VERSION {
+            return Err(SuperRootError::InvalidVersion(version));
+        }
+        buf.advance(1);
+
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString.into());
+        }
+        let started_len = buf.len();
+        let pre_state = Header::decode_bytes(buf, false)?;
+        let pre_state = SuperRoot::decode(&mut &pre_state[..])?;
+        let pending_progress = Decodable::decode(buf)?;
+        let step = Decodable::decode(buf)?;
+        if started_len - buf.len() != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: started_len - buf.len(),
+            }
+            .into());
+        }
+        Ok(Self { pre_state, pending_progress, step })
+    }
+
+    /// Returns the hash of the transition state, the keccak256 hash of its encoding.
+    pub fn hash(&self) -> B256 {
+        keccak256(self.encoded())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_primitives::hex;
+
+    fn super_root() -> SuperRoot {
+        SuperRoot::new(
+            0x64,
+            vec![
+                OutputRootWithChain::new(902, B256::repeat_byte(2)),
+                OutputRootWithChain::new(901, B256::repeat_byte(1)),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_super_root_encoding() {
+        let root = super_root();
+        let encoded = root.encoded();
+
+        assert_eq!(encoded.len(), root.encoded_length());
+        assert_eq!(encoded[0], SUPER_ROOT_VERSION);
+        assert_eq!(&encoded[1..9], &0x64u64.to_be_bytes());
+        assert_eq!(U256::from_be_slice(&encoded[9..41]), U256::from(901));
+        assert_eq!(&encoded[41..73], B256::repeat_byte(1).as_slice());
+        assert_eq!(root.hash(), keccak256(&encoded));
+
+        assert_eq!(SuperRoot::decode(&mut encoded.as_slice()).unwrap(), root);
+        assert_eq!(root.output_root(902), Some(B256::repeat_byte(2)));
+        assert_eq!(root.output_root(903), None);
+    }
+
+    #[test]
+    fn test_super_root_decode_invalid() {
+        let mut encoded = super_root().encoded();
+
+        assert_eq!(
+            SuperRoot::decode(&mut &encoded[..40]).unwrap_err(),
+            SuperRootError::UnexpectedLength(40)
+        );
+
+        encoded[0] = 2;
+        assert_eq!(
+            SuperRoot::decode(&mut encoded.as_slice()).unwrap_err(),
+            SuperRootError::InvalidVersion(2)
+        );
+
+        let unsorted = SuperRoot {
+            timestamp: 0,
+            output_roots: vec![
+                OutputRootWithChain::new(902, B256::ZERO),
+                OutputRootWithChain::new(901, B256::ZERO),
+            ],
+        };
+        assert_eq!(
+            SuperRoot::decode(&mut unsorted.encoded().as_slice()).unwrap_err(),
+            SuperRootError::UnsortedChains
+        );
+    }
+
+    #[test]
+    fn test_transition_state_roundtrip() {
+        let state = TransitionState::new(
+            super_root(),
+            vec![OptimisticBlock::new(B256::repeat_byte(3), B256::repeat_byte(4))],
+            1,
+        );
+        let encoded = state.encoded();
+
+        assert_eq!(encoded[0], TRANSITION_STATE_VERSION);
+        assert_eq!(TransitionState::decode(&mut encoded.as_slice()).unwrap(), state);
+        assert_eq!(state.hash(), keccak256(&encoded));
+
+        let empty = TransitionState::new(super_root(), vec![], 0);
+        assert_eq!(TransitionState::decode(&mut empty.encoded().as_slice()).unwrap(), empty);
+    }
+
+    #[test]
+    fn test_hash_reference_vectors() {
+        // The preimages are spelled out byte by byte, following the encodings of the spec.
+        let super_root_preimage = [
+            &[SUPER_ROOT_VERSION][..],
+            &hex!("0000000000000064"),
+            &hex!("0000000000000000000000000000000000000000000000000000000000000385"),
+            &[1; 32],
+            &hex!("0000000000000000000000000000000000000000000000000000000000000386"),
+            &[2; 32],
+        ]
+        .concat();
+        assert_eq!(super_root().hash(), keccak256(&super_root_preimage));
+
+        let state = TransitionState::new(
+            super_root(),
+            vec![OptimisticBlock::new(B256::repeat_byte(3), B256::repeat_byte(4))],
+            1,
+        );
+        let transition_state_preimage = [
+            &[TRANSITION_STATE_VERSION][..],
+            // rlp list header, 210 bytes
+            &hex!("f8d2"),
+            // rlp string header of the super root, 137 bytes
+            &hex!("b889"),
+            &super_root_preimage,
+            // rlp list headers of the pending progress and of its single block
+            &hex!("f844f842"),
+            &hex!("a0"),
+            &[3; 32],
+            &hex!("a0"),
+            &[4; 32],
+            // step
+            &hex!("01"),
+        ]
+        .concat();
+        assert_eq!(state.hash(), keccak256(&transition_state_preimage));
+    }
+}
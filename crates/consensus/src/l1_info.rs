@@ -0,0 +1,468 @@
+//! The L1 info deposit, the first transaction of every L2 block.
+//!
+//! Spec: <https://specs.optimism.io/protocol/deposits.html#l1-attributes-deposited-transaction>
+
+use crate::OpHardforkSchedule;
+use alloc::vec;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{Address, B256, U256, address};
+
+/// The address of the L1 info depositor account, the sender of the L1 info deposit.
+pub const L1_INFO_DEPOSITOR_ADDRESS: Address =
+    address!("0xdeaddeaddeaddeaddeaddeaddeaddeaddead0001");
+
+/// The address of the `L1Block` predeploy, the recipient of the L1 info deposit.
+pub const L1_BLOCK_ADDRESS: Address = address!("0x4200000000000000000000000000000000000015");
+
+/// The selector of `setL1BlockValues`, the ABI encoded L1 info calldata before Ecotone.
+pub const L1_INFO_BEDROCK_SELECTOR: [u8; 4] = [0x01, 0x5d, 0x8e, 0xb9];
+
+/// The selector of `setL1BlockValuesEcotone`, the packed L1 info calldata since Ecotone.
+pub const L1_INFO_ECOTONE_SELECTOR: [u8; 4] = [0x44, 0x0a, 0x5e, 0x20];
+
+/// The selector of `setL1BlockValuesIsthmus`, the packed L1 info calldata since Isthmus.
+pub const L1_INFO_ISTHMUS_SELECTOR: [u8; 4] = [0x09, 0x89, 0x99, 0xbe];
+
+/// The selector of `setL1BlockValuesJovian`, the packed L1 info calldata since Jovian.
+pub const L1_INFO_JOVIAN_SELECTOR: [u8; 4] = [0x3d, 0xb6, 0xbe, 0x2b];
+
+/// The L1 origin and sequence number of an L2 block, decoded from the calldata of its L1 info
+/// deposit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct L1InfoOrigin {
+    /// The L1 origin of the block.
+    pub l1_origin: BlockNumHash,
+    /// The distance to the first block of the epoch.
+    pub seq_num: u64,
+}
+
+impl L1InfoOrigin {
+    /// Decodes the L1 origin and sequence number from the calldata of an L1 info deposit.
+    ///
+    /// Only the fields shared by all versions are read, so trailing fields added by later
+    /// versions of the calldata are ignored.
+    pub fn decode(input: &[u8]) -> Result<Self, L1InfoError> {
+        let (selector, data) = input.split_first_chunk::<4>().ok_or(L1InfoError::Empty)?;
+        // The offsets of the number, hash and sequence number after the selector.
+        let (number, hash, seq_num, len) = match *selector {
+            L1_INFO_BEDROCK_SELECTOR => (24, 96, 152, 256),
+            L1_INFO_ECOTONE_SELECTOR | L1_INFO_ISTHMUS_SELECTOR | L1_INFO_JOVIAN_SELECTOR => {
+                (24, 96, 8, 160)
+            }
+            selector => return Err(L1InfoError::UnknownSelector(selector)),
+        };
+        if data.len() < len {
+            return Err(L1InfoError::InvalidLength(input.len()));
+        }
+        let read_u64 = |offset: usize| {
+            u64::from_be_bytes(data[offset..offset + 8].try_into().expect("8 bytes"))
+        };
+        Ok(Self {
+            l1_origin: BlockNumHash {
+                number: read_u64(number),
+                hash: B256::from_slice(&data[hash..hash + 32]),
+            },
+            seq_num: read_u64(seq_num),
+        })
+    }
+}
+
+/// The L1 block attributes and L1 fee parameters of an L2 block, decoded from the calldata of its
+/// L1 info deposit.
+///
+/// The fee parameters that are not part of the calldata version are `None`: the Bedrock overhead
+/// and scalar are replaced by the Ecotone scalars, Isthmus adds the operator fee parameters and
+/// Jovian the DA footprint gas scalar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct L1BlockInfoTx {
+    /// The L1 origin of the block.
+    pub l1_origin: BlockNumHash,
+    /// The timestamp of the L1 origin.
+    pub l1_timestamp: u64,
+    /// The distance to the first block of the epoch.
+    pub seq_num: u64,
+    /// The base fee of the L1 origin.
+    pub base_fee: U256,
+    /// The versioned batcher address.
+    pub batcher_hash: B256,
+    /// The L1 fee overhead, before Ecotone.
+    pub l1_fee_overhead: Option<U256>,
+    /// The L1 fee scalar, scaled by `1e6`, before Ecotone.
+    pub l1_fee_scalar: Option<U256>,
+    /// The scalar of the L1 base fee, since Ecotone.
+    pub base_fee_scalar: Option<u32>,
+    /// The blob base fee of the L1 origin, since Ecotone.
+    pub blob_base_fee: Option<U256>,
+    /// The scalar of the L1 blob base fee, since Ecotone.
+    pub blob_base_fee_scalar: Option<u32>,
+    /// The operator fee scalar, since Isthmus.
+    pub operator_fee_scalar: Option<u32>,
+    /// The operator fee constant, since Isthmus.
+    pub operator_fee_constant: Option<u64>,
+    /// The DA footprint gas scalar, since Jovian.
+    pub da_footprint_gas_scalar: Option<u16>,
+}
+
+impl L1BlockInfoTx {
+    /// Decodes the L1 block attributes and fee parameters from the calldata of an L1 info
+    /// deposit.
+    pub fn decode(input: &[u8]) -> Result<Self, L1InfoError> {
+        let (selector, data) = input.split_first_chunk::<4>().ok_or(L1InfoError::Empty)?;
+        let len = match *selector {
+            L1_INFO_BEDROCK_SELECTOR => 256,
+            L1_INFO_ECOTONE_SELECTOR => 160,
+            L1_INFO_ISTHMUS_SELECTOR => 172,
+            L1_INFO_JOVIAN_SELECTOR => 174,
+            selector => return Err(L1InfoError::UnknownSelector(selector)),
+        };
+        if data.len() < len {
+            return Err(L1InfoError::InvalidLength(input.len()));
+        }
+        let read = |offset: usize, len: usize| &data[offset..offset + len];
+        let read_u64 = |offset: usize| u64::from_be_bytes(read(offset, 8).try_into().unwrap());
+        let read_u32 = |offset: usize| u32::from_be_bytes(read(offset, 4).try_into().unwrap());
+        let read_u256 = |offset: usize| U256::from_be_slice(read(offset, 32));
+        let read_b256 = |offset: usize| B256::from_slice(read(offset, 32));
+
+        if *selector == L1_INFO_BEDROCK_SELECTOR {
+            return Ok(Self {
+                l1_origin: BlockNumHash { number: read_u64(24), hash: read_b256(96) },
+                l1_timestamp: read_u64(56),
+                seq_num: read_u64(152),
+                base_fee: read_u256(64),
+                batcher_hash: read_b256(160),
+                l1_fee_overhead: Some(read_u256(192)),
+                l1_fee_scalar: Some(read_u256(224)),
+                ..Default::default()
+            });
+        }
+        let isthmus = len >= 172;
+        Ok(Self {
+            l1_origin: BlockNumHash { number: read_u64(24), hash: read_b256(96) },
+            l1_timestamp: read_u64(16),
+            seq_num: read_u64(8),
+            base_fee: read_u256(32),
+            batcher_hash: read_b256(128),
+            base_fee_scalar: Some(read_u32(0)),
+            blob_base_fee: Some(read_u256(64)),
+            blob_base_fee_scalar: Some(read_u32(4)),
+            operator_fee_scalar: isthmus.then(|| read_u32(160)),
+            operator_fee_constant: isthmus.then(|| read_u64(164)),
+            da_footprint_gas_scalar: (len >= 174)
+                .then(|| u16::from_be_bytes(read(172, 2).try_into().unwrap())),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the L1 origin and sequence number of the block.
+    pub const fn origin(&self) -> L1InfoOrigin {
+        L1InfoOrigin { l1_origin: self.l1_origin, seq_num: self.seq_num }
+    }
+
+    /// Returns true if the fee parameters are the Ecotone scalars rather than the Bedrock
+    /// overhead and scalar.
+    ///
+    /// The first Ecotone block still carries Bedrock calldata, so the calldata version rather
+    /// than the hardfork schedule decides the L1 fee formula.
+    pub const fn is_ecotone(&self) -> bool {
+        self.base_fee_scalar.is_some()
+    }
+
+    /// Computes the L1 data fee of an EIP-2718 encoded, signed transaction included in a block
+    /// with the given timestamp.
+    ///
+    /// Spec: <https://specs.optimism.io/protocol/exec-engine.html#l1-cost-fees-l1-fee-vault>
+    pub fn l1_data_cost(
+        &self,
+        encoded_tx: &[u8],
+        hardforks: &OpHardforkSchedule,
+        timestamp: u64,
+    ) -> L1DataCost {
+        let Some(base_fee_scalar) = self.base_fee_scalar else {
+            // Before Regolith, the signature of the transaction was priced as 68 non-zero bytes.
+            let signature_gas =
+                if hardforks.is_regolith_active(timestamp) { 0 } else { 68 * NON_ZERO_BYTE_GAS };
+            let gas_used = U256::from(calldata_gas(encoded_tx) + signature_gas)
+                + self.l1_fee_overhead.unwrap_or_default();
+            let fee = gas_used * self.base_fee * self.l1_fee_scalar.unwrap_or_default()
+                / U256::from(1_000_000);
+            return L1DataCost { gas_used, fee };
+        };
+        let base_fee_scalar = U256::from(base_fee_scalar);
+        let blob_base_fee_scalar = U256::from(self.blob_base_fee_scalar.unwrap_or_default());
+        let blob_base_fee = self.blob_base_fee.unwrap_or_default();
+        let fee_scaled = base_fee_scalar * self.base_fee * U256::from(NON_ZERO_BYTE_GAS)
+            + blob_base_fee_scalar * blob_base_fee;
+
+        if hardforks.is_fjord_active(timestamp) {
+            // The estimated compressed size of the transaction, scaled by `1e6`.
+            let estimated_size = (FJORD_L1_COST_FASTLZ_COEF * flz_compress_len(encoded_tx) as u64)
+                .saturating_sub(FJORD_L1_COST_INTERCEPT)
+                .max(FJORD_MIN_TRANSACTION_SIZE_SCALED);
+            let estimated_size = U256::from(estimated_size);
+            return L1DataCost {
+                gas_used: estimated_size * U256::from(NON_ZERO_BYTE_GAS) / U256::from(1_000_000),
+                fee: estimated_size * fee_scaled / U256::from(1_000_000_000_000u64),
+            };
+        }
+        let gas_used = U256::from(calldata_gas(encoded_tx));
+        L1DataCost {
+            gas_used,
+            fee: gas_used * fee_scaled / U256::from(NON_ZERO_BYTE_GAS * 1_000_000),
+        }
+    }
+}
+
+/// The L1 data cost of a transaction, see [`L1BlockInfoTx::l1_data_cost`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct L1DataCost {
+    /// The L1 gas attributed to the transaction.
+    pub gas_used: U256,
+    /// The L1 data fee of the transaction, in wei.
+    pub fee: U256,
+}
+
+/// The L1 gas of a zero byte of calldata.
+const ZERO_BYTE_GAS: u64 = 4;
+
+/// The L1 gas of a non-zero byte of calldata.
+const NON_ZERO_BYTE_GAS: u64 = 16;
+
+/// The intercept of the Fjord compressed size estimation, scaled by `1e6`.
+const FJORD_L1_COST_INTERCEPT: u64 = 42_585_600;
+
+/// The FastLZ coefficient of the Fjord compressed size estimation, scaled by `1e6`.
+const FJORD_L1_COST_FASTLZ_COEF: u64 = 836_500;
+
+/// The minimum estimated size of a transaction since Fjord, scaled by `1e6`.
+const FJORD_MIN_TRANSACTION_SIZE_SCALED: u64 = 100 * 1_000_000;
+
+/// Returns the L1 calldata gas of the given bytes.
+fn calldata_gas(data: &[u8]) -> u64 {
+    data.iter().map(|&byte| if byte == 0 { ZERO_BYTE_GAS } else { NON_ZERO_BYTE_GAS }).sum()
+}
+
+/// Returns the length of the given bytes once compressed with FastLZ (level 1), as done by the
+/// `LibZip.flzCompress` function of solady used by the `GasPriceOracle` since Fjord.
+pub fn flz_compress_len(input: &[u8]) -> u32 {
+    let len = input.len() as u32;
+    let u24 = |i: u32| {
+        let i = i as usize;
+        u32::from(input[i]) | (u32::from(input[i + 1]) << 8) | (u32::from(input[i + 2]) << 16)
+    };
+    let hash = |v: u32| (2_654_435_769u32.wrapping_mul(v) >> 19) & 0x1fff;
+    let literals = |r: u32| {
+        let rest = r % 0x20;
+        0x21 * (r / 0x20) + if rest == 0 { 0 } else { rest + 1 }
+    };
+    let matched = |l: u32| {
+        let l = l - 1;
+        3 * (l / 262) + if l % 262 >= 6 { 3 } else { 2 }
+    };
+
+    let mut n = 0;
+    let mut ht = vec![0u32; 8192];
+    let mut anchor = 0;
+    let ip_limit = len.saturating_sub(13);
+    let mut ip = anchor + 2;
+    while ip < ip_limit {
+        let mut r;
+        loop {
+            let s = u24(ip);
+            let h = hash(s) as usize;
+            r = ht[h];
+            ht[h] = ip;
+            let distance = ip.wrapping_sub(r);
+            if ip >= ip_limit {
+                break;
+            }
+            ip += 1;
+            if distance <= 0x1fff && s == u24(r) {
+                break;
+            }
+        }
+        if ip >= ip_limit {
+            break;
+        }
+        ip -= 1;
+        if ip > anchor {
+            n += literals(ip - anchor);
+        }
+        // The match length, counting the first mismatching byte.
+        let end = ip_limit + 9 - (ip + 3);
+        let mut l = 0;
+        while l < end {
+            if input[(r + 3 + l) as usize] != input[(ip + 3 + l) as usize] {
+                l += 1;
+                break;
+            }
+            l += 1;
+        }
+        n += matched(l);
+        ip += l;
+        for _ in 0..2 {
+            ht[hash(u24(ip)) as usize] = ip;
+            ip += 1;
+        }
+        anchor = ip;
+    }
+    n + literals(len - anchor)
+}
+
+/// An error decoding the calldata of an L1 info deposit.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum L1InfoError {
+    /// The calldata is shorter than a selector.
+    #[error("Empty L1 info calldata")]
+    Empty,
+    /// The selector is not one of the L1 info selectors.
+    #[error("Unknown L1 info selector: {0:?}")]
+    UnknownSelector([u8; 4]),
+    /// The calldata is too short for its selector.
+    #[error("Invalid L1 info calldata length: {0}")]
+    InvalidLength(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, hex};
+
+    const BEDROCK_L1_INFO: [u8; 260] = hex!(
+        "015d8eb900000000000000000000000000000000000000000000000000000000008057650000000000000000000000000000000000000000000000000000000063d96d10000000000000000000000000000000000000000000000000000000000009f35273d89754a1e0387b89520d989d3be9c37c1f32495a88faf1ea05c61121ab0d1900000000000000000000000000000000000000000000000000000000000000010000000000000000000000002d679b567db6187c0c8323fa982cfb88b74dbcc7000000000000000000000000000000000000000000000000000000000000083400000000000000000000000000000000000000000000000000000000000f4240"
+    );
+
+    const ECOTONE_L1_INFO: [u8; 164] = hex!(
+        "440a5e20000008dd00101c1200000000000000040000000066c352bb000000000139c4f500000000000000000000000000000000000000000000000000000000c0cff1460000000000000000000000000000000000000000000000000000000000000001d4c88f4065ac9671e8b1329b90773e89b5ddff9cf8675b2b5e9c1b28320609930000000000000000000000005050f69a9786f081509234f1a7f4684b5e5b76c9"
+    );
+
+    #[test]
+    fn test_decode_bedrock_l1_info() {
+        let input = BEDROCK_L1_INFO;
+        let origin = L1InfoOrigin::decode(&input).unwrap();
+
+        assert_eq!(origin.l1_origin.number, 0x805765);
+        assert_eq!(
+            origin.l1_origin.hash,
+            b256!("0x73d89754a1e0387b89520d989d3be9c37c1f32495a88faf1ea05c61121ab0d19")
+        );
+        assert_eq!(origin.seq_num, 1);
+        assert_eq!(L1InfoOrigin::decode(&input[..100]), Err(L1InfoError::InvalidLength(100)));
+    }
+
+    #[test]
+    fn test_decode_ecotone_l1_info() {
+        let input = ECOTONE_L1_INFO;
+        let origin = L1InfoOrigin::decode(&input).unwrap();
+
+        assert_eq!(origin.l1_origin.number, 0x139c4f5);
+        assert_eq!(
+            origin.l1_origin.hash,
+            b256!("0xd4c88f4065ac9671e8b1329b90773e89b5ddff9cf8675b2b5e9c1b2832060993")
+        );
+        assert_eq!(origin.seq_num, 4);
+    }
+
+    #[test]
+    fn test_decode_isthmus_l1_info() {
+        let input = hex!(
+            "098999be000007d0000dbba0000000000000000800000000690be848000000000092042e000000000000000000000000000000000000000000000000000000000000000900000000000000000000000000000000000000000000000000000000000000010ffd7e2fb2c36e5f27c015872ce733a7b4f3fc0f4ee668d7469c557c48f8250f0000000000000000000000004ab3387810ef500bfe05a49dc53a44c222cbab3e000000000000000000000000"
+        );
+        let origin = L1InfoOrigin::decode(&input).unwrap();
+
+        assert_eq!(origin.l1_origin.number, 0x92042e);
+        assert_eq!(
+            origin.l1_origin.hash,
+            b256!("0x0ffd7e2fb2c36e5f27c015872ce733a7b4f3fc0f4ee668d7469c557c48f8250f")
+        );
+        assert_eq!(origin.seq_num, 8);
+    }
+
+    #[test]
+    fn test_decode_invalid_l1_info() {
+        assert_eq!(L1InfoOrigin::decode(&[0x44]), Err(L1InfoError::Empty));
+        assert_eq!(
+            L1InfoOrigin::decode(&[1, 2, 3, 4]),
+            Err(L1InfoError::UnknownSelector([1, 2, 3, 4]))
+        );
+    }
+
+    #[test]
+    fn test_decode_l1_block_info_tx() {
+        let bedrock = L1BlockInfoTx::decode(&BEDROCK_L1_INFO).unwrap();
+        assert_eq!(bedrock.origin(), L1InfoOrigin::decode(&BEDROCK_L1_INFO).unwrap());
+        assert_eq!(bedrock.l1_timestamp, 0x63d96d10);
+        assert_eq!(bedrock.base_fee, U256::from(0x9f352));
+        assert_eq!(
+            bedrock.batcher_hash,
+            address!("0x2d679b567db6187c0c8323fa982cfb88b74dbcc7").into_word()
+        );
+        assert_eq!(bedrock.l1_fee_overhead, Some(U256::from(2100)));
+        assert_eq!(bedrock.l1_fee_scalar, Some(U256::from(1_000_000)));
+        assert!(!bedrock.is_ecotone());
+
+        let ecotone = L1BlockInfoTx::decode(&ECOTONE_L1_INFO).unwrap();
+        assert_eq!(ecotone.origin(), L1InfoOrigin::decode(&ECOTONE_L1_INFO).unwrap());
+        assert_eq!(ecotone.l1_timestamp, 0x66c352bb);
+        assert_eq!(ecotone.base_fee, U256::from(0xc0cff146u64));
+        assert_eq!(ecotone.base_fee_scalar, Some(2269));
+        assert_eq!(ecotone.blob_base_fee, Some(U256::from(1)));
+        assert_eq!(ecotone.blob_base_fee_scalar, Some(1_055_762));
+        assert_eq!(ecotone.l1_fee_overhead, None);
+        assert_eq!(ecotone.operator_fee_scalar, None);
+        assert!(ecotone.is_ecotone());
+
+        let mut jovian = ECOTONE_L1_INFO.to_vec();
+        jovian[..4].copy_from_slice(&L1_INFO_JOVIAN_SELECTOR);
+        assert_eq!(
+            L1BlockInfoTx::decode(&jovian),
+            Err(L1InfoError::InvalidLength(ECOTONE_L1_INFO.len()))
+        );
+        jovian.extend_from_slice(&hex!("000007d0000000000000000a0190"));
+        let jovian = L1BlockInfoTx::decode(&jovian).unwrap();
+        assert_eq!(jovian.operator_fee_scalar, Some(2000));
+        assert_eq!(jovian.operator_fee_constant, Some(10));
+        assert_eq!(jovian.da_footprint_gas_scalar, Some(400));
+    }
+
+    #[test]
+    fn test_l1_data_cost() {
+        let tx = [0, 1, 2];
+        let regolith = OpHardforkSchedule { regolith_time: Some(0), ..Default::default() };
+        let fjord = OpHardforkSchedule { fjord_time: Some(0), ..regolith };
+
+        // Bedrock: (4 + 16 + 16 + 2100 overhead) * base fee * 1.0 scalar.
+        let bedrock = L1BlockInfoTx::decode(&BEDROCK_L1_INFO).unwrap();
+        assert_eq!(
+            bedrock.l1_data_cost(&tx, &regolith, 0),
+            L1DataCost { gas_used: U256::from(2136), fee: U256::from(1_392_915_504u64) }
+        );
+        // Before Regolith, the signature adds 68 non-zero bytes.
+        assert_eq!(
+            bedrock.l1_data_cost(&tx, &OpHardforkSchedule::default(), 0),
+            L1DataCost { gas_used: U256::from(3224), fee: U256::from(2_102_415_536u64) }
+        );
+
+        let ecotone = L1BlockInfoTx::decode(&ECOTONE_L1_INFO).unwrap();
+        assert_eq!(
+            ecotone.l1_data_cost(&tx, &regolith, 0),
+            L1DataCost { gas_used: U256::from(36), fee: U256::from(264_235_750u64) }
+        );
+        // Since Fjord, small transactions are priced at the minimum size of 100 bytes.
+        assert_eq!(
+            ecotone.l1_data_cost(&tx, &fjord, 0),
+            L1DataCost { gas_used: U256::from(1600), fee: U256::from(11_743_811_126u64) }
+        );
+    }
+
+    #[test]
+    fn test_flz_compress_len() {
+        assert_eq!(flz_compress_len(&[]), 0);
+        assert_eq!(flz_compress_len(&[1, 2, 3, 4, 5]), 6);
+        // Incompressible data is stored as literal runs of 32 bytes plus a control byte.
+        let incompressible: alloc::vec::Vec<u8> = (0..64u8).collect();
+        assert_eq!(flz_compress_len(&incompressible), 66);
+        // Repeated data compresses into a single long match between two short literal runs.
+        assert_eq!(flz_compress_len(&[0xaa; 1000]), 21);
+    }
+}
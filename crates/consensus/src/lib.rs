@@ -32,11 +32,33 @@ pub use eip1559::{
 mod source;
 pub use source::*;
 
+pub mod deposits;
+pub use deposits::{DepositError, decode_deposit_log, decode_deposits_from_receipts};
+
+mod hardforks;
+pub use hardforks::{OpHardfork, OpHardforkSchedule};
+
+pub mod l1_info;
+pub use l1_info::{
+    L1_BLOCK_ADDRESS, L1_INFO_BEDROCK_SELECTOR, L1_INFO_DEPOSITOR_ADDRESS,
+    L1_INFO_ECOTONE_SELECTOR, L1_INFO_ISTHMUS_SELECTOR, L1_INFO_JOVIAN_SELECTOR, L1BlockInfoTx,
+    L1DataCost, L1InfoError, L1InfoOrigin, flz_compress_len,
+};
+
 mod block;
 pub use block::OpBlock;
 
+mod block_info;
+pub use block_info::{BlockInfo, L2BlockInfo, L2BlockInfoError};
+
+mod output_root;
+pub use output_root::{OUTPUT_ROOT_VERSION, OutputRoot, OutputRootError};
+
 pub mod interop;
 
+#[cfg(feature = "derivation")]
+pub mod derivation;
+
 #[cfg(feature = "serde")]
 pub use transaction::serde_deposit_tx_rpc;
 
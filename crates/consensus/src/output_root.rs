@@ -0,0 +1,111 @@
+//! The L2 output root, committing to the state of an L2 block.
+//!
+//! Spec: <https://specs.optimism.io/protocol/proposals.html#l2-output-commitment-construction>
+
+use alloy_primitives::{B256, Bytes, keccak256};
+
+/// The version of the [`OutputRoot`] encoding.
+pub const OUTPUT_ROOT_VERSION: B256 = B256::ZERO;
+
+/// The preimage of a version 0 L2 output root:
+/// `version ++ state_root ++ message_passer_storage_root ++ block_hash`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct OutputRoot {
+    /// The state root of the L2 block.
+    pub state_root: B256,
+    /// The storage root of the `L2ToL1MessagePasser` predeploy.
+    pub message_passer_storage_root: B256,
+    /// The hash of the L2 block.
+    pub block_hash: B256,
+}
+
+impl OutputRoot {
+    /// The length of the encoded output root preimage.
+    pub const ENCODED_LENGTH: usize = 128;
+
+    /// Creates a new [`OutputRoot`].
+    pub const fn new(
+        state_root: B256,
+        message_passer_storage_root: B256,
+        block_hash: B256,
+    ) -> Self {
+        Self { state_root, message_passer_storage_root, block_hash }
+    }
+
+    /// Returns the encoded output root preimage.
+    pub fn encode(&self) -> [u8; Self::ENCODED_LENGTH] {
+        let mut out = [0u8; Self::ENCODED_LENGTH];
+        out[..32].copy_from_slice(OUTPUT_ROOT_VERSION.as_slice());
+        out[32..64].copy_from_slice(self.state_root.as_slice());
+        out[64..96].copy_from_slice(self.message_passer_storage_root.as_slice());
+        out[96..].copy_from_slice(self.block_hash.as_slice());
+        out
+    }
+
+    /// Returns the encoded output root preimage as [`Bytes`].
+    pub fn encoded(&self) -> Bytes {
+        Bytes::copy_from_slice(&self.encode())
+    }
+
+    /// Decodes an output root preimage.
+    pub fn decode(buf: &[u8]) -> Result<Self, OutputRootError> {
+        if buf.len() != Self::ENCODED_LENGTH {
+            return Err(OutputRootError::InvalidLength(buf.len()));
+        }
+        let version = B256::from_slice(&buf[..32]);
+        if version != OUTPUT_ROOT_VERSION {
+            return Err(OutputRootError::UnsupportedVersion(version));
+        }
+        Ok(Self {
+            state_root: B256::from_slice(&buf[32..64]),
+            message_passer_storage_root: B256::from_slice(&buf[64..96]),
+            block_hash: B256::from_slice(&buf[96..]),
+        })
+    }
+
+    /// Returns the output root, the keccak256 hash of the preimage.
+    pub fn hash(&self) -> B256 {
+        keccak256(self.encode())
+    }
+}
+
+/// An error decoding an [`OutputRoot`] preimage.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum OutputRootError {
+    /// The preimage does not have the expected length.
+    #[error("Invalid output root preimage length: {0}")]
+    InvalidLength(usize),
+    /// The preimage has an unsupported version.
+    #[error("Unsupported output root version: {0}")]
+    UnsupportedVersion(B256),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_root_roundtrip() {
+        let output =
+            OutputRoot::new(B256::repeat_byte(1), B256::repeat_byte(2), B256::repeat_byte(3));
+        let encoded = output.encode();
+
+        assert_eq!(encoded[..32], [0u8; 32]);
+        assert_eq!(OutputRoot::decode(&encoded), Ok(output));
+        assert_eq!(output.hash(), keccak256(encoded));
+    }
+
+    #[test]
+    fn test_decode_invalid_output_root() {
+        let mut encoded = OutputRoot::default().encode();
+
+        assert_eq!(OutputRoot::decode(&encoded[1..]), Err(OutputRootError::InvalidLength(127)));
+        encoded[31] = 1;
+        assert_eq!(
+            OutputRoot::decode(&encoded),
+            Err(OutputRootError::UnsupportedVersion(B256::with_last_byte(1)))
+        );
+    }
+}
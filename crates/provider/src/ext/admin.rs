@@ -0,0 +1,154 @@
+use alloy_network::Network;
+use alloy_primitives::B256;
+use alloy_provider::Provider;
+use alloy_transport::TransportResult;
+use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+
+/// Extension trait that gives access to the rollup node `admin` RPC methods.
+///
+/// The provider should be connected to the admin RPC of the rollup node (e.g. op-node).
+///
+/// See also <https://docs.optimism.io/operators/node-operators/json-rpc>
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait OpAdminApi<N> {
+    /// Resets the derivation pipeline.
+    async fn reset_derivation_pipeline(&self) -> TransportResult<()>;
+
+    /// Starts the sequencer on top of the block with the given hash.
+    async fn start_sequencer(&self, block_hash: B256) -> TransportResult<()>;
+
+    /// Stops the sequencer, returning the hash of the last sequenced block.
+    async fn stop_sequencer(&self) -> TransportResult<B256>;
+
+    /// Returns true if the sequencer is active.
+    async fn sequencer_active(&self) -> TransportResult<bool>;
+
+    /// Inserts the given payload into the unsafe chain and gossips it.
+    async fn post_unsafe_payload(&self, payload: OpExecutionPayloadEnvelope)
+    -> TransportResult<()>;
+
+    /// Makes the sequencer the leader of the conductor cluster, regardless of the conductor.
+    async fn override_leader(&self) -> TransportResult<()>;
+
+    /// Enables or disables recover mode, in which the sequencer only derives blocks from L1.
+    async fn set_recover_mode(&self, mode: bool) -> TransportResult<()>;
+
+    /// Returns true if the sequencer is managed by a conductor.
+    async fn conductor_enabled(&self) -> TransportResult<bool>;
+
+    /// Sets the log level of the node, e.g. `"debug"`.
+    async fn set_log_level(&self, level: String) -> TransportResult<()>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, P> OpAdminApi<N> for P
+where
+    N: Network,
+    P: Provider<N>,
+{
+    async fn reset_derivation_pipeline(&self) -> TransportResult<()> {
+        self.client().request_noparams("admin_resetDerivationPipeline").await
+    }
+
+    async fn start_sequencer(&self, block_hash: B256) -> TransportResult<()> {
+        self.client().request("admin_startSequencer", (block_hash,)).await
+    }
+
+    async fn stop_sequencer(&self) -> TransportResult<B256> {
+        self.client().request_noparams("admin_stopSequencer").await
+    }
+
+    async fn sequencer_active(&self) -> TransportResult<bool> {
+        self.client().request_noparams("admin_sequencerActive").await
+    }
+
+    async fn post_unsafe_payload(
+        &self,
+        payload: OpExecutionPayloadEnvelope,
+    ) -> TransportResult<()> {
+        self.client().request("admin_postUnsafePayload", (payload,)).await
+    }
+
+    async fn override_leader(&self) -> TransportResult<()> {
+        self.client().request_noparams("admin_overrideLeader").await
+    }
+
+    async fn set_recover_mode(&self, mode: bool) -> TransportResult<()> {
+        self.client().request("admin_setRecoverMode", (mode,)).await
+    }
+
+    async fn conductor_enabled(&self) -> TransportResult<bool> {
+        self.client().request_noparams("admin_conductorEnabled").await
+    }
+
+    async fn set_log_level(&self, level: String) -> TransportResult<()> {
+        self.client().request("admin_setLogLevel", (level,)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ext::test_utils::RecordingTransport;
+    use alloy_transport::mock::Asserter;
+    use serde_json::json;
+
+    const ENVELOPE: &str = r#"{"parentBeaconBlockRoot":"0x9999999999999999999999999999999999999999999999999999999999999999","executionPayload":{"parentHash":"0xe927a1448525fb5d32cb50ee1408461a945ba6c39bd5cf5621407d500ecc8de9","feeRecipient":"0x0000000000000000000000000000000000000000","stateRoot":"0x10f8a0830000e8edef6d00cc727ff833f064b1950afd591ae41357f97e543119","receiptsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","prevRandao":"0xe0d8b4521a7da1582a713244ffb6a86aa1726932087386e2dc7973f43fc6cb24","blockNumber":"0x1","gasLimit":"0x2ffbd2","gasUsed":"0x0","timestamp":"0x1235","extraData":"0xd883010d00846765746888676f312e32312e30856c696e7578","baseFeePerGas":"0x342770c0","blockHash":"0x44d0fa5f2f73a938ebb96a2a21679eb8dea3e7b7dd8fd9f35aa756dda8bf0a8a","transactions":[],"withdrawals":[],"blobGasUsed":"0x0","excessBlobGas":"0x0"}}"#;
+
+    #[test]
+    fn test_serde_post_unsafe_payload_params() {
+        let payload: OpExecutionPayloadEnvelope = serde_json::from_str(ENVELOPE).unwrap();
+        let params = serde_json::to_value((payload.clone(),)).unwrap();
+
+        assert_eq!(params, json!([serde_json::from_str::<serde_json::Value>(ENVELOPE).unwrap()]));
+        assert_eq!(
+            serde_json::from_value::<(OpExecutionPayloadEnvelope,)>(params).unwrap().0,
+            payload
+        );
+    }
+
+    #[tokio::test]
+    async fn test_admin_requests() {
+        let asserter = Asserter::new();
+        let transport = RecordingTransport::new(asserter.clone());
+        let provider = transport.provider();
+        let payload: OpExecutionPayloadEnvelope = serde_json::from_str(ENVELOPE).unwrap();
+
+        asserter.push_success(&());
+        provider.post_unsafe_payload(payload).await.unwrap();
+
+        asserter.push_success(&());
+        provider.override_leader().await.unwrap();
+
+        asserter.push_success(&());
+        provider.set_recover_mode(true).await.unwrap();
+
+        asserter.push_success(&true);
+        assert!(provider.conductor_enabled().await.unwrap());
+
+        asserter.push_success(&());
+        provider.set_log_level("debug".to_string()).await.unwrap();
+
+        asserter.push_success(&B256::repeat_byte(2));
+        assert_eq!(provider.stop_sequencer().await.unwrap(), B256::repeat_byte(2));
+
+        asserter.push_failure_msg("sequencer already running");
+        assert!(provider.start_sequencer(B256::ZERO).await.is_err());
+
+        let envelope = serde_json::from_str::<serde_json::Value>(ENVELOPE).unwrap();
+        assert_eq!(
+            transport.take_requests(),
+            [
+                json!({ "method": "admin_postUnsafePayload", "params": [envelope] }),
+                json!({ "method": "admin_overrideLeader", "params": null }),
+                json!({ "method": "admin_setRecoverMode", "params": [true] }),
+                json!({ "method": "admin_conductorEnabled", "params": null }),
+                json!({ "method": "admin_setLogLevel", "params": ["debug"] }),
+                json!({ "method": "admin_stopSequencer", "params": null }),
+                json!({ "method": "admin_startSequencer", "params": [B256::ZERO] }),
+            ]
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use alloy_network::Network;
+use alloy_primitives::U64;
+use alloy_provider::Provider;
+use alloy_transport::TransportResult;
+
+/// Extension trait that gives access to the op-batcher `admin` RPC methods.
+///
+/// The provider should be connected to the admin RPC of the batcher.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait BatcherAdminApi<N> {
+    /// Starts the batch submission loop.
+    async fn start_batcher(&self) -> TransportResult<()>;
+
+    /// Stops the batch submission loop, after submitting the pending channel data.
+    async fn stop_batcher(&self) -> TransportResult<()>;
+
+    /// Forces the batcher to close and submit the current channel.
+    async fn flush_batcher(&self) -> TransportResult<()>;
+
+    /// Sets the data availability limits the batcher applies to the sequencer with
+    /// `miner_setMaxDASize`. 0 means no maximum.
+    async fn set_throttle(&self, max_tx_size: U64, max_block_size: U64) -> TransportResult<()>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, P> BatcherAdminApi<N> for P
+where
+    N: Network,
+    P: Provider<N>,
+{
+    async fn start_batcher(&self) -> TransportResult<()> {
+        self.client().request_noparams("admin_startBatcher").await
+    }
+
+    async fn stop_batcher(&self) -> TransportResult<()> {
+        self.client().request_noparams("admin_stopBatcher").await
+    }
+
+    async fn flush_batcher(&self) -> TransportResult<()> {
+        self.client().request_noparams("admin_flushBatcher").await
+    }
+
+    async fn set_throttle(&self, max_tx_size: U64, max_block_size: U64) -> TransportResult<()> {
+        self.client().request("admin_setThrottle", (max_tx_size, max_block_size)).await
+    }
+}
+
+/// Extension trait that gives access to the op-proposer `admin` RPC methods.
+///
+/// The provider should be connected to the admin RPC of the proposer.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait ProposerAdminApi<N> {
+    /// Starts submitting output roots.
+    async fn start_l2_output_submitting(&self) -> TransportResult<()>;
+
+    /// Stops submitting output roots.
+    async fn stop_l2_output_submitting(&self) -> TransportResult<()>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, P> ProposerAdminApi<N> for P
+where
+    N: Network,
+    P: Provider<N>,
+{
+    async fn start_l2_output_submitting(&self) -> TransportResult<()> {
+        self.client().request_noparams("admin_startL2OutputSubmitting").await
+    }
+
+    async fn stop_l2_output_submitting(&self) -> TransportResult<()> {
+        self.client().request_noparams("admin_stopL2OutputSubmitting").await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ext::test_utils::RecordingTransport;
+    use alloy_transport::mock::Asserter;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_batcher_admin_requests() {
+        let asserter = Asserter::new();
+        let transport = RecordingTransport::new(asserter.clone());
+        let provider = transport.provider();
+
+        for _ in 0..4 {
+            asserter.push_success(&());
+        }
+        provider.start_batcher().await.unwrap();
+        provider.stop_batcher().await.unwrap();
+        provider.flush_batcher().await.unwrap();
+        provider.set_throttle(U64::from(20_000), U64::from(130_000)).await.unwrap();
+
+        assert_eq!(
+            transport.take_requests(),
+            [
+                json!({ "method": "admin_startBatcher", "params": null }),
+                json!({ "method": "admin_stopBatcher", "params": null }),
+                json!({ "method": "admin_flushBatcher", "params": null }),
+                json!({ "method": "admin_setThrottle", "params": ["0x4e20", "0x1fbd0"] }),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_proposer_admin_requests() {
+        let asserter = Asserter::new();
+        let transport = RecordingTransport::new(asserter.clone());
+        let provider = transport.provider();
+
+        asserter.push_success(&());
+        asserter.push_success(&());
+        provider.start_l2_output_submitting().await.unwrap();
+        provider.stop_l2_output_submitting().await.unwrap();
+
+        assert_eq!(
+            transport.take_requests(),
+            [
+                json!({ "method": "admin_startL2OutputSubmitting", "params": null }),
+                json!({ "method": "admin_stopL2OutputSubmitting", "params": null }),
+            ]
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use alloy_eips::Encodable2718;
+use alloy_network::Network;
+use alloy_provider::{PendingTransactionBuilder, Provider};
+use alloy_transport::TransportResult;
+use op_alloy_consensus::OpTxEnvelope;
+use op_alloy_rpc_types::TransactionConditional;
+
+/// Extension trait to submit OP transactions with `eth_sendRawTransactionConditional`.
+///
+/// The sequencer only includes the transaction while the [`TransactionConditional`] holds, see
+/// [`TransactionConditionalExt`](op_alloy_rpc_types::TransactionConditionalExt).
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait ConditionalTransactionApi<N: Network> {
+    /// Broadcasts the given signed transaction with the given conditional.
+    async fn send_tx_envelope_conditional(
+        &self,
+        tx: OpTxEnvelope,
+        conditional: TransactionConditional,
+    ) -> TransportResult<PendingTransactionBuilder<N>>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, P> ConditionalTransactionApi<N> for P
+where
+    N: Network,
+    P: Provider<N>,
+{
+    async fn send_tx_envelope_conditional(
+        &self,
+        tx: OpTxEnvelope,
+        conditional: TransactionConditional,
+    ) -> TransportResult<PendingTransactionBuilder<N>> {
+        self.send_raw_transaction_conditional(&tx.encoded_2718(), conditional).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{SignableTransaction, TxEip1559};
+    use alloy_primitives::{Address, B256, Signature};
+    use alloy_provider::ProviderBuilder;
+    use alloy_transport::mock::Asserter;
+
+    #[tokio::test]
+    async fn test_send_tx_envelope_conditional() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        let tx = OpTxEnvelope::Eip1559(
+            TxEip1559 { chain_id: 5000, to: Address::ZERO.into(), ..Default::default() }
+                .into_signed(Signature::test_signature()),
+        );
+        let conditional =
+            TransactionConditional { block_number_max: Some(100), ..Default::default() };
+
+        asserter.push_success(&tx.tx_hash());
+        let pending = provider.send_tx_envelope_conditional(tx.clone(), conditional).await.unwrap();
+        assert_eq!(*pending.tx_hash(), tx.tx_hash());
+        assert_ne!(*pending.tx_hash(), B256::ZERO);
+    }
+}
@@ -0,0 +1,162 @@
+use alloy_network::Network;
+use alloy_provider::Provider;
+use alloy_transport::TransportResult;
+use op_alloy_rpc_types::{ClusterMembership, ServerInfo};
+use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+
+/// Extension trait that gives access to the op-conductor `conductor` RPC methods.
+///
+/// Membership changes take the `version` of the [`ClusterMembership`] they were computed from,
+/// and fail if the membership changed in the meantime.
+///
+/// See also <https://docs.optimism.io/operators/chain-operators/tools/op-conductor>
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait ConductorApi<N> {
+    /// Returns true if the conductor is the leader of the cluster.
+    async fn leader(&self) -> TransportResult<bool>;
+
+    /// Returns the leader of the cluster.
+    async fn leader_with_id(&self) -> TransportResult<ServerInfo>;
+
+    /// Returns true if the conductor is active, i.e. neither paused nor stopped.
+    async fn active(&self) -> TransportResult<bool>;
+
+    /// Pauses the control loop of the conductor.
+    async fn pause(&self) -> TransportResult<()>;
+
+    /// Resumes the control loop of the conductor.
+    async fn resume(&self) -> TransportResult<()>;
+
+    /// Returns true if the control loop of the conductor is paused.
+    async fn paused(&self) -> TransportResult<bool>;
+
+    /// Stops the conductor.
+    async fn stop(&self) -> TransportResult<()>;
+
+    /// Returns true if the conductor is stopped.
+    async fn stopped(&self) -> TransportResult<bool>;
+
+    /// Transfers leadership to another server of the cluster.
+    async fn transfer_leader(&self) -> TransportResult<()>;
+
+    /// Transfers leadership to the given server.
+    async fn transfer_leader_to_server(&self, id: String, addr: String) -> TransportResult<()>;
+
+    /// Adds the given server to the cluster as a voter, if the membership is still at `version`.
+    async fn add_server_as_voter(
+        &self,
+        id: String,
+        addr: String,
+        version: u64,
+    ) -> TransportResult<()>;
+
+    /// Adds the given server to the cluster as a non-voter, if the membership is still at
+    /// `version`.
+    async fn add_server_as_nonvoter(
+        &self,
+        id: String,
+        addr: String,
+        version: u64,
+    ) -> TransportResult<()>;
+
+    /// Removes the given server from the cluster, if the membership is still at `version`.
+    async fn remove_server(&self, id: String, version: u64) -> TransportResult<()>;
+
+    /// Returns the membership of the cluster.
+    async fn cluster_membership(&self) -> TransportResult<ClusterMembership>;
+
+    /// Commits the given unsafe payload to the cluster state. Only the leader accepts payloads.
+    async fn commit_unsafe_payload(
+        &self,
+        payload: OpExecutionPayloadEnvelope,
+    ) -> TransportResult<()>;
+
+    /// Returns true if the sequencer managed by the conductor is healthy.
+    async fn sequencer_healthy(&self) -> TransportResult<bool>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, P> ConductorApi<N> for P
+where
+    N: Network,
+    P: Provider<N>,
+{
+    async fn leader(&self) -> TransportResult<bool> {
+        self.client().request_noparams("conductor_leader").await
+    }
+
+    async fn leader_with_id(&self) -> TransportResult<ServerInfo> {
+        self.client().request_noparams("conductor_leaderWithID").await
+    }
+
+    async fn active(&self) -> TransportResult<bool> {
+        self.client().request_noparams("conductor_active").await
+    }
+
+    async fn pause(&self) -> TransportResult<()> {
+        self.client().request_noparams("conductor_pause").await
+    }
+
+    async fn resume(&self) -> TransportResult<()> {
+        self.client().request_noparams("conductor_resume").await
+    }
+
+    async fn paused(&self) -> TransportResult<bool> {
+        self.client().request_noparams("conductor_paused").await
+    }
+
+    async fn stop(&self) -> TransportResult<()> {
+        self.client().request_noparams("conductor_stop").await
+    }
+
+    async fn stopped(&self) -> TransportResult<bool> {
+        self.client().request_noparams("conductor_stopped").await
+    }
+
+    async fn transfer_leader(&self) -> TransportResult<()> {
+        self.client().request_noparams("conductor_transferLeader").await
+    }
+
+    async fn transfer_leader_to_server(&self, id: String, addr: String) -> TransportResult<()> {
+        self.client().request("conductor_transferLeaderToServer", (id, addr)).await
+    }
+
+    async fn add_server_as_voter(
+        &self,
+        id: String,
+        addr: String,
+        version: u64,
+    ) -> TransportResult<()> {
+        self.client().request("conductor_addServerAsVoter", (id, addr, version)).await
+    }
+
+    async fn add_server_as_nonvoter(
+        &self,
+        id: String,
+        addr: String,
+        version: u64,
+    ) -> TransportResult<()> {
+        self.client().request("conductor_addServerAsNonvoter", (id, addr, version)).await
+    }
+
+    async fn remove_server(&self, id: String, version: u64) -> TransportResult<()> {
+        self.client().request("conductor_removeServer", (id, version)).await
+    }
+
+    async fn cluster_membership(&self) -> TransportResult<ClusterMembership> {
+        self.client().request_noparams("conductor_clusterMembership").await
+    }
+
+    async fn commit_unsafe_payload(
+        &self,
+        payload: OpExecutionPayloadEnvelope,
+    ) -> TransportResult<()> {
+        self.client().request("conductor_commitUnsafePayload", (payload,)).await
+    }
+
+    async fn sequencer_healthy(&self) -> TransportResult<bool> {
+        self.client().request_noparams("conductor_sequencerHealthy").await
+    }
+}
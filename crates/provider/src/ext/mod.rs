@@ -1,4 +1,28 @@
 //! Extended APIs for the OP provider module.
 
+/// Rollup node admin API extension.
+pub mod admin;
+
+/// Batcher and proposer admin API extensions.
+pub mod batcher;
+
+/// Conditional transaction submission extension.
+pub mod conditional;
+
+/// Conductor API extension.
+pub mod conductor;
+
 /// Engine API extension.
 pub mod engine;
+
+/// Rollup node API extension.
+pub mod node;
+
+/// Rollup node P2P API extension.
+pub mod p2p;
+
+/// Supervisor API extension.
+pub mod supervisor;
+
+#[cfg(test)]
+pub(crate) mod test_utils;
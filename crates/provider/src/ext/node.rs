@@ -0,0 +1,59 @@
+use alloy_network::Network;
+use alloy_primitives::U64;
+use alloy_provider::Provider;
+use alloy_transport::TransportResult;
+use op_alloy_rpc_types::{OutputResponse, RollupConfig, SafeHeadResponse, SyncStatus};
+
+/// Extension trait that gives access to the rollup node `optimism` RPC methods.
+///
+/// The provider should be connected to the rollup node (e.g. op-node), not to the execution
+/// client.
+///
+/// See also <https://docs.optimism.io/operators/node-operators/json-rpc>
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait RollupNodeApi<N> {
+    /// Returns the output root at the given L2 block number.
+    async fn output_at_block(&self, block_number: u64) -> TransportResult<OutputResponse>;
+
+    /// Returns the sync status of the rollup node.
+    async fn sync_status(&self) -> TransportResult<SyncStatus>;
+
+    /// Returns the rollup config of the rollup node.
+    async fn rollup_config(&self) -> TransportResult<RollupConfig>;
+
+    /// Returns the version of the rollup node.
+    async fn version(&self) -> TransportResult<String>;
+
+    /// Returns the safe L2 head recorded at the given L1 block number, or at the closest
+    /// recorded L1 block before it.
+    async fn safe_head_at_l1_block(&self, block_number: u64) -> TransportResult<SafeHeadResponse>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, P> RollupNodeApi<N> for P
+where
+    N: Network,
+    P: Provider<N>,
+{
+    async fn output_at_block(&self, block_number: u64) -> TransportResult<OutputResponse> {
+        self.client().request("optimism_outputAtBlock", (U64::from(block_number),)).await
+    }
+
+    async fn sync_status(&self) -> TransportResult<SyncStatus> {
+        self.client().request_noparams("optimism_syncStatus").await
+    }
+
+    async fn rollup_config(&self) -> TransportResult<RollupConfig> {
+        self.client().request_noparams("optimism_rollupConfig").await
+    }
+
+    async fn version(&self) -> TransportResult<String> {
+        self.client().request_noparams("optimism_version").await
+    }
+
+    async fn safe_head_at_l1_block(&self, block_number: u64) -> TransportResult<SafeHeadResponse> {
+        self.client().request("optimism_safeHeadAtL1Block", (U64::from(block_number),)).await
+    }
+}
@@ -0,0 +1,98 @@
+use alloy_network::Network;
+use alloy_provider::Provider;
+use alloy_transport::TransportResult;
+use op_alloy_rpc_types::{PeerDump, PeerInfo, PeerStats};
+
+/// Extension trait that gives access to the rollup node `opp2p` RPC methods.
+///
+/// Peers are identified by their libp2p peer id, e.g. `16Uiu2HAm...`.
+///
+/// See also <https://docs.optimism.io/operators/node-operators/json-rpc>
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait OpP2PApi<N> {
+    /// Returns the peer info of the node itself.
+    async fn self_info(&self) -> TransportResult<PeerInfo>;
+
+    /// Returns the known peers of the node, or only the connected ones if `connected` is true.
+    async fn peers(&self, connected: bool) -> TransportResult<PeerDump>;
+
+    /// Returns the peer statistics of the node.
+    async fn peer_stats(&self) -> TransportResult<PeerStats>;
+
+    /// Returns the ENRs of the nodes in the discovery table.
+    async fn discovery_table(&self) -> TransportResult<Vec<String>>;
+
+    /// Blocks the peer with the given id.
+    async fn block_peer(&self, peer_id: String) -> TransportResult<()>;
+
+    /// Unblocks the peer with the given id.
+    async fn unblock_peer(&self, peer_id: String) -> TransportResult<()>;
+
+    /// Returns the ids of the blocked peers.
+    async fn list_blocked_peers(&self) -> TransportResult<Vec<String>>;
+
+    /// Protects the peer with the given id from being pruned.
+    async fn protect_peer(&self, peer_id: String) -> TransportResult<()>;
+
+    /// Removes the protection of the peer with the given id.
+    async fn unprotect_peer(&self, peer_id: String) -> TransportResult<()>;
+
+    /// Connects to the peer with the given multi-address.
+    async fn connect_peer(&self, addr: String) -> TransportResult<()>;
+
+    /// Disconnects from the peer with the given id.
+    async fn disconnect_peer(&self, peer_id: String) -> TransportResult<()>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, P> OpP2PApi<N> for P
+where
+    N: Network,
+    P: Provider<N>,
+{
+    async fn self_info(&self) -> TransportResult<PeerInfo> {
+        self.client().request_noparams("opp2p_self").await
+    }
+
+    async fn peers(&self, connected: bool) -> TransportResult<PeerDump> {
+        self.client().request("opp2p_peers", (connected,)).await
+    }
+
+    async fn peer_stats(&self) -> TransportResult<PeerStats> {
+        self.client().request_noparams("opp2p_peerStats").await
+    }
+
+    async fn discovery_table(&self) -> TransportResult<Vec<String>> {
+        self.client().request_noparams("opp2p_discoveryTable").await
+    }
+
+    async fn block_peer(&self, peer_id: String) -> TransportResult<()> {
+        self.client().request("opp2p_blockPeer", (peer_id,)).await
+    }
+
+    async fn unblock_peer(&self, peer_id: String) -> TransportResult<()> {
+        self.client().request("opp2p_unblockPeer", (peer_id,)).await
+    }
+
+    async fn list_blocked_peers(&self) -> TransportResult<Vec<String>> {
+        self.client().request_noparams("opp2p_listBlockedPeers").await
+    }
+
+    async fn protect_peer(&self, peer_id: String) -> TransportResult<()> {
+        self.client().request("opp2p_protectPeer", (peer_id,)).await
+    }
+
+    async fn unprotect_peer(&self, peer_id: String) -> TransportResult<()> {
+        self.client().request("opp2p_unprotectPeer", (peer_id,)).await
+    }
+
+    async fn connect_peer(&self, addr: String) -> TransportResult<()> {
+        self.client().request("opp2p_connectPeer", (addr,)).await
+    }
+
+    async fn disconnect_peer(&self, peer_id: String) -> TransportResult<()> {
+        self.client().request("opp2p_disconnectPeer", (peer_id,)).await
+    }
+}
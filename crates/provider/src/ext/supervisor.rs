@@ -0,0 +1,117 @@
+use alloy_eips::BlockNumHash;
+use alloy_network::Network;
+use alloy_primitives::{B256, ChainId, U64};
+use alloy_provider::Provider;
+use alloy_transport::TransportResult;
+use op_alloy_consensus::{BlockInfo, interop::SafetyLevel};
+use op_alloy_rpc_types::{
+    DerivedIdPair, ExecutingDescriptor, SuperRootOutput, SupervisorSyncStatus,
+};
+use std::collections::BTreeMap;
+
+/// Extension trait that gives access to the interop supervisor RPC methods.
+///
+/// This follows the Optimism specs that can be found at:
+/// <https://specs.optimism.io/interop/supervisor.html#rpc-api>
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait SupervisorApi<N> {
+    /// Checks that the executing messages declared by the access-list `inbox_entries` are valid
+    /// at `min_safety` or safer, in the context of the `executing_descriptor`.
+    ///
+    /// The `inbox_entries` are the `CrossL2Inbox` storage keys of the access list, see
+    /// [`encode_access_list`](op_alloy_consensus::interop::encode_access_list).
+    async fn check_access_list(
+        &self,
+        inbox_entries: Vec<B256>,
+        min_safety: SafetyLevel,
+        executing_descriptor: ExecutingDescriptor,
+    ) -> TransportResult<()>;
+
+    /// Returns the L1 block the given cross-safe L2 block was derived from.
+    async fn cross_derived_to_source(
+        &self,
+        chain_id: ChainId,
+        derived: BlockNumHash,
+    ) -> TransportResult<BlockInfo>;
+
+    /// Returns the local-unsafe head of the given chain.
+    async fn local_unsafe(&self, chain_id: ChainId) -> TransportResult<BlockNumHash>;
+
+    /// Returns the cross-safe head of the given chain, and the L1 block it was derived from.
+    async fn cross_safe(&self, chain_id: ChainId) -> TransportResult<DerivedIdPair>;
+
+    /// Returns the finalized head of the given chain.
+    async fn finalized(&self, chain_id: ChainId) -> TransportResult<BlockNumHash>;
+
+    /// Returns the super root of the dependency set at the given timestamp.
+    async fn super_root_at_timestamp(&self, timestamp: u64) -> TransportResult<SuperRootOutput>;
+
+    /// Returns the sync status of the supervisor.
+    async fn sync_status(&self) -> TransportResult<SupervisorSyncStatus>;
+
+    /// Returns the last L2 block of each chain that was derived from the given L1 block.
+    async fn all_safe_derived_at(
+        &self,
+        derived_from: BlockNumHash,
+    ) -> TransportResult<BTreeMap<ChainId, BlockNumHash>>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, P> SupervisorApi<N> for P
+where
+    N: Network,
+    P: Provider<N>,
+{
+    async fn check_access_list(
+        &self,
+        inbox_entries: Vec<B256>,
+        min_safety: SafetyLevel,
+        executing_descriptor: ExecutingDescriptor,
+    ) -> TransportResult<()> {
+        self.client()
+            .request(
+                "supervisor_checkAccessList",
+                (inbox_entries, min_safety, executing_descriptor),
+            )
+            .await
+    }
+
+    async fn cross_derived_to_source(
+        &self,
+        chain_id: ChainId,
+        derived: BlockNumHash,
+    ) -> TransportResult<BlockInfo> {
+        self.client()
+            .request("supervisor_crossDerivedToSource", (U64::from(chain_id), derived))
+            .await
+    }
+
+    async fn local_unsafe(&self, chain_id: ChainId) -> TransportResult<BlockNumHash> {
+        self.client().request("supervisor_localUnsafe", (U64::from(chain_id),)).await
+    }
+
+    async fn cross_safe(&self, chain_id: ChainId) -> TransportResult<DerivedIdPair> {
+        self.client().request("supervisor_crossSafe", (U64::from(chain_id),)).await
+    }
+
+    async fn finalized(&self, chain_id: ChainId) -> TransportResult<BlockNumHash> {
+        self.client().request("supervisor_finalized", (U64::from(chain_id),)).await
+    }
+
+    async fn super_root_at_timestamp(&self, timestamp: u64) -> TransportResult<SuperRootOutput> {
+        self.client().request("supervisor_superRootAtTimestamp", (U64::from(timestamp),)).await
+    }
+
+    async fn sync_status(&self) -> TransportResult<SupervisorSyncStatus> {
+        self.client().request_noparams("supervisor_syncStatus").await
+    }
+
+    async fn all_safe_derived_at(
+        &self,
+        derived_from: BlockNumHash,
+    ) -> TransportResult<BTreeMap<ChainId, BlockNumHash>> {
+        self.client().request("supervisor_allSafeDerivedAt", (derived_from,)).await
+    }
+}
@@ -0,0 +1,60 @@
+//! Test utilities for the provider extensions.
+
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_provider::{
+    RootProvider,
+    mock::{Asserter, MockTransport},
+};
+use alloy_rpc_client::RpcClient;
+use alloy_transport::{TransportError, TransportFut};
+use serde_json::{Value, json};
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+/// A mock transport that records the requests it receives, and answers them with the responses
+/// pushed into its [`Asserter`].
+///
+/// Unlike the plain [`MockTransport`], this allows asserting the method and params that are sent.
+#[derive(Debug, Clone)]
+pub(crate) struct RecordingTransport {
+    inner: MockTransport,
+    requests: Arc<Mutex<Vec<Value>>>,
+}
+
+impl RecordingTransport {
+    /// Creates a new [`RecordingTransport`] answering with the responses of the `asserter`.
+    pub(crate) fn new(asserter: Asserter) -> Self {
+        Self { inner: MockTransport::new(asserter), requests: Default::default() }
+    }
+
+    /// Returns a provider sending its requests to this transport.
+    pub(crate) fn provider(&self) -> RootProvider {
+        RootProvider::new(RpcClient::new(self.clone(), true))
+    }
+
+    /// Returns the recorded requests as `{"method": .., "params": ..}` objects, and clears them.
+    pub(crate) fn take_requests(&self) -> Vec<Value> {
+        std::mem::take(&mut *self.requests.lock().unwrap())
+    }
+}
+
+impl tower::Service<RequestPacket> for RecordingTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        self.requests.lock().unwrap().extend(req.requests().iter().map(|req| {
+            let params =
+                req.params().map(|params| serde_json::from_str::<Value>(params.get()).unwrap());
+            json!({ "method": req.method(), "params": params })
+        }));
+        self.inner.call(req)
+    }
+}
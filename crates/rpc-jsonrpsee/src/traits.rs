@@ -2,8 +2,26 @@
 
 //! Various `jsonrpsee` docs
 
-use alloy_primitives::{B256, U64};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{Address, B256, Bytes, ChainId, U64, U256};
+use alloy_rpc_types_eth::{
+    AccessListResult, Block, BlockId, BlockNumberOrTag, BlockOverrides,
+    EIP1186AccountProofResponse, FeeHistory, Filter, FilterChanges, FilterId, Header, Index, Log,
+    PendingTransactionFilterKind, SyncStatus as EthSyncStatus,
+    simulate::{SimulatePayload, SimulatedBlock},
+    state::StateOverride,
+};
+use alloy_serde::JsonStorageKey;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use op_alloy_consensus::{BlockInfo, interop::SafetyLevel};
+use op_alloy_rpc_types::{
+    ClusterMembership, DerivedIdPair, ExecutingDescriptor, OpTransactionReceipt,
+    OpTransactionRequest, OutputResponse, PeerDump, PeerInfo, PeerStats, RollupConfig,
+    SafeHeadResponse, ServerInfo, SuperRootOutput, SupervisorSyncStatus, SyncStatus, Transaction,
+    TransactionConditional,
+};
+use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+use std::collections::BTreeMap;
 
 /// The admin namespace endpoints
 /// https://github.com/ethereum-optimism/optimism/blob/c7ad0ebae5dca3bf8aa6f219367a95c15a15ae41/op-node/node/api.go#L28-L36
@@ -21,6 +39,521 @@ pub trait OpAdminApi {
 
     #[method(name = "sequencerActive")]
     async fn admin_sequencer_active(&self) -> RpcResult<bool>;
+
+    /// Inserts the given payload into the unsafe chain and gossips it.
+    #[method(name = "postUnsafePayload")]
+    async fn admin_post_unsafe_payload(&self, payload: OpExecutionPayloadEnvelope)
+    -> RpcResult<()>;
+
+    /// Makes the sequencer the leader of the conductor cluster, regardless of the conductor.
+    #[method(name = "overrideLeader")]
+    async fn admin_override_leader(&self) -> RpcResult<()>;
+
+    /// Enables or disables recover mode, in which the sequencer only derives blocks from L1.
+    #[method(name = "setRecoverMode")]
+    async fn admin_set_recover_mode(&self, mode: bool) -> RpcResult<()>;
+
+    /// Returns true if the sequencer is managed by a conductor.
+    #[method(name = "conductorEnabled")]
+    async fn admin_conductor_enabled(&self) -> RpcResult<bool>;
+
+    /// Sets the log level of the node, e.g. `"debug"`.
+    #[method(name = "setLogLevel")]
+    async fn admin_set_log_level(&self, level: String) -> RpcResult<()>;
+}
+
+/// The rollup node `optimism` namespace endpoints
+/// https://docs.optimism.io/operators/node-operators/json-rpc
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "optimism"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "optimism"))]
+pub trait RollupNodeApi {
+    /// Returns the output root at the given L2 block number.
+    #[method(name = "outputAtBlock")]
+    async fn output_at_block(&self, block_number: U64) -> RpcResult<OutputResponse>;
+
+    /// Returns the sync status of the rollup node.
+    #[method(name = "syncStatus")]
+    async fn sync_status(&self) -> RpcResult<SyncStatus>;
+
+    /// Returns the rollup config of the rollup node.
+    #[method(name = "rollupConfig")]
+    async fn rollup_config(&self) -> RpcResult<RollupConfig>;
+
+    /// Returns the version of the rollup node.
+    #[method(name = "version")]
+    async fn version(&self) -> RpcResult<String>;
+
+    /// Returns the safe L2 head recorded at the given L1 block number, or at the closest
+    /// recorded L1 block before it.
+    #[method(name = "safeHeadAtL1Block")]
+    async fn safe_head_at_l1_block(&self, block_number: U64) -> RpcResult<SafeHeadResponse>;
+}
+
+/// The rollup node `opp2p` namespace endpoints
+/// https://docs.optimism.io/operators/node-operators/json-rpc
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "opp2p"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "opp2p"))]
+pub trait OpP2PApi {
+    /// Returns the peer info of the node itself.
+    #[method(name = "self")]
+    async fn opp2p_self(&self) -> RpcResult<PeerInfo>;
+
+    /// Returns the known peers of the node, or only the connected ones if `connected` is true.
+    #[method(name = "peers")]
+    async fn opp2p_peers(&self, connected: bool) -> RpcResult<PeerDump>;
+
+    /// Returns the peer statistics of the node.
+    #[method(name = "peerStats")]
+    async fn opp2p_peer_stats(&self) -> RpcResult<PeerStats>;
+
+    /// Returns the ENRs of the nodes in the discovery table.
+    #[method(name = "discoveryTable")]
+    async fn opp2p_discovery_table(&self) -> RpcResult<Vec<String>>;
+
+    /// Blocks the peer with the given id.
+    #[method(name = "blockPeer")]
+    async fn opp2p_block_peer(&self, peer_id: String) -> RpcResult<()>;
+
+    /// Unblocks the peer with the given id.
+    #[method(name = "unblockPeer")]
+    async fn opp2p_unblock_peer(&self, peer_id: String) -> RpcResult<()>;
+
+    /// Returns the ids of the blocked peers.
+    #[method(name = "listBlockedPeers")]
+    async fn opp2p_list_blocked_peers(&self) -> RpcResult<Vec<String>>;
+
+    /// Protects the peer with the given id from being pruned.
+    #[method(name = "protectPeer")]
+    async fn opp2p_protect_peer(&self, peer_id: String) -> RpcResult<()>;
+
+    /// Removes the protection of the peer with the given id.
+    #[method(name = "unprotectPeer")]
+    async fn opp2p_unprotect_peer(&self, peer_id: String) -> RpcResult<()>;
+
+    /// Connects to the peer with the given multi-address.
+    #[method(name = "connectPeer")]
+    async fn opp2p_connect_peer(&self, addr: String) -> RpcResult<()>;
+
+    /// Disconnects from the peer with the given id.
+    #[method(name = "disconnectPeer")]
+    async fn opp2p_disconnect_peer(&self, peer_id: String) -> RpcResult<()>;
+}
+
+/// The op-batcher `admin` namespace endpoints
+/// https://github.com/ethereum-optimism/optimism/blob/develop/op-batcher/rpc/api.go
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait BatcherAdminApi {
+    /// Starts the batch submission loop.
+    #[method(name = "startBatcher")]
+    async fn admin_start_batcher(&self) -> RpcResult<()>;
+
+    /// Stops the batch submission loop, after submitting the pending channel data.
+    #[method(name = "stopBatcher")]
+    async fn admin_stop_batcher(&self) -> RpcResult<()>;
+
+    /// Forces the batcher to close and submit the current channel.
+    #[method(name = "flushBatcher")]
+    async fn admin_flush_batcher(&self) -> RpcResult<()>;
+
+    /// Sets the data availability limits the batcher applies to the sequencer with
+    /// `miner_setMaxDASize`. 0 means no maximum.
+    #[method(name = "setThrottle")]
+    async fn admin_set_throttle(&self, max_tx_size: U64, max_block_size: U64) -> RpcResult<()>;
+}
+
+/// The op-proposer `admin` namespace endpoints
+/// https://github.com/ethereum-optimism/optimism/blob/develop/op-proposer/proposer/rpc/api.go
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait ProposerAdminApi {
+    /// Starts submitting output roots.
+    #[method(name = "startL2OutputSubmitting")]
+    async fn admin_start_l2_output_submitting(&self) -> RpcResult<()>;
+
+    /// Stops submitting output roots.
+    #[method(name = "stopL2OutputSubmitting")]
+    async fn admin_stop_l2_output_submitting(&self) -> RpcResult<()>;
+}
+
+/// The op-conductor `conductor` namespace endpoints
+/// https://docs.optimism.io/operators/chain-operators/tools/op-conductor
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "conductor"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "conductor"))]
+pub trait ConductorApi {
+    /// Returns true if the conductor is the leader of the cluster.
+    #[method(name = "leader")]
+    async fn conductor_leader(&self) -> RpcResult<bool>;
+
+    /// Returns the leader of the cluster.
+    #[method(name = "leaderWithID")]
+    async fn conductor_leader_with_id(&self) -> RpcResult<ServerInfo>;
+
+    /// Returns true if the conductor is active, i.e. neither paused nor stopped.
+    #[method(name = "active")]
+    async fn conductor_active(&self) -> RpcResult<bool>;
+
+    /// Pauses the control loop of the conductor.
+    #[method(name = "pause")]
+    async fn conductor_pause(&self) -> RpcResult<()>;
+
+    /// Resumes the control loop of the conductor.
+    #[method(name = "resume")]
+    async fn conductor_resume(&self) -> RpcResult<()>;
+
+    /// Returns true if the control loop of the conductor is paused.
+    #[method(name = "paused")]
+    async fn conductor_paused(&self) -> RpcResult<bool>;
+
+    /// Stops the conductor.
+    #[method(name = "stop")]
+    async fn conductor_stop(&self) -> RpcResult<()>;
+
+    /// Returns true if the conductor is stopped.
+    #[method(name = "stopped")]
+    async fn conductor_stopped(&self) -> RpcResult<bool>;
+
+    /// Transfers leadership to another server of the cluster.
+    #[method(name = "transferLeader")]
+    async fn conductor_transfer_leader(&self) -> RpcResult<()>;
+
+    /// Transfers leadership to the given server.
+    #[method(name = "transferLeaderToServer")]
+    async fn conductor_transfer_leader_to_server(&self, id: String, addr: String) -> RpcResult<()>;
+
+    /// Adds the given server to the cluster as a voter, if the membership is still at `version`.
+    #[method(name = "addServerAsVoter")]
+    async fn conductor_add_server_as_voter(
+        &self,
+        id: String,
+        addr: String,
+        version: u64,
+    ) -> RpcResult<()>;
+
+    /// Adds the given server to the cluster as a non-voter, if the membership is still at
+    /// `version`.
+    #[method(name = "addServerAsNonvoter")]
+    async fn conductor_add_server_as_nonvoter(
+        &self,
+        id: String,
+        addr: String,
+        version: u64,
+    ) -> RpcResult<()>;
+
+    /// Removes the given server from the cluster, if the membership is still at `version`.
+    #[method(name = "removeServer")]
+    async fn conductor_remove_server(&self, id: String, version: u64) -> RpcResult<()>;
+
+    /// Returns the membership of the cluster.
+    #[method(name = "clusterMembership")]
+    async fn conductor_cluster_membership(&self) -> RpcResult<ClusterMembership>;
+
+    /// Commits the given unsafe payload to the cluster state. Only the leader accepts payloads.
+    #[method(name = "commitUnsafePayload")]
+    async fn conductor_commit_unsafe_payload(
+        &self,
+        payload: OpExecutionPayloadEnvelope,
+    ) -> RpcResult<()>;
+
+    /// Returns true if the sequencer managed by the conductor is healthy.
+    #[method(name = "sequencerHealthy")]
+    async fn conductor_sequencer_healthy(&self) -> RpcResult<bool>;
+}
+
+/// The `eth` namespace endpoints, typed with the OP transaction, receipt and request types.
+/// https://ethereum.github.io/execution-apis/api-documentation/
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait OpEthApi {
+    /// Returns the sync status of the node.
+    #[method(name = "syncing")]
+    async fn syncing(&self) -> RpcResult<EthSyncStatus>;
+
+    /// Returns the accounts owned by the node.
+    #[method(name = "accounts")]
+    async fn accounts(&self) -> RpcResult<Vec<Address>>;
+
+    /// Returns the number of the most recent block.
+    #[method(name = "blockNumber")]
+    async fn block_number(&self) -> RpcResult<U256>;
+
+    /// Returns the chain id.
+    #[method(name = "chainId")]
+    async fn chain_id(&self) -> RpcResult<Option<U64>>;
+
+    /// Returns the block with the given hash, with full transactions if `full` is true.
+    #[method(name = "getBlockByHash")]
+    async fn block_by_hash(&self, hash: B256, full: bool) -> RpcResult<Option<Block<Transaction>>>;
+
+    /// Returns the block with the given number, with full transactions if `full` is true.
+    #[method(name = "getBlockByNumber")]
+    async fn block_by_number(
+        &self,
+        number: BlockNumberOrTag,
+        full: bool,
+    ) -> RpcResult<Option<Block<Transaction>>>;
+
+    /// Returns the number of transactions in the block with the given hash.
+    #[method(name = "getBlockTransactionCountByHash")]
+    async fn block_transaction_count_by_hash(&self, hash: B256) -> RpcResult<Option<U256>>;
+
+    /// Returns the number of transactions in the block with the given number.
+    #[method(name = "getBlockTransactionCountByNumber")]
+    async fn block_transaction_count_by_number(
+        &self,
+        number: BlockNumberOrTag,
+    ) -> RpcResult<Option<U256>>;
+
+    /// Returns the number of uncles in the block with the given hash.
+    #[method(name = "getUncleCountByBlockHash")]
+    async fn block_uncles_count_by_hash(&self, hash: B256) -> RpcResult<Option<U256>>;
+
+    /// Returns the number of uncles in the block with the given number.
+    #[method(name = "getUncleCountByBlockNumber")]
+    async fn block_uncles_count_by_number(
+        &self,
+        number: BlockNumberOrTag,
+    ) -> RpcResult<Option<U256>>;
+
+    /// Returns the receipts of all transactions in the given block.
+    #[method(name = "getBlockReceipts")]
+    async fn block_receipts(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<OpTransactionReceipt>>>;
+
+    /// Returns the uncle at the given index of the block with the given hash.
+    #[method(name = "getUncleByBlockHashAndIndex")]
+    async fn uncle_by_block_hash_and_index(
+        &self,
+        hash: B256,
+        index: Index,
+    ) -> RpcResult<Option<Block<Transaction>>>;
+
+    /// Returns the uncle at the given index of the block with the given number.
+    #[method(name = "getUncleByBlockNumberAndIndex")]
+    async fn uncle_by_block_number_and_index(
+        &self,
+        number: BlockNumberOrTag,
+        index: Index,
+    ) -> RpcResult<Option<Block<Transaction>>>;
+
+    /// Returns the EIP-2718 encoded transaction with the given hash.
+    #[method(name = "getRawTransactionByHash")]
+    async fn raw_transaction_by_hash(&self, hash: B256) -> RpcResult<Option<Bytes>>;
+
+    /// Returns the transaction with the given hash.
+    #[method(name = "getTransactionByHash")]
+    async fn transaction_by_hash(&self, hash: B256) -> RpcResult<Option<Transaction>>;
+
+    /// Returns the EIP-2718 encoded transaction at the given index of the block with the given
+    /// hash.
+    #[method(name = "getRawTransactionByBlockHashAndIndex")]
+    async fn raw_transaction_by_block_hash_and_index(
+        &self,
+        hash: B256,
+        index: Index,
+    ) -> RpcResult<Option<Bytes>>;
+
+    /// Returns the transaction at the given index of the block with the given hash.
+    #[method(name = "getTransactionByBlockHashAndIndex")]
+    async fn transaction_by_block_hash_and_index(
+        &self,
+        hash: B256,
+        index: Index,
+    ) -> RpcResult<Option<Transaction>>;
+
+    /// Returns the EIP-2718 encoded transaction at the given index of the block with the given
+    /// number.
+    #[method(name = "getRawTransactionByBlockNumberAndIndex")]
+    async fn raw_transaction_by_block_number_and_index(
+        &self,
+        number: BlockNumberOrTag,
+        index: Index,
+    ) -> RpcResult<Option<Bytes>>;
+
+    /// Returns the transaction at the given index of the block with the given number.
+    #[method(name = "getTransactionByBlockNumberAndIndex")]
+    async fn transaction_by_block_number_and_index(
+        &self,
+        number: BlockNumberOrTag,
+        index: Index,
+    ) -> RpcResult<Option<Transaction>>;
+
+    /// Returns the transaction of the given sender with the given nonce.
+    #[method(name = "getTransactionBySenderAndNonce")]
+    async fn transaction_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: U64,
+    ) -> RpcResult<Option<Transaction>>;
+
+    /// Returns the receipt of the transaction with the given hash.
+    #[method(name = "getTransactionReceipt")]
+    async fn transaction_receipt(&self, hash: B256) -> RpcResult<Option<OpTransactionReceipt>>;
+
+    /// Returns the balance of the given account.
+    #[method(name = "getBalance")]
+    async fn balance(&self, address: Address, block_id: Option<BlockId>) -> RpcResult<U256>;
+
+    /// Returns the value of the given storage slot of the given account.
+    #[method(name = "getStorageAt")]
+    async fn storage_at(
+        &self,
+        address: Address,
+        slot: JsonStorageKey,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<B256>;
+
+    /// Returns the nonce of the given account.
+    #[method(name = "getTransactionCount")]
+    async fn transaction_count(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<U256>;
+
+    /// Returns the code of the given account.
+    #[method(name = "getCode")]
+    async fn get_code(&self, address: Address, block_id: Option<BlockId>) -> RpcResult<Bytes>;
+
+    /// Returns the header of the block with the given number.
+    #[method(name = "getHeaderByNumber")]
+    async fn header_by_number(&self, number: BlockNumberOrTag) -> RpcResult<Option<Header>>;
+
+    /// Returns the header of the block with the given hash.
+    #[method(name = "getHeaderByHash")]
+    async fn header_by_hash(&self, hash: B256) -> RpcResult<Option<Header>>;
+
+    /// Simulates the given blocks of calls on top of the given block.
+    #[method(name = "simulateV1")]
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload<OpTransactionRequest>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<SimulatedBlock<Block<Transaction>>>>;
+
+    /// Executes the given call on top of the given block, without creating a transaction.
+    #[method(name = "call")]
+    async fn call(
+        &self,
+        request: OpTransactionRequest,
+        block_id: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> RpcResult<Bytes>;
+
+    /// Returns the access list and gas used of the given call.
+    #[method(name = "createAccessList")]
+    async fn create_access_list(
+        &self,
+        request: OpTransactionRequest,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<AccessListResult>;
+
+    /// Returns the gas needed to execute the given call.
+    #[method(name = "estimateGas")]
+    async fn estimate_gas(
+        &self,
+        request: OpTransactionRequest,
+        block_id: Option<BlockId>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<U256>;
+
+    /// Returns the current gas price.
+    #[method(name = "gasPrice")]
+    async fn gas_price(&self) -> RpcResult<U256>;
+
+    /// Returns the suggested priority fee per gas.
+    #[method(name = "maxPriorityFeePerGas")]
+    async fn max_priority_fee_per_gas(&self) -> RpcResult<U256>;
+
+    /// Returns the fee history of the given number of blocks up to `newest_block`.
+    #[method(name = "feeHistory")]
+    async fn fee_history(
+        &self,
+        block_count: U64,
+        newest_block: BlockNumberOrTag,
+        reward_percentiles: Option<Vec<f64>>,
+    ) -> RpcResult<FeeHistory>;
+
+    /// Signs the given transaction request with an account of the node and submits it.
+    #[method(name = "sendTransaction")]
+    async fn send_transaction(&self, request: OpTransactionRequest) -> RpcResult<B256>;
+
+    /// Submits the given EIP-2718 encoded transaction. Returns the transaction hash.
+    #[method(name = "sendRawTransaction")]
+    async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256>;
+
+    /// Signs the given message with an account of the node.
+    #[method(name = "sign")]
+    async fn sign(&self, address: Address, message: Bytes) -> RpcResult<Bytes>;
+
+    /// Signs the given transaction request with an account of the node, returning the EIP-2718
+    /// encoded transaction.
+    #[method(name = "signTransaction")]
+    async fn sign_transaction(&self, request: OpTransactionRequest) -> RpcResult<Bytes>;
+
+    /// Returns the account and storage proofs of the given account.
+    #[method(name = "getProof")]
+    async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<JsonStorageKey>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<EIP1186AccountProofResponse>;
+
+    /// Returns the logs matching the given filter.
+    #[method(name = "getLogs")]
+    async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>>;
+}
+
+/// The `eth` namespace filter endpoints, typed with the OP transaction type.
+/// https://ethereum.github.io/execution-apis/api-documentation/
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait OpEthFilterApi {
+    /// Installs a log filter, returning its id.
+    #[method(name = "newFilter")]
+    async fn new_filter(&self, filter: Filter) -> RpcResult<FilterId>;
+
+    /// Installs a filter for new blocks, returning its id.
+    #[method(name = "newBlockFilter")]
+    async fn new_block_filter(&self) -> RpcResult<FilterId>;
+
+    /// Installs a filter for new pending transactions, returning its id.
+    #[method(name = "newPendingTransactionFilter")]
+    async fn new_pending_transaction_filter(
+        &self,
+        kind: Option<PendingTransactionFilterKind>,
+    ) -> RpcResult<FilterId>;
+
+    /// Returns the changes of the given filter since it was last polled.
+    #[method(name = "getFilterChanges")]
+    async fn filter_changes(&self, id: FilterId) -> RpcResult<FilterChanges<Transaction>>;
+
+    /// Returns all logs matching the given log filter.
+    #[method(name = "getFilterLogs")]
+    async fn filter_logs(&self, id: FilterId) -> RpcResult<Vec<Log>>;
+
+    /// Uninstalls the given filter.
+    #[method(name = "uninstallFilter")]
+    async fn uninstall_filter(&self, id: FilterId) -> RpcResult<bool>;
+}
+
+/// Conditional transaction submission in the `eth` namespace
+/// https://github.com/ethereum-optimism/op-geth/blob/928070c7fc097362ed2d40a4f72889ba91544931/core/types/transaction_conditional.go
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait EthConditionalApi {
+    /// Submits the given EIP-2718 encoded transaction, to be included only while the given
+    /// conditional holds. Returns the transaction hash.
+    #[method(name = "sendRawTransactionConditional")]
+    async fn send_raw_transaction_conditional(
+        &self,
+        bytes: Bytes,
+        condition: TransactionConditional,
+    ) -> RpcResult<B256>;
 }
 
 /// Op API extension for controlling the miner.
@@ -36,3 +569,54 @@ pub trait MinerApiExt {
     #[method(name = "setGasLimit")]
     async fn set_gas_limit(&self, gas_limit: U64) -> RpcResult<bool>;
 }
+
+/// The supervisor namespace endpoints for interop.
+/// https://specs.optimism.io/interop/supervisor.html
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "supervisor"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "supervisor"))]
+pub trait SupervisorApi {
+    /// Checks that the executing messages declared by the access-list `inbox_entries` are valid
+    /// at `min_safety` or safer, in the context of the `executing_descriptor`.
+    #[method(name = "checkAccessList")]
+    async fn check_access_list(
+        &self,
+        inbox_entries: Vec<B256>,
+        min_safety: SafetyLevel,
+        executing_descriptor: ExecutingDescriptor,
+    ) -> RpcResult<()>;
+
+    /// Returns the L1 block the given cross-safe L2 block was derived from.
+    #[method(name = "crossDerivedToSource")]
+    async fn cross_derived_to_source(
+        &self,
+        chain_id: U64,
+        derived: BlockNumHash,
+    ) -> RpcResult<BlockInfo>;
+
+    /// Returns the local-unsafe head of the given chain.
+    #[method(name = "localUnsafe")]
+    async fn local_unsafe(&self, chain_id: U64) -> RpcResult<BlockNumHash>;
+
+    /// Returns the cross-safe head of the given chain, and the L1 block it was derived from.
+    #[method(name = "crossSafe")]
+    async fn cross_safe(&self, chain_id: U64) -> RpcResult<DerivedIdPair>;
+
+    /// Returns the finalized head of the given chain.
+    #[method(name = "finalized")]
+    async fn finalized(&self, chain_id: U64) -> RpcResult<BlockNumHash>;
+
+    /// Returns the super root of the dependency set at the given timestamp.
+    #[method(name = "superRootAtTimestamp")]
+    async fn super_root_at_timestamp(&self, timestamp: U64) -> RpcResult<SuperRootOutput>;
+
+    /// Returns the sync status of the supervisor.
+    #[method(name = "syncStatus")]
+    async fn sync_status(&self) -> RpcResult<SupervisorSyncStatus>;
+
+    /// Returns the last L2 block of each chain that was derived from the given L1 block.
+    #[method(name = "allSafeDerivedAt")]
+    async fn all_safe_derived_at(
+        &self,
+        derived_from: BlockNumHash,
+    ) -> RpcResult<BTreeMap<ChainId, BlockNumHash>>;
+}
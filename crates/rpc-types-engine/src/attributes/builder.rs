@@ -0,0 +1,357 @@
+//! Builder for [`OpPayloadAttributes`].
+
+use super::OpPayloadAttributes;
+use alloc::vec::Vec;
+use alloy_consensus::TxReceipt;
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Address, B64, B256, Bytes, Log};
+use alloy_rpc_types_engine::PayloadAttributes;
+use op_alloy_consensus::{
+    DepositError, EIP1559ParamError, OpHardforkSchedule, TxDeposit, decode_deposits_from_receipts,
+};
+
+/// An error building [`OpPayloadAttributes`] with the [`OpPayloadAttributesBuilder`].
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+pub enum OpAttributesBuilderError {
+    /// The L1 info deposit was not provided.
+    #[error("Missing L1 info deposit")]
+    MissingL1InfoDeposit,
+    /// The gas limit was not provided.
+    #[error("Missing gas limit")]
+    MissingGasLimit,
+    /// The parent beacon block root was not provided after Ecotone.
+    #[error("Missing parent beacon block root after Ecotone")]
+    MissingParentBeaconBlockRoot,
+    /// The parent beacon block root was provided before Ecotone.
+    #[error("Parent beacon block root must be None before Ecotone")]
+    UnexpectedParentBeaconBlockRoot,
+    /// Upgrade transactions were provided outside of a hardfork activation block.
+    #[error("Upgrade transactions are only allowed in hardfork activation blocks")]
+    UnexpectedUpgradeTransactions,
+    /// Invalid EIP-1559 parameters or minimum base fee for the active hardforks.
+    #[error(transparent)]
+    EIP1559Params(#[from] EIP1559ParamError),
+    /// The user deposits could not be decoded.
+    #[error(transparent)]
+    Deposit(#[from] DepositError),
+}
+
+/// Builder for the [`OpPayloadAttributes`] of a block.
+///
+/// The attributes are versioned according to the [`OpHardforkSchedule`] at the block timestamp:
+/// - Canyon: withdrawals are set to an empty list.
+/// - Ecotone: the parent beacon block root is required.
+/// - Holocene: the EIP-1559 parameters are required.
+/// - Jovian: the minimum base fee is required.
+///
+/// The forced transactions are, in order: the L1 info deposit, the user deposits and the upgrade
+/// transactions. Upgrade transactions are only allowed in blocks that activate a hardfork.
+#[derive(Debug, Clone)]
+pub struct OpPayloadAttributesBuilder {
+    hardforks: OpHardforkSchedule,
+    parent_timestamp: u64,
+    timestamp: u64,
+    l1_info_deposit: Option<TxDeposit>,
+    user_deposits: Vec<TxDeposit>,
+    upgrade_transactions: Vec<Bytes>,
+    suggested_fee_recipient: Address,
+    prev_randao: B256,
+    parent_beacon_block_root: Option<B256>,
+    gas_limit: Option<u64>,
+    eip_1559_params: Option<B64>,
+    min_base_fee: Option<u64>,
+    no_tx_pool: bool,
+}
+
+impl OpPayloadAttributesBuilder {
+    /// Creates a new builder for a block with the given `timestamp`, built on top of a parent with
+    /// `parent_timestamp`.
+    pub const fn new(hardforks: OpHardforkSchedule, parent_timestamp: u64, timestamp: u64) -> Self {
+        Self {
+            hardforks,
+            parent_timestamp,
+            timestamp,
+            l1_info_deposit: None,
+            user_deposits: Vec::new(),
+            upgrade_transactions: Vec::new(),
+            suggested_fee_recipient: Address::ZERO,
+            prev_randao: B256::ZERO,
+            parent_beacon_block_root: None,
+            gas_limit: None,
+            eip_1559_params: None,
+            min_base_fee: None,
+            no_tx_pool: false,
+        }
+    }
+
+    /// Sets the L1 info deposit, the first transaction of the block.
+    pub fn l1_info_deposit(mut self, deposit: TxDeposit) -> Self {
+        self.l1_info_deposit = Some(deposit);
+        self
+    }
+
+    /// Appends user deposits, included after the L1 info deposit.
+    pub fn user_deposits(mut self, deposits: impl IntoIterator<Item = TxDeposit>) -> Self {
+        self.user_deposits.extend(deposits);
+        self
+    }
+
+    /// Appends the user deposits emitted by the `deposit_contract` in the receipts of the L1
+    /// origin with the given `l1_block_hash`.
+    ///
+    /// See [`decode_deposits_from_receipts`].
+    pub fn user_deposits_from_receipts<R>(
+        self,
+        deposit_contract: Address,
+        l1_block_hash: B256,
+        receipts: &[R],
+    ) -> Result<Self, DepositError>
+    where
+        R: TxReceipt<Log = Log>,
+    {
+        let deposits = decode_deposits_from_receipts(deposit_contract, l1_block_hash, receipts)?;
+        Ok(self.user_deposits(deposits))
+    }
+
+    /// Appends EIP-2718 encoded upgrade transactions, included after the user deposits.
+    pub fn upgrade_transactions(mut self, transactions: impl IntoIterator<Item = Bytes>) -> Self {
+        self.upgrade_transactions.extend(transactions);
+        self
+    }
+
+    /// Sets the suggested fee recipient.
+    pub const fn suggested_fee_recipient(mut self, suggested_fee_recipient: Address) -> Self {
+        self.suggested_fee_recipient = suggested_fee_recipient;
+        self
+    }
+
+    /// Sets the `prev_randao` value, the mix digest of the L1 origin.
+    pub const fn prev_randao(mut self, prev_randao: B256) -> Self {
+        self.prev_randao = prev_randao;
+        self
+    }
+
+    /// Sets the parent beacon block root of the L1 origin.
+    pub const fn parent_beacon_block_root(mut self, parent_beacon_block_root: B256) -> Self {
+        self.parent_beacon_block_root = Some(parent_beacon_block_root);
+        self
+    }
+
+    /// Sets the gas limit.
+    pub const fn gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    /// Sets the EIP-1559 parameters.
+    pub const fn eip_1559_params(mut self, eip_1559_params: B64) -> Self {
+        self.eip_1559_params = Some(eip_1559_params);
+        self
+    }
+
+    /// Sets the minimum base fee.
+    pub const fn min_base_fee(mut self, min_base_fee: u64) -> Self {
+        self.min_base_fee = Some(min_base_fee);
+        self
+    }
+
+    /// Sets whether transactions are taken out of the tx-pool.
+    pub const fn no_tx_pool(mut self, no_tx_pool: bool) -> Self {
+        self.no_tx_pool = no_tx_pool;
+        self
+    }
+
+    /// Builds the [`OpPayloadAttributes`], validating the inputs against the active hardforks.
+    pub fn build(self) -> Result<OpPayloadAttributes, OpAttributesBuilderError> {
+        let hardforks = &self.hardforks;
+        let timestamp = self.timestamp;
+
+        let l1_info_deposit =
+            self.l1_info_deposit.ok_or(OpAttributesBuilderError::MissingL1InfoDeposit)?;
+        let gas_limit = self.gas_limit.ok_or(OpAttributesBuilderError::MissingGasLimit)?;
+
+        let parent_beacon_block_root =
+            match (hardforks.is_ecotone_active(timestamp), self.parent_beacon_block_root) {
+                (true, None) => return Err(OpAttributesBuilderError::MissingParentBeaconBlockRoot),
+                (false, Some(_)) => {
+                    return Err(OpAttributesBuilderError::UnexpectedParentBeaconBlockRoot);
+                }
+                (_, root) => root,
+            };
+
+        match (hardforks.is_holocene_active(timestamp), self.eip_1559_params) {
+            (true, None) => return Err(EIP1559ParamError::NoEIP1559Params.into()),
+            (false, Some(_)) => return Err(EIP1559ParamError::EIP1559ParamsMustBeNone.into()),
+            _ => {}
+        }
+
+        match (hardforks.is_jovian_active(timestamp), self.min_base_fee) {
+            (true, None) => return Err(EIP1559ParamError::MinBaseFeeNotSet.into()),
+            (false, Some(_)) => return Err(EIP1559ParamError::MinBaseFeeMustBeNone.into()),
+            _ => {}
+        }
+
+        if !self.upgrade_transactions.is_empty()
+            && hardforks.activated_forks(self.parent_timestamp, timestamp).next().is_none()
+        {
+            return Err(OpAttributesBuilderError::UnexpectedUpgradeTransactions);
+        }
+
+        let mut transactions =
+            Vec::with_capacity(1 + self.user_deposits.len() + self.upgrade_transactions.len());
+        transactions.push(l1_info_deposit.encoded_2718().into());
+        transactions.extend(self.user_deposits.iter().map(|tx| tx.encoded_2718().into()));
+        transactions.extend(self.upgrade_transactions);
+
+        Ok(OpPayloadAttributes {
+            payload_attributes: PayloadAttributes {
+                timestamp,
+                prev_randao: self.prev_randao,
+                suggested_fee_recipient: self.suggested_fee_recipient,
+                withdrawals: hardforks.is_canyon_active(timestamp).then(Vec::new),
+                parent_beacon_block_root,
+            },
+            transactions: Some(transactions),
+            no_tx_pool: Some(self.no_tx_pool),
+            gas_limit: Some(gas_limit),
+            eip_1559_params: self.eip_1559_params,
+            min_base_fee: self.min_base_fee,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_primitives::{TxKind, U256, b64, bytes};
+    use alloy_rpc_types_engine::ForkchoiceUpdateVersion;
+
+    fn l1_info_deposit() -> TxDeposit {
+        TxDeposit {
+            source_hash: B256::repeat_byte(1),
+            from: Address::repeat_byte(2),
+            to: TxKind::Call(Address::repeat_byte(3)),
+            gas_limit: 1_000_000,
+            is_system_transaction: true,
+            input: bytes!("015d8eb9"),
+            ..Default::default()
+        }
+    }
+
+    fn user_deposit() -> TxDeposit {
+        TxDeposit {
+            source_hash: B256::repeat_byte(4),
+            from: Address::repeat_byte(5),
+            to: TxKind::Create,
+            value: U256::from(1),
+            gas_limit: 21_000,
+            ..Default::default()
+        }
+    }
+
+    fn jovian_schedule() -> OpHardforkSchedule {
+        OpHardforkSchedule {
+            regolith_time: Some(0),
+            canyon_time: Some(0),
+            ecotone_time: Some(0),
+            fjord_time: Some(0),
+            granite_time: Some(0),
+            holocene_time: Some(0),
+            isthmus_time: Some(0),
+            jovian_time: Some(10),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_build_bedrock() {
+        let attributes = OpPayloadAttributesBuilder::new(OpHardforkSchedule::default(), 0, 2)
+            .l1_info_deposit(l1_info_deposit())
+            .gas_limit(30_000_000)
+            .build()
+            .unwrap();
+
+        assert_eq!(attributes.payload_attributes.withdrawals, None);
+        assert_eq!(attributes.payload_attributes.parent_beacon_block_root, None);
+        assert_eq!(attributes.fcu_version(), ForkchoiceUpdateVersion::V1);
+    }
+
+    #[test]
+    fn test_build_jovian() {
+        let upgrade_tx = bytes!("7e01");
+        let attributes = OpPayloadAttributesBuilder::new(jovian_schedule(), 8, 10)
+            .l1_info_deposit(l1_info_deposit())
+            .user_deposits([user_deposit()])
+            .upgrade_transactions([upgrade_tx.clone()])
+            .suggested_fee_recipient(Address::repeat_byte(6))
+            .prev_randao(B256::repeat_byte(7))
+            .parent_beacon_block_root(B256::repeat_byte(8))
+            .gas_limit(30_000_000)
+            .eip_1559_params(b64!("0000000800000008"))
+            .min_base_fee(1)
+            .no_tx_pool(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            attributes.transactions,
+            Some(vec![
+                l1_info_deposit().encoded_2718().into(),
+                user_deposit().encoded_2718().into(),
+                upgrade_tx
+            ])
+        );
+        assert_eq!(attributes.payload_attributes.withdrawals, Some(vec![]));
+        assert_eq!(
+            attributes.payload_attributes.parent_beacon_block_root,
+            Some(B256::repeat_byte(8))
+        );
+        assert_eq!(attributes.gas_limit, Some(30_000_000));
+        assert_eq!(attributes.no_tx_pool, Some(true));
+        assert_eq!(attributes.min_base_fee, Some(1));
+        assert_eq!(attributes.fcu_version(), ForkchoiceUpdateVersion::V3);
+    }
+
+    #[test]
+    fn test_build_invalid_combinations() {
+        let builder = || {
+            OpPayloadAttributesBuilder::new(jovian_schedule(), 2, 4)
+                .l1_info_deposit(l1_info_deposit())
+                .parent_beacon_block_root(B256::ZERO)
+                .gas_limit(30_000_000)
+        };
+
+        assert_eq!(
+            builder().build().unwrap_err(),
+            OpAttributesBuilderError::EIP1559Params(EIP1559ParamError::NoEIP1559Params)
+        );
+        assert_eq!(
+            builder().eip_1559_params(B64::ZERO).min_base_fee(1).build().unwrap_err(),
+            OpAttributesBuilderError::EIP1559Params(EIP1559ParamError::MinBaseFeeMustBeNone)
+        );
+        assert_eq!(
+            builder().eip_1559_params(B64::ZERO).upgrade_transactions([bytes!("7e")]).build(),
+            Err(OpAttributesBuilderError::UnexpectedUpgradeTransactions)
+        );
+        assert!(builder().eip_1559_params(B64::ZERO).build().is_ok());
+
+        let err = OpPayloadAttributesBuilder::new(OpHardforkSchedule::default(), 0, 2)
+            .l1_info_deposit(l1_info_deposit())
+            .gas_limit(30_000_000)
+            .eip_1559_params(B64::ZERO)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            OpAttributesBuilderError::EIP1559Params(EIP1559ParamError::EIP1559ParamsMustBeNone)
+        );
+
+        let err = OpPayloadAttributesBuilder::new(jovian_schedule(), 2, 4)
+            .l1_info_deposit(l1_info_deposit())
+            .gas_limit(30_000_000)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, OpAttributesBuilderError::MissingParentBeaconBlockRoot);
+    }
+}
@@ -0,0 +1,359 @@
+//! Consistency checks between [`OpPayloadAttributes`] and [`OpBlock`]s.
+
+use super::OpPayloadAttributes;
+use alloc::vec::Vec;
+use alloy_eips::{eip1559::BaseFeeParams, eip2718::Encodable2718, eip4895::Withdrawal};
+use alloy_primitives::{Address, B64, B256, Bytes};
+use alloy_rpc_types_engine::PayloadAttributes;
+use op_alloy_consensus::{
+    EIP1559ParamError, OpBlock, decode_holocene_extra_data, decode_jovian_extra_data,
+    encode_holocene_extra_data,
+};
+
+/// A field of an [`OpBlock`] that doesn't match the [`OpPayloadAttributes`] it is checked against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttributesMismatch {
+    /// The block timestamp differs.
+    Timestamp {
+        /// The timestamp of the attributes.
+        expected: u64,
+        /// The timestamp of the block.
+        actual: u64,
+    },
+    /// The block `prev_randao` (mix hash) differs.
+    PrevRandao {
+        /// The `prev_randao` of the attributes.
+        expected: B256,
+        /// The mix hash of the block.
+        actual: B256,
+    },
+    /// The block fee recipient differs.
+    SuggestedFeeRecipient {
+        /// The suggested fee recipient of the attributes.
+        expected: Address,
+        /// The beneficiary of the block.
+        actual: Address,
+    },
+    /// The number of transactions differs.
+    TransactionCount {
+        /// The number of transactions in the attributes.
+        expected: usize,
+        /// The number of transactions in the block.
+        actual: usize,
+    },
+    /// The EIP-2718 encoding of the transaction at the given index differs.
+    Transaction {
+        /// The index of the transaction.
+        index: usize,
+    },
+    /// The block gas limit differs.
+    GasLimit {
+        /// The gas limit of the attributes.
+        expected: Option<u64>,
+        /// The gas limit of the block.
+        actual: u64,
+    },
+    /// The block withdrawals differ.
+    Withdrawals {
+        /// The withdrawals of the attributes.
+        expected: Option<Vec<Withdrawal>>,
+        /// The withdrawals of the block.
+        actual: Option<Vec<Withdrawal>>,
+    },
+    /// The block parent beacon block root differs.
+    ParentBeaconBlockRoot {
+        /// The parent beacon block root of the attributes.
+        expected: Option<B256>,
+        /// The parent beacon block root of the block.
+        actual: Option<B256>,
+    },
+    /// The block extra data could not be decoded as Holocene or Jovian extra data.
+    InvalidExtraData(EIP1559ParamError),
+    /// The EIP-1559 parameters encoded in the block extra data differ.
+    EIP1559Params {
+        /// The (`elasticity`, `denominator`) of the attributes.
+        expected: (u32, u32),
+        /// The (`elasticity`, `denominator`) of the block.
+        actual: (u32, u32),
+    },
+    /// The minimum base fee encoded in the block extra data differs.
+    MinBaseFee {
+        /// The minimum base fee of the attributes.
+        expected: Option<u64>,
+        /// The minimum base fee of the block.
+        actual: Option<u64>,
+    },
+}
+
+impl OpPayloadAttributes {
+    /// Derives the [`OpPayloadAttributes`] that the given [`OpBlock`] was built from.
+    ///
+    /// All transactions of the block are forced and `no_tx_pool` is set. The EIP-1559 parameters
+    /// and minimum base fee are decoded from the Holocene or Jovian extra data, and are left
+    /// unset if the extra data is empty (pre-Holocene).
+    pub fn from_block(block: &OpBlock) -> Result<Self, EIP1559ParamError> {
+        let header = &block.header;
+        let (eip_1559_params, min_base_fee) = match header.extra_data.first() {
+            None => (None, None),
+            Some(0) => {
+                decode_holocene_extra_data(&header.extra_data)?;
+                (Some(B64::from_slice(&header.extra_data[1..9])), None)
+            }
+            Some(_) => {
+                let (_, _, min_base_fee) = decode_jovian_extra_data(&header.extra_data)?;
+                (Some(B64::from_slice(&header.extra_data[1..9])), Some(min_base_fee))
+            }
+        };
+
+        Ok(Self {
+            payload_attributes: PayloadAttributes {
+                timestamp: header.timestamp,
+                prev_randao: header.mix_hash,
+                suggested_fee_recipient: header.beneficiary,
+                withdrawals: block.body.withdrawals.clone().map(|w| w.into_inner()),
+                parent_beacon_block_root: header.parent_beacon_block_root,
+            },
+            transactions: Some(
+                block.body.transactions.iter().map(|tx| tx.encoded_2718().into()).collect(),
+            ),
+            no_tx_pool: Some(true),
+            gas_limit: Some(header.gas_limit),
+            eip_1559_params,
+            min_base_fee,
+        })
+    }
+
+    /// Returns true if the given [`OpBlock`] matches these attributes.
+    ///
+    /// See [`block_mismatches`](Self::block_mismatches).
+    pub fn matches_block(&self, block: &OpBlock, default_base_fee_params: BaseFeeParams) -> bool {
+        self.block_mismatches(block, default_base_fee_params).is_empty()
+    }
+
+    /// Checks the given [`OpBlock`] against these attributes, and returns all mismatched fields.
+    ///
+    /// The `default_base_fee_params` are used in place of zero EIP-1559 parameters, as when the
+    /// block extra data is encoded. The extra data is only checked if the attributes carry
+    /// EIP-1559 parameters (post-Holocene).
+    ///
+    /// See also: <https://github.com/ethereum-optimism/optimism/blob/develop/op-node/rollup/attributes/engine_consolidate.go>
+    pub fn block_mismatches(
+        &self,
+        block: &OpBlock,
+        default_base_fee_params: BaseFeeParams,
+    ) -> Vec<AttributesMismatch> {
+        let attributes = &self.payload_attributes;
+        let header = &block.header;
+        let mut mismatches = Vec::new();
+
+        if attributes.timestamp != header.timestamp {
+            mismatches.push(AttributesMismatch::Timestamp {
+                expected: attributes.timestamp,
+                actual: header.timestamp,
+            });
+        }
+        if attributes.prev_randao != header.mix_hash {
+            mismatches.push(AttributesMismatch::PrevRandao {
+                expected: attributes.prev_randao,
+                actual: header.mix_hash,
+            });
+        }
+        if attributes.suggested_fee_recipient != header.beneficiary {
+            mismatches.push(AttributesMismatch::SuggestedFeeRecipient {
+                expected: attributes.suggested_fee_recipient,
+                actual: header.beneficiary,
+            });
+        }
+
+        let transactions = self.transactions.as_deref().unwrap_or_default();
+        let block_transactions = &block.body.transactions;
+        if transactions.len() != block_transactions.len() {
+            mismatches.push(AttributesMismatch::TransactionCount {
+                expected: transactions.len(),
+                actual: block_transactions.len(),
+            });
+        }
+        for (index, (tx, block_tx)) in transactions.iter().zip(block_transactions).enumerate() {
+            if tx.as_ref() != block_tx.encoded_2718().as_slice() {
+                mismatches.push(AttributesMismatch::Transaction { index });
+            }
+        }
+
+        if self.gas_limit != Some(header.gas_limit) {
+            mismatches.push(AttributesMismatch::GasLimit {
+                expected: self.gas_limit,
+                actual: header.gas_limit,
+            });
+        }
+
+        let block_withdrawals = block.body.withdrawals.as_ref().map(|w| w.as_slice());
+        if attributes.withdrawals.as_deref() != block_withdrawals {
+            mismatches.push(AttributesMismatch::Withdrawals {
+                expected: attributes.withdrawals.clone(),
+                actual: block_withdrawals.map(<[Withdrawal]>::to_vec),
+            });
+        }
+
+        if attributes.parent_beacon_block_root != header.parent_beacon_block_root {
+            mismatches.push(AttributesMismatch::ParentBeaconBlockRoot {
+                expected: attributes.parent_beacon_block_root,
+                actual: header.parent_beacon_block_root,
+            });
+        }
+
+        if self.eip_1559_params.is_some() {
+            self.check_extra_data(&header.extra_data, default_base_fee_params, &mut mismatches);
+        }
+
+        mismatches
+    }
+
+    /// Checks the EIP-1559 parameters and minimum base fee encoded in the block `extra_data`.
+    fn check_extra_data(
+        &self,
+        extra_data: &Bytes,
+        default_base_fee_params: BaseFeeParams,
+        mismatches: &mut Vec<AttributesMismatch>,
+    ) {
+        let expected = match self.expected_eip_1559_params(default_base_fee_params) {
+            Ok(expected) => expected,
+            Err(err) => {
+                mismatches.push(AttributesMismatch::InvalidExtraData(err));
+                return;
+            }
+        };
+        let actual = match extra_data.first() {
+            Some(0) => decode_holocene_extra_data(extra_data).map(|(e, d)| (e, d, None)),
+            _ => decode_jovian_extra_data(extra_data).map(|(e, d, fee)| (e, d, Some(fee))),
+        };
+        let (elasticity, denominator, min_base_fee) = match actual {
+            Ok(actual) => actual,
+            Err(err) => {
+                mismatches.push(AttributesMismatch::InvalidExtraData(err));
+                return;
+            }
+        };
+
+        if expected != (elasticity, denominator) {
+            mismatches.push(AttributesMismatch::EIP1559Params {
+                expected,
+                actual: (elasticity, denominator),
+            });
+        }
+        if self.min_base_fee != min_base_fee {
+            mismatches.push(AttributesMismatch::MinBaseFee {
+                expected: self.min_base_fee,
+                actual: min_base_fee,
+            });
+        }
+    }
+
+    /// Returns the (`elasticity`, `denominator`) the block is built with, substituting the
+    /// `default_base_fee_params` for zero EIP-1559 parameters.
+    fn expected_eip_1559_params(
+        &self,
+        default_base_fee_params: BaseFeeParams,
+    ) -> Result<(u32, u32), EIP1559ParamError> {
+        let params = self.eip_1559_params.ok_or(EIP1559ParamError::NoEIP1559Params)?;
+        let extra_data = encode_holocene_extra_data(params, default_base_fee_params)?;
+        decode_holocene_extra_data(&extra_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_consensus::{BlockBody, Header};
+    use alloy_primitives::{Sealable, U256, b64, bytes};
+    use op_alloy_consensus::{OpTxEnvelope, TxDeposit, encode_jovian_extra_data};
+
+    const DEFAULT_PARAMS: BaseFeeParams = BaseFeeParams::new(250, 6);
+
+    fn block() -> OpBlock {
+        let deposit = TxDeposit {
+            source_hash: B256::repeat_byte(1),
+            from: Address::repeat_byte(2),
+            value: U256::from(3),
+            gas_limit: 1_000_000,
+            input: bytes!("015d8eb9"),
+            ..Default::default()
+        };
+        OpBlock {
+            header: Header {
+                timestamp: 10,
+                mix_hash: B256::repeat_byte(4),
+                beneficiary: Address::repeat_byte(5),
+                gas_limit: 30_000_000,
+                parent_beacon_block_root: Some(B256::repeat_byte(6)),
+                extra_data: encode_jovian_extra_data(b64!("0000000800000002"), DEFAULT_PARAMS, 7)
+                    .unwrap(),
+                ..Default::default()
+            },
+            body: BlockBody {
+                transactions: vec![OpTxEnvelope::Deposit(deposit.seal_slow())],
+                ommers: vec![],
+                withdrawals: Some(Default::default()),
+            },
+        }
+    }
+
+    #[test]
+    fn test_from_block_roundtrip() {
+        let block = block();
+        let attributes = OpPayloadAttributes::from_block(&block).unwrap();
+
+        assert_eq!(attributes.eip_1559_params, Some(b64!("0000000800000002")));
+        assert_eq!(attributes.min_base_fee, Some(7));
+        assert_eq!(attributes.no_tx_pool, Some(true));
+        assert_eq!(attributes.payload_attributes.withdrawals, Some(vec![]));
+        assert!(attributes.matches_block(&block, DEFAULT_PARAMS));
+    }
+
+    #[test]
+    fn test_from_block_pre_holocene() {
+        let mut block = block();
+        block.header.extra_data = Bytes::new();
+
+        let attributes = OpPayloadAttributes::from_block(&block).unwrap();
+        assert_eq!(attributes.eip_1559_params, None);
+        assert_eq!(attributes.min_base_fee, None);
+
+        block.header.extra_data = bytes!("02");
+        assert_eq!(
+            OpPayloadAttributes::from_block(&block).unwrap_err(),
+            EIP1559ParamError::InvalidExtraDataLength
+        );
+    }
+
+    #[test]
+    fn test_block_mismatches() {
+        let block = block();
+        let mut attributes = OpPayloadAttributes::from_block(&block).unwrap();
+        attributes.payload_attributes.timestamp = 12;
+        attributes.payload_attributes.withdrawals = None;
+        attributes.transactions.as_mut().unwrap()[0] = bytes!("7e");
+        attributes.gas_limit = None;
+        attributes.eip_1559_params = Some(B64::ZERO);
+        attributes.min_base_fee = Some(8);
+
+        assert_eq!(
+            attributes.block_mismatches(&block, DEFAULT_PARAMS),
+            vec![
+                AttributesMismatch::Timestamp { expected: 12, actual: 10 },
+                AttributesMismatch::Transaction { index: 0 },
+                AttributesMismatch::GasLimit { expected: None, actual: 30_000_000 },
+                AttributesMismatch::Withdrawals { expected: None, actual: Some(vec![]) },
+                AttributesMismatch::EIP1559Params { expected: (6, 250), actual: (2, 8) },
+                AttributesMismatch::MinBaseFee { expected: Some(8), actual: Some(7) },
+            ]
+        );
+
+        attributes.transactions = None;
+        assert!(
+            attributes
+                .block_mismatches(&block, DEFAULT_PARAMS)
+                .contains(&AttributesMismatch::TransactionCount { expected: 0, actual: 1 })
+        );
+    }
+}
@@ -1,18 +1,28 @@
 //! Optimism-specific payload attributes.
 
+mod builder;
+pub use builder::{OpAttributesBuilderError, OpPayloadAttributesBuilder};
+
+mod consistency;
+pub use consistency::AttributesMismatch;
+
+mod replacement;
+pub use replacement::BlockReplacementError;
+
 use alloc::vec::Vec;
 use alloy_eips::{
     Decodable2718,
     eip1559::BaseFeeParams,
     eip2718::{Eip2718Result, WithEncoded},
 };
-use alloy_primitives::{B64, Bytes};
+use alloy_primitives::{B64, B256, Bytes, keccak256};
 use alloy_rlp::Result;
-use alloy_rpc_types_engine::PayloadAttributes;
+use alloy_rpc_types_engine::{ForkchoiceUpdateVersion, PayloadAttributes, PayloadId};
 use op_alloy_consensus::{
     EIP1559ParamError, OpTxEnvelope, decode_eip_1559_params, encode_holocene_extra_data,
     encode_jovian_extra_data,
 };
+use sha2::{Digest, Sha256};
 
 /// Optimism Payload Attributes
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
@@ -92,6 +102,86 @@ impl OpPayloadAttributes {
             .ok_or(EIP1559ParamError::NoEIP1559Params)?
     }
 
+    /// Returns the `engine_forkchoiceUpdated` version to send these attributes with.
+    ///
+    /// Attributes with a parent beacon block root (Ecotone) use V3, attributes with withdrawals
+    /// (Canyon) use V2, and all others use V1.
+    pub const fn fcu_version(&self) -> ForkchoiceUpdateVersion {
+        if self.payload_attributes.parent_beacon_block_root.is_some() {
+            ForkchoiceUpdateVersion::V3
+        } else if self.payload_attributes.withdrawals.is_some() {
+            ForkchoiceUpdateVersion::V2
+        } else {
+            ForkchoiceUpdateVersion::V1
+        }
+    }
+
+    /// Computes the [`PayloadId`] for a payload built on top of `parent_hash` with these
+    /// attributes.
+    ///
+    /// This mirrors op-geth's `BuildPayloadArgs.Id()`, so that the returned id matches the one
+    /// handed out by the execution engine in the `engine_forkchoiceUpdated` response. The
+    /// `version` is the payload version byte, e.g. `3` for `engine_forkchoiceUpdatedV3`, and is
+    /// written to the first byte of the id.
+    ///
+    /// The hash input is, in order:
+    /// - the parent hash, timestamp, prev randao and fee recipient
+    /// - the RLP encoded withdrawals, an empty list if there are none
+    /// - the parent beacon block root, if any
+    /// - if `no_tx_pool` is set or transactions are forced: the `no_tx_pool` flag, the number of
+    ///   forced transactions and their hashes
+    /// - the gas limit, EIP-1559 parameters and minimum base fee, each only if set
+    ///
+    /// See also: <https://github.com/ethereum-optimism/op-geth/blob/optimism/miner/payload_building.go>
+    pub fn payload_id(&self, parent_hash: &B256, version: u8) -> PayloadId {
+        let attributes = &self.payload_attributes;
+        let mut hasher = Sha256::new();
+        hasher.update(parent_hash);
+        hasher.update(attributes.timestamp.to_be_bytes());
+        hasher.update(attributes.prev_randao);
+        hasher.update(attributes.suggested_fee_recipient);
+
+        // op-geth RLP encodes a nil withdrawals list as an empty list.
+        let mut withdrawals = Vec::new();
+        alloy_rlp::encode_list(
+            attributes.withdrawals.as_deref().unwrap_or_default(),
+            &mut withdrawals,
+        );
+        hasher.update(withdrawals);
+
+        if let Some(parent_beacon_block_root) = attributes.parent_beacon_block_root {
+            hasher.update(parent_beacon_block_root);
+        }
+
+        let no_tx_pool = self.no_tx_pool.unwrap_or_default();
+        let transactions = self.transactions.as_deref().unwrap_or_default();
+        if no_tx_pool || !transactions.is_empty() {
+            hasher.update([no_tx_pool as u8]);
+            hasher.update((transactions.len() as u64).to_be_bytes());
+            for tx in transactions {
+                // The hash of an EIP-2718 encoded transaction is the hash of its encoding, so the
+                // transactions don't need to be decoded.
+                hasher.update(keccak256(tx));
+            }
+        }
+
+        if let Some(gas_limit) = self.gas_limit {
+            hasher.update(gas_limit.to_be_bytes());
+        }
+
+        if let Some(eip_1559_params) = self.eip_1559_params {
+            hasher.update(eip_1559_params);
+        }
+
+        if let Some(min_base_fee) = self.min_base_fee {
+            hasher.update(min_base_fee.to_be_bytes());
+        }
+
+        let mut out = hasher.finalize();
+        out[0] = version;
+        PayloadId::new(out[..8].try_into().expect("sufficient length"))
+    }
+
     /// Returns an iterator over the decoded [`OpTxEnvelope`] in this attributes.
     ///
     /// This iterator will be empty if there are no transactions in the attributes.
@@ -166,7 +256,7 @@ impl OpPayloadAttributes {
 mod test {
     use super::*;
     use alloc::vec;
-    use alloy_primitives::{Address, B256, b64};
+    use alloy_primitives::{Address, address, b64, b256, bytes};
     use alloy_rpc_types_engine::PayloadAttributes;
     use core::str::FromStr;
 
@@ -335,6 +425,86 @@ mod test {
         assert_eq!(result.unwrap_err(), EIP1559ParamError::MinBaseFeeMustBeNone);
     }
 
+    // Vector taken from op-geth, which returned `0x03d2dae446d2a86a` for these attributes.
+    #[test]
+    fn test_payload_id_parity_op_geth() {
+        let attributes = OpPayloadAttributes {
+            payload_attributes: PayloadAttributes {
+                timestamp: 1728933301,
+                prev_randao: b256!(
+                    "0x9158595abbdab2c90635087619aa7042bbebe47642dfab3c9bfb934f6b082765"
+                ),
+                suggested_fee_recipient: address!("0x4200000000000000000000000000000000000011"),
+                withdrawals: Some(vec![]),
+                parent_beacon_block_root: Some(b256!(
+                    "0x8fe0193b9bf83cb7e5a08538e494fecc23046aab9a497af3704f4afdae3250ff"
+                )),
+            },
+            transactions: Some(vec![bytes!(
+                "7ef8f8a0dc19cfa777d90980e4875d0a548a881baaa3f83f14d1bc0d3038bc329350e54194deaddeaddeaddeaddeaddeaddeaddeaddead00019442000000000000000000000000000000000000158080830f424080b8a4440a5e20000f424000000000000000000000000300000000670d6d890000000000000125000000000000000000000000000000000000000000000000000000000000000700000000000000000000000000000000000000000000000000000000000000014bf9181db6e381d4384bbf69c48b0ee0eed23c6ca26143c6d2544f9d39997a590000000000000000000000007f83d659683caf2767fd3c720981d51f5bc365bc"
+            )]),
+            no_tx_pool: None,
+            gas_limit: Some(30000000),
+            eip_1559_params: None,
+            min_base_fee: None,
+        };
+
+        let parent_hash =
+            b256!("0x3533bf30edaf9505d0810bf475cbe4e5f4b9889904b9845e83efdeab4e92eb1e");
+        assert_eq!(
+            attributes.payload_id(&parent_hash, 3),
+            PayloadId::new(b64!("03d2dae446d2a86a").0)
+        );
+    }
+
+    #[test]
+    fn test_payload_id_version_byte() {
+        let attributes = OpPayloadAttributes::default();
+        let v2 = attributes.payload_id(&B256::ZERO, 2);
+        let v3 = attributes.payload_id(&B256::ZERO, 3);
+
+        assert_eq!(v2.0[0], 2);
+        assert_eq!(v3.0[0], 3);
+        assert_eq!(v2.0[1..], v3.0[1..]);
+    }
+
+    #[test]
+    fn test_payload_id_extended_fields() {
+        let attributes = OpPayloadAttributes {
+            payload_attributes: PayloadAttributes {
+                timestamp: 0x1337,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: Address::ZERO,
+                withdrawals: Some(vec![]),
+                parent_beacon_block_root: Some(B256::ZERO),
+            },
+            transactions: Some(vec![]),
+            no_tx_pool: Some(false),
+            gas_limit: Some(30_000_000),
+            eip_1559_params: Some(b64!("0000000800000008")),
+            min_base_fee: None,
+        };
+        let parent_hash = B256::ZERO;
+        let base = attributes.payload_id(&parent_hash, 3);
+
+        // An empty forced transaction list without `no_tx_pool` is not part of the hash.
+        let no_txs =
+            OpPayloadAttributes { transactions: None, no_tx_pool: None, ..attributes.clone() };
+        assert_eq!(no_txs.payload_id(&parent_hash, 3), base);
+
+        let no_tx_pool = OpPayloadAttributes { no_tx_pool: Some(true), ..attributes.clone() };
+        assert_ne!(no_tx_pool.payload_id(&parent_hash, 3), base);
+
+        let eip_1559_params = OpPayloadAttributes { eip_1559_params: None, ..attributes.clone() };
+        assert_ne!(eip_1559_params.payload_id(&parent_hash, 3), base);
+
+        let min_base_fee = OpPayloadAttributes { min_base_fee: Some(0), ..attributes.clone() };
+        assert_ne!(min_base_fee.payload_id(&parent_hash, 3), base);
+
+        let gas_limit = OpPayloadAttributes { gas_limit: Some(30_000_001), ..attributes };
+        assert_ne!(gas_limit.payload_id(&parent_hash, 3), base);
+    }
+
     // <https://github.com/alloy-rs/op-alloy/issues/601>
     #[test]
     fn test_serde_attributes() {
@@ -0,0 +1,149 @@
+//! [`OpPayloadAttributes`] of interop replacement blocks.
+
+use super::OpPayloadAttributes;
+use alloc::vec::Vec;
+use alloy_consensus::Sealable;
+use alloy_eips::{Decodable2718, eip2718::Encodable2718};
+use alloy_primitives::B256;
+use op_alloy_consensus::{
+    BlockInfo, EIP1559ParamError, OpBlock, OpTxEnvelope, OutputRoot, TxDeposit,
+    interop::replaced_output_root,
+};
+
+/// An error building the [`OpPayloadAttributes`] of a replacement block.
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+pub enum BlockReplacementError {
+    /// The output root is not the output root of the invalidated block.
+    #[error("Output root of block {actual} does not match invalidated block {expected}")]
+    OutputRootMismatch {
+        /// The hash of the invalidated block.
+        expected: B256,
+        /// The block hash committed to by the output root.
+        actual: B256,
+    },
+    /// The invalidated block is not a child of the given parent.
+    #[error("Invalidated block is not a child of parent {0}")]
+    ParentMismatch(B256),
+    /// The extra data of the invalidated block is invalid.
+    #[error(transparent)]
+    EIP1559Params(#[from] EIP1559ParamError),
+}
+
+impl OpPayloadAttributes {
+    /// Returns the attributes of the block replacing the `invalidated` block on top of its
+    /// `parent`.
+    ///
+    /// The replacement block keeps the attributes and deposits of the invalidated block, drops all
+    /// other transactions and appends the [block replacement
+    /// deposit](TxDeposit::block_replacement) committing to the `output_root` of the invalidated
+    /// block.
+    pub fn block_replacement(
+        parent: &BlockInfo,
+        invalidated: &OpBlock,
+        output_root: &OutputRoot,
+    ) -> Result<Self, BlockReplacementError> {
+        let header = &invalidated.header;
+        if header.parent_hash != parent.hash || header.number != parent.number + 1 {
+            return Err(BlockReplacementError::ParentMismatch(parent.hash));
+        }
+        let hash = header.hash_slow();
+        if output_root.block_hash != hash {
+            return Err(BlockReplacementError::OutputRootMismatch {
+                expected: hash,
+                actual: output_root.block_hash,
+            });
+        }
+
+        let mut attributes = Self::from_block(invalidated)?;
+        attributes.transactions = Some(
+            invalidated
+                .body
+                .transactions
+                .iter()
+                .filter(|tx| tx.is_deposit())
+                .map(|tx| tx.encoded_2718().into())
+                .chain(core::iter::once(
+                    OpTxEnvelope::Deposit(TxDeposit::block_replacement(output_root).seal_slow())
+                        .encoded_2718()
+                        .into(),
+                ))
+                .collect(),
+        );
+        Ok(attributes)
+    }
+
+    /// Returns the output root of the invalidated block, if these are the attributes of a
+    /// replacement block.
+    pub fn replaced_output_root(&self) -> Option<OutputRoot> {
+        let transactions = self
+            .transactions
+            .iter()
+            .flatten()
+            .map_while(|tx| OpTxEnvelope::decode_2718(&mut tx.as_ref()).ok())
+            .collect::<Vec<_>>();
+        replaced_output_root(&transactions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_consensus::{BlockBody, Header, Signed, TxEip1559};
+    use alloy_primitives::Signature;
+
+    fn invalidated_block(parent: &BlockInfo) -> OpBlock {
+        let deposit = OpTxEnvelope::Deposit(TxDeposit::default().seal_slow());
+        let user_tx: OpTxEnvelope =
+            Signed::new_unchecked(TxEip1559::default(), Signature::test_signature(), B256::ZERO)
+                .into();
+        OpBlock {
+            header: Header {
+                parent_hash: parent.hash,
+                number: parent.number + 1,
+                timestamp: parent.timestamp + 2,
+                gas_limit: 30_000_000,
+                ..Default::default()
+            },
+            body: BlockBody { transactions: vec![deposit, user_tx], ..Default::default() },
+        }
+    }
+
+    #[test]
+    fn test_block_replacement() {
+        let parent = BlockInfo::new(B256::repeat_byte(1), 10, B256::ZERO, 100);
+        let block = invalidated_block(&parent);
+        let output_root = OutputRoot::new(B256::ZERO, B256::ZERO, block.header.hash_slow());
+
+        let attributes =
+            OpPayloadAttributes::block_replacement(&parent, &block, &output_root).unwrap();
+        assert_eq!(attributes.payload_attributes.timestamp, 102);
+        assert_eq!(attributes.gas_limit, Some(30_000_000));
+        assert_eq!(attributes.no_tx_pool, Some(true));
+        assert_eq!(attributes.transactions.as_ref().unwrap().len(), 2);
+        assert_eq!(attributes.replaced_output_root(), Some(output_root));
+
+        assert_eq!(OpPayloadAttributes::from_block(&block).unwrap().replaced_output_root(), None);
+    }
+
+    #[test]
+    fn test_block_replacement_mismatch() {
+        let parent = BlockInfo::new(B256::repeat_byte(1), 10, B256::ZERO, 100);
+        let block = invalidated_block(&parent);
+        let output_root = OutputRoot::default();
+
+        assert_eq!(
+            OpPayloadAttributes::block_replacement(&parent, &block, &output_root),
+            Err(BlockReplacementError::OutputRootMismatch {
+                expected: block.header.hash_slow(),
+                actual: B256::ZERO
+            })
+        );
+
+        let other = BlockInfo { number: 11, ..parent };
+        assert_eq!(
+            OpPayloadAttributes::block_replacement(&other, &block, &output_root),
+            Err(BlockReplacementError::ParentMismatch(parent.hash))
+        );
+    }
+}
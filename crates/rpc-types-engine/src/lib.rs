@@ -12,7 +12,10 @@ extern crate alloc;
 pub use alloy_rpc_types_engine::ForkchoiceUpdateVersion;
 
 mod attributes;
-pub use attributes::OpPayloadAttributes;
+pub use attributes::{
+    AttributesMismatch, BlockReplacementError, OpAttributesBuilderError, OpPayloadAttributes,
+    OpPayloadAttributesBuilder,
+};
 
 mod envelope;
 pub use envelope::{
@@ -25,7 +28,7 @@ pub use sidecar::OpExecutionPayloadSidecar;
 
 pub mod payload;
 pub use payload::{
-    OpExecutionPayload,
+    OpExecutionPayload, OpPayloadViolation,
     error::OpPayloadError,
     v3::OpExecutionPayloadEnvelopeV3,
     v4::{OpExecutionPayloadEnvelopeV4, OpExecutionPayloadV4},
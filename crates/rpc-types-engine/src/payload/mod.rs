@@ -4,6 +4,9 @@ pub mod error;
 pub mod v3;
 pub mod v4;
 
+mod validation;
+pub use validation::OpPayloadViolation;
+
 use crate::{OpExecutionPayloadSidecar, OpExecutionPayloadV4};
 use alloc::vec::Vec;
 use alloy_consensus::{Block, BlockHeader, HeaderInfo, Transaction};
@@ -0,0 +1,236 @@
+//! Validation of OP Stack specific rules on [`OpExecutionPayload`]s.
+
+use super::OpExecutionPayload;
+use crate::OpExecutionPayloadSidecar;
+use alloc::vec::Vec;
+use alloy_eips::{Decodable2718, eip2718::EIP4844_TX_TYPE_ID, eip7685::EMPTY_REQUESTS_HASH};
+use alloy_primitives::TxKind;
+use op_alloy_consensus::{
+    DEPOSIT_TX_TYPE_ID, L1_BLOCK_ADDRESS, L1_INFO_DEPOSITOR_ADDRESS, OpHardforkSchedule, TxDeposit,
+};
+
+/// A violation of the OP Stack rules by an [`OpExecutionPayload`].
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum OpPayloadViolation {
+    /// The payload has no transactions, so it lacks the L1 info deposit.
+    #[error("missing L1 info deposit")]
+    MissingL1InfoDeposit,
+    /// The first transaction is not the L1 info deposit.
+    #[error("transaction 0 is not the L1 info deposit")]
+    InvalidL1InfoDeposit,
+    /// The transaction at the given index is empty.
+    #[error("transaction {0} is empty")]
+    EmptyTransaction(usize),
+    /// The deposit at the given index follows a non-deposit transaction.
+    #[error("deposit transaction {0} after non-deposit transaction")]
+    DepositAfterUserTransaction(usize),
+    /// The transaction at the given index is an EIP-4844 blob transaction.
+    #[error("transaction {0} is a blob transaction")]
+    BlobTransaction(usize),
+    /// The payload contains L1 withdrawals.
+    #[error("non-empty L1 withdrawals")]
+    NonEmptyL1Withdrawals,
+    /// The sidecar contains blob versioned hashes.
+    #[error("non-empty blob versioned hashes")]
+    NonEmptyBlobVersionedHashes,
+    /// The sidecar contains execution layer requests.
+    #[error("non-empty EL requests")]
+    NonEmptyELRequests,
+    /// The payload lacks the withdrawals root after Isthmus.
+    #[error("missing withdrawals root after Isthmus")]
+    MissingWithdrawalsRoot,
+    /// The payload has a withdrawals root before Isthmus.
+    #[error("withdrawals root before Isthmus")]
+    UnexpectedWithdrawalsRoot,
+}
+
+impl OpExecutionPayload {
+    /// Checks the OP Stack specific rules that are not enforced when converting the payload into
+    /// a block, and returns all violations.
+    ///
+    /// The transactions are only inspected as far as necessary: the L1 info deposit is decoded,
+    /// all other transactions are only checked for their EIP-2718 type. This makes the check
+    /// cheap enough to run on gossiped payloads before they are sent to the engine.
+    ///
+    /// The rules are:
+    /// - The first transaction is the L1 info deposit.
+    /// - All deposits come before any other transaction.
+    /// - There are no EIP-4844 blob transactions.
+    /// - There are no L1 withdrawals, blob versioned hashes or EL requests.
+    /// - The [`V4`](Self::V4) withdrawals root is present if and only if Isthmus is active.
+    pub fn op_violations(
+        &self,
+        sidecar: &OpExecutionPayloadSidecar,
+        hardforks: &OpHardforkSchedule,
+    ) -> Vec<OpPayloadViolation> {
+        let mut violations = Vec::new();
+        let transactions = self.transactions();
+
+        match transactions.first() {
+            None => violations.push(OpPayloadViolation::MissingL1InfoDeposit),
+            Some(tx) if !is_l1_info_deposit(tx) => {
+                violations.push(OpPayloadViolation::InvalidL1InfoDeposit)
+            }
+            Some(_) => {}
+        }
+
+        let mut seen_user_transaction = false;
+        for (index, tx) in transactions.iter().enumerate() {
+            match tx.first().copied() {
+                None => violations.push(OpPayloadViolation::EmptyTransaction(index)),
+                Some(DEPOSIT_TX_TYPE_ID) if seen_user_transaction => {
+                    violations.push(OpPayloadViolation::DepositAfterUserTransaction(index))
+                }
+                Some(DEPOSIT_TX_TYPE_ID) => {}
+                Some(ty) => {
+                    seen_user_transaction = true;
+                    if ty == EIP4844_TX_TYPE_ID {
+                        violations.push(OpPayloadViolation::BlobTransaction(index));
+                    }
+                }
+            }
+        }
+
+        if self.as_v2().is_some_and(|payload| !payload.withdrawals.is_empty()) {
+            violations.push(OpPayloadViolation::NonEmptyL1Withdrawals);
+        }
+        if sidecar.versioned_hashes().is_some_and(|hashes| !hashes.is_empty()) {
+            violations.push(OpPayloadViolation::NonEmptyBlobVersionedHashes);
+        }
+        if sidecar.requests_hash().is_some_and(|hash| hash != EMPTY_REQUESTS_HASH) {
+            violations.push(OpPayloadViolation::NonEmptyELRequests);
+        }
+
+        match (hardforks.is_isthmus_active(self.timestamp()), self.as_v4().is_some()) {
+            (true, false) => violations.push(OpPayloadViolation::MissingWithdrawalsRoot),
+            (false, true) => violations.push(OpPayloadViolation::UnexpectedWithdrawalsRoot),
+            _ => {}
+        }
+
+        violations
+    }
+}
+
+/// Returns true if the given EIP-2718 encoded transaction is an L1 info deposit.
+fn is_l1_info_deposit(tx: &[u8]) -> bool {
+    TxDeposit::decode_2718_exact(tx).is_ok_and(|deposit| {
+        deposit.from == L1_INFO_DEPOSITOR_ADDRESS && deposit.to == TxKind::Call(L1_BLOCK_ADDRESS)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OpExecutionPayloadV4;
+    use alloc::vec;
+    use alloy_eips::{Encodable2718, eip4895::Withdrawal};
+    use alloy_primitives::{Address, B256, Bloom, Bytes, U256, bytes};
+    use alloy_rpc_types_engine::{
+        CancunPayloadFields, ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3,
+        PraguePayloadFields,
+    };
+
+    fn l1_info_deposit() -> Bytes {
+        TxDeposit {
+            from: L1_INFO_DEPOSITOR_ADDRESS,
+            to: TxKind::Call(L1_BLOCK_ADDRESS),
+            gas_limit: 1_000_000,
+            input: bytes!("440a5e20"),
+            ..Default::default()
+        }
+        .encoded_2718()
+        .into()
+    }
+
+    fn payload_v3(transactions: Vec<Bytes>) -> ExecutionPayloadV3 {
+        ExecutionPayloadV3 {
+            payload_inner: ExecutionPayloadV2 {
+                payload_inner: ExecutionPayloadV1 {
+                    parent_hash: B256::ZERO,
+                    fee_recipient: Address::ZERO,
+                    state_root: B256::ZERO,
+                    receipts_root: B256::ZERO,
+                    logs_bloom: Bloom::ZERO,
+                    prev_randao: B256::ZERO,
+                    block_number: 1,
+                    gas_limit: 30_000_000,
+                    gas_used: 0,
+                    timestamp: 10,
+                    extra_data: Bytes::new(),
+                    base_fee_per_gas: U256::ZERO,
+                    block_hash: B256::ZERO,
+                    transactions,
+                },
+                withdrawals: vec![],
+            },
+            blob_gas_used: 0,
+            excess_blob_gas: 0,
+        }
+    }
+
+    fn sidecar() -> OpExecutionPayloadSidecar {
+        OpExecutionPayloadSidecar::v3(CancunPayloadFields::new(B256::ZERO, vec![]))
+    }
+
+    #[test]
+    fn test_valid_payload() {
+        let payload = OpExecutionPayload::V3(payload_v3(vec![
+            l1_info_deposit(),
+            l1_info_deposit(),
+            bytes!("02f8"),
+        ]));
+        assert_eq!(payload.op_violations(&sidecar(), &OpHardforkSchedule::default()), vec![]);
+
+        let hardforks = OpHardforkSchedule { isthmus_time: Some(0), ..Default::default() };
+        let payload = OpExecutionPayload::V4(OpExecutionPayloadV4::from_v3_with_withdrawals_root(
+            payload_v3(vec![l1_info_deposit()]),
+            B256::ZERO,
+        ));
+        let sidecar = OpExecutionPayloadSidecar::v4(
+            CancunPayloadFields::new(B256::ZERO, vec![]),
+            PraguePayloadFields::new(EMPTY_REQUESTS_HASH),
+        );
+        assert_eq!(payload.op_violations(&sidecar, &hardforks), vec![]);
+    }
+
+    #[test]
+    fn test_all_violations() {
+        let mut payload =
+            payload_v3(vec![bytes!("02f8"), Bytes::new(), l1_info_deposit(), bytes!("03f8")]);
+        payload.payload_inner.withdrawals = vec![Withdrawal::default()];
+        let sidecar = OpExecutionPayloadSidecar::v4(
+            CancunPayloadFields::new(B256::ZERO, vec![B256::ZERO]),
+            PraguePayloadFields::new(B256::ZERO),
+        );
+        let hardforks = OpHardforkSchedule { isthmus_time: Some(0), ..Default::default() };
+
+        assert_eq!(
+            OpExecutionPayload::V3(payload).op_violations(&sidecar, &hardforks),
+            vec![
+                OpPayloadViolation::InvalidL1InfoDeposit,
+                OpPayloadViolation::EmptyTransaction(1),
+                OpPayloadViolation::DepositAfterUserTransaction(2),
+                OpPayloadViolation::BlobTransaction(3),
+                OpPayloadViolation::NonEmptyL1Withdrawals,
+                OpPayloadViolation::NonEmptyBlobVersionedHashes,
+                OpPayloadViolation::NonEmptyELRequests,
+                OpPayloadViolation::MissingWithdrawalsRoot,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_withdrawals_root_before_isthmus() {
+        let payload = OpExecutionPayload::V4(OpExecutionPayloadV4::from_v3_with_withdrawals_root(
+            payload_v3(vec![]),
+            B256::ZERO,
+        ));
+        assert_eq!(
+            payload.op_violations(&sidecar(), &OpHardforkSchedule::default()),
+            vec![
+                OpPayloadViolation::MissingL1InfoDeposit,
+                OpPayloadViolation::UnexpectedWithdrawalsRoot
+            ]
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! Validation of [`TransactionConditional`]s submitted with `eth_sendRawTransactionConditional`.
+//!
+//! A conditional transaction is only included while the block number and timestamp are in range
+//! and the `knownAccounts` match the state. The sequencer checks the conditional against its own
+//! state; [`TransactionConditionalExt::validate`] checks it against a header and the
+//! `eth_getProof` responses of the known accounts at that header, verifying the proofs against
+//! the state root.
+
+use alloy_consensus::{BlockHeader, TrieAccount};
+use alloy_primitives::{Address, B256, keccak256};
+use alloy_rpc_types_eth::{
+    EIP1186AccountProofResponse,
+    erc4337::{AccountStorage, TransactionConditional},
+};
+use alloy_trie::{EMPTY_ROOT_HASH, KECCAK_EMPTY, Nibbles, proof::verify_proof};
+
+/// The maximum [cost](TransactionConditional::cost) of a conditional accepted by the sequencer.
+pub const MAX_TRANSACTION_CONDITIONAL_COST: u64 = 1000;
+
+/// Extension trait to validate a [`TransactionConditional`] against proven state.
+pub trait TransactionConditionalExt {
+    /// Validates the conditional against the given header and the `eth_getProof` responses of
+    /// the known accounts at that header.
+    ///
+    /// The account proofs are verified against the state root of the header, and the storage
+    /// proofs of the known slots against the storage root of their account.
+    fn validate<H: BlockHeader>(
+        &self,
+        header: &H,
+        proofs: &[EIP1186AccountProofResponse],
+    ) -> Result<(), TransactionConditionalError>;
+}
+
+impl TransactionConditionalExt for TransactionConditional {
+    fn validate<H: BlockHeader>(
+        &self,
+        header: &H,
+        proofs: &[EIP1186AccountProofResponse],
+    ) -> Result<(), TransactionConditionalError> {
+        let cost = self.cost();
+        if cost > MAX_TRANSACTION_CONDITIONAL_COST {
+            return Err(TransactionConditionalError::CostTooHigh(cost));
+        }
+        if !self.matches_block_number(header.number()) {
+            return Err(TransactionConditionalError::BlockNumberOutOfRange(header.number()));
+        }
+        if !self.matches_timestamp(header.timestamp()) {
+            return Err(TransactionConditionalError::TimestampOutOfRange(header.timestamp()));
+        }
+
+        for (&address, storage) in &self.known_accounts {
+            let proof = proofs
+                .iter()
+                .find(|proof| proof.address == address)
+                .ok_or(TransactionConditionalError::MissingAccountProof(address))?;
+            verify_account_proof(header.state_root(), proof)?;
+
+            match storage {
+                AccountStorage::RootHash(root) => {
+                    if proof.storage_hash != *root {
+                        return Err(TransactionConditionalError::StorageRootMismatch {
+                            address,
+                            expected: *root,
+                            actual: proof.storage_hash,
+                        });
+                    }
+                }
+                AccountStorage::Slots(slots) => {
+                    for (slot, value) in slots {
+                        let slot = B256::from(*slot);
+                        let storage_proof = proof
+                            .storage_proof
+                            .iter()
+                            .find(|storage_proof| storage_proof.key.as_b256() == slot)
+                            .ok_or(TransactionConditionalError::MissingStorageProof {
+                                address,
+                                slot,
+                            })?;
+
+                        let expected = (!storage_proof.value.is_zero())
+                            .then(|| alloy_rlp::encode(storage_proof.value));
+                        verify_proof(
+                            proof.storage_hash,
+                            Nibbles::unpack(keccak256(slot)),
+                            expected,
+                            &storage_proof.proof,
+                        )
+                        .map_err(|_| {
+                            TransactionConditionalError::InvalidStorageProof { address, slot }
+                        })?;
+
+                        let actual = B256::from(storage_proof.value);
+                        if actual != *value {
+                            return Err(TransactionConditionalError::StorageSlotMismatch {
+                                address,
+                                slot,
+                                expected: *value,
+                                actual,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Verifies the account proof of an `eth_getProof` response against the given state root.
+///
+/// Empty accounts are proven to be absent from the state trie.
+fn verify_account_proof(
+    state_root: B256,
+    proof: &EIP1186AccountProofResponse,
+) -> Result<(), TransactionConditionalError> {
+    let is_empty = proof.nonce == 0
+        && proof.balance.is_zero()
+        && proof.code_hash == KECCAK_EMPTY
+        && proof.storage_hash == EMPTY_ROOT_HASH;
+    let expected = (!is_empty).then(|| {
+        alloy_rlp::encode(TrieAccount {
+            nonce: proof.nonce,
+            balance: proof.balance,
+            storage_root: proof.storage_hash,
+            code_hash: proof.code_hash,
+        })
+    });
+
+    verify_proof(
+        state_root,
+        Nibbles::unpack(keccak256(proof.address)),
+        expected,
+        &proof.account_proof,
+    )
+    .map_err(|_| TransactionConditionalError::InvalidAccountProof(proof.address))
+}
+
+/// An error validating a [`TransactionConditional`].
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionConditionalError {
+    /// The cost of the conditional exceeds [`MAX_TRANSACTION_CONDITIONAL_COST`].
+    #[error("Conditional cost {0} exceeds the maximum of {MAX_TRANSACTION_CONDITIONAL_COST}")]
+    CostTooHigh(u64),
+    /// The block number is outside of the allowed range.
+    #[error("Block number {0} is out of range")]
+    BlockNumberOutOfRange(u64),
+    /// The timestamp is outside of the allowed range.
+    #[error("Timestamp {0} is out of range")]
+    TimestampOutOfRange(u64),
+    /// There is no proof for a known account.
+    #[error("Missing account proof for {0}")]
+    MissingAccountProof(Address),
+    /// The account proof does not match the state root.
+    #[error("Invalid account proof for {0}")]
+    InvalidAccountProof(Address),
+    /// The storage root of a known account does not match.
+    #[error("Storage root mismatch for {address}: expected {expected}, got {actual}")]
+    StorageRootMismatch {
+        /// The account address.
+        address: Address,
+        /// The expected storage root.
+        expected: B256,
+        /// The proven storage root.
+        actual: B256,
+    },
+    /// There is no storage proof for a known slot.
+    #[error("Missing storage proof for {address} slot {slot}")]
+    MissingStorageProof {
+        /// The account address.
+        address: Address,
+        /// The storage slot.
+        slot: B256,
+    },
+    /// The storage proof does not match the storage root of the account.
+    #[error("Invalid storage proof for {address} slot {slot}")]
+    InvalidStorageProof {
+        /// The account address.
+        address: Address,
+        /// The storage slot.
+        slot: B256,
+    },
+    /// The value of a known slot does not match.
+    #[error("Storage slot mismatch for {address} slot {slot}: expected {expected}, got {actual}")]
+    StorageSlotMismatch {
+        /// The account address.
+        address: Address,
+        /// The storage slot.
+        slot: B256,
+        /// The expected value.
+        expected: B256,
+        /// The proven value.
+        actual: B256,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{vec, vec::Vec};
+    use alloy_consensus::Header;
+    use alloy_primitives::{Bytes, U256, address, map::HashMap};
+    use alloy_rpc_types_eth::EIP1186StorageProof;
+    use alloy_trie::{HashBuilder, proof::ProofRetainer};
+
+    const ACCOUNT: Address = address!("0x4200000000000000000000000000000000000015");
+    const OTHER: Address = address!("0x4200000000000000000000000000000000000016");
+
+    /// Builds a trie of the given leaves, returning its root and the proof of `target`.
+    fn trie(mut leaves: Vec<(B256, Vec<u8>)>, target: B256) -> (B256, Vec<Bytes>) {
+        leaves.sort_by_key(|(key, _)| *key);
+        let mut builder = HashBuilder::default()
+            .with_proof_retainer(ProofRetainer::new(vec![Nibbles::unpack(target)]));
+        for (key, value) in &leaves {
+            builder.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = builder.root();
+        let proof = builder.take_proof_nodes().into_nodes_sorted();
+        (root, proof.into_iter().map(|(_, node)| node).collect())
+    }
+
+    /// Returns a header and the proof of [`ACCOUNT`], whose slot 1 holds 42.
+    fn proven_state() -> (Header, EIP1186AccountProofResponse) {
+        let slot = B256::with_last_byte(1);
+        let (storage_hash, storage_proof) = trie(
+            vec![
+                (keccak256(slot), alloy_rlp::encode(U256::from(42))),
+                (keccak256(B256::with_last_byte(2)), alloy_rlp::encode(U256::from(7))),
+            ],
+            keccak256(slot),
+        );
+        let account = TrieAccount {
+            nonce: 1,
+            balance: U256::from(100),
+            storage_root: storage_hash,
+            code_hash: KECCAK_EMPTY,
+        };
+        let other = TrieAccount { nonce: 5, ..Default::default() };
+        let (state_root, account_proof) = trie(
+            vec![
+                (keccak256(ACCOUNT), alloy_rlp::encode(account)),
+                (keccak256(OTHER), alloy_rlp::encode(other)),
+            ],
+            keccak256(ACCOUNT),
+        );
+
+        let header = Header { number: 100, timestamp: 1_000, state_root, ..Default::default() };
+        let proof = EIP1186AccountProofResponse {
+            address: ACCOUNT,
+            balance: account.balance,
+            code_hash: account.code_hash,
+            nonce: account.nonce,
+            storage_hash,
+            account_proof,
+            storage_proof: vec![EIP1186StorageProof {
+                key: slot.into(),
+                value: U256::from(42),
+                proof: storage_proof,
+            }],
+        };
+        (header, proof)
+    }
+
+    fn slots(slot: u64, value: u64) -> AccountStorage {
+        AccountStorage::Slots(HashMap::from_iter([(
+            U256::from(slot),
+            B256::from(U256::from(value)),
+        )]))
+    }
+
+    #[test]
+    fn test_validate_known_accounts() {
+        let (header, proof) = proven_state();
+        let proofs = [proof];
+
+        let mut conditional = TransactionConditional::default();
+        conditional.known_accounts.insert(ACCOUNT, slots(1, 42));
+        assert_eq!(conditional.validate(&header, &proofs), Ok(()));
+
+        conditional
+            .known_accounts
+            .insert(ACCOUNT, AccountStorage::RootHash(proofs[0].storage_hash));
+        assert_eq!(conditional.validate(&header, &proofs), Ok(()));
+
+        conditional.known_accounts.insert(ACCOUNT, slots(1, 43));
+        assert_eq!(
+            conditional.validate(&header, &proofs),
+            Err(TransactionConditionalError::StorageSlotMismatch {
+                address: ACCOUNT,
+                slot: B256::with_last_byte(1),
+                expected: B256::with_last_byte(43),
+                actual: B256::with_last_byte(42),
+            })
+        );
+
+        conditional.known_accounts.insert(ACCOUNT, slots(2, 7));
+        assert_eq!(
+            conditional.validate(&header, &proofs),
+            Err(TransactionConditionalError::MissingStorageProof {
+                address: ACCOUNT,
+                slot: B256::with_last_byte(2),
+            })
+        );
+
+        conditional.known_accounts.insert(ACCOUNT, AccountStorage::RootHash(B256::ZERO));
+        assert!(matches!(
+            conditional.validate(&header, &proofs),
+            Err(TransactionConditionalError::StorageRootMismatch { .. })
+        ));
+
+        conditional.known_accounts.clear();
+        conditional.known_accounts.insert(OTHER, AccountStorage::RootHash(EMPTY_ROOT_HASH));
+        assert_eq!(
+            conditional.validate(&header, &proofs),
+            Err(TransactionConditionalError::MissingAccountProof(OTHER))
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_forged_proofs() {
+        let (header, proof) = proven_state();
+        let mut conditional = TransactionConditional::default();
+        conditional.known_accounts.insert(ACCOUNT, slots(1, 43));
+
+        let mut forged = proof.clone();
+        forged.storage_proof[0].value = U256::from(43);
+        assert_eq!(
+            conditional.validate(&header, &[forged]),
+            Err(TransactionConditionalError::InvalidStorageProof {
+                address: ACCOUNT,
+                slot: B256::with_last_byte(1),
+            })
+        );
+
+        let mut forged = proof;
+        forged.balance = U256::from(1_000_000);
+        assert_eq!(
+            conditional.validate(&header, &[forged]),
+            Err(TransactionConditionalError::InvalidAccountProof(ACCOUNT))
+        );
+    }
+
+    #[test]
+    fn test_validate_block_attributes() {
+        let (header, proof) = proven_state();
+        let proofs = [proof];
+        let conditional = TransactionConditional {
+            block_number_min: Some(90),
+            block_number_max: Some(100),
+            timestamp_max: Some(999),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            conditional.validate(&header, &proofs),
+            Err(TransactionConditionalError::TimestampOutOfRange(1_000))
+        );
+        assert_eq!(
+            TransactionConditional { block_number_min: Some(101), ..Default::default() }
+                .validate(&header, &proofs),
+            Err(TransactionConditionalError::BlockNumberOutOfRange(100))
+        );
+
+        let mut conditional = TransactionConditional::default();
+        for i in 0..501u64 {
+            conditional
+                .known_accounts
+                .insert(Address::left_padding_from(&i.to_be_bytes()), slots(i, 0));
+        }
+        assert_eq!(
+            conditional.validate(&header, &proofs),
+            Err(TransactionConditionalError::CostTooHigh(1002))
+        );
+    }
+
+    #[test]
+    fn test_serde_transaction_conditional() {
+        let json = r#"{"knownAccounts":{"0x4200000000000000000000000000000000000015":"0x0101010101010101010101010101010101010101010101010101010101010101"},"blockNumberMin":"0x64","timestampMax":"0x3e8"}"#;
+        let conditional: TransactionConditional = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            conditional.known_accounts[&ACCOUNT],
+            AccountStorage::RootHash(B256::repeat_byte(1))
+        );
+        assert_eq!(conditional.block_number_min, Some(100));
+        assert_eq!(conditional.timestamp_max, Some(1_000));
+        assert_eq!(serde_json::to_string(&conditional).unwrap(), json);
+    }
+}
@@ -0,0 +1,86 @@
+//! Types for the op-conductor `conductor` RPC namespace.
+//!
+//! Reference: <https://docs.optimism.io/operators/chain-operators/tools/op-conductor>
+
+use alloc::{string::String, vec::Vec};
+use serde::{Deserialize, Serialize};
+
+/// The suffrage of a server in the raft cluster, encoded as its raft discriminant.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "u8", try_from = "u8")]
+#[repr(u8)]
+pub enum ServerSuffrage {
+    /// The server votes in leader elections and can become the leader.
+    #[default]
+    Voter = 0,
+    /// The server replicates the log but does not vote.
+    Nonvoter = 1,
+}
+
+impl From<ServerSuffrage> for u8 {
+    fn from(value: ServerSuffrage) -> Self {
+        value as Self
+    }
+}
+
+impl TryFrom<u8> for ServerSuffrage {
+    type Error = UnknownServerSuffrage;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Voter),
+            1 => Ok(Self::Nonvoter),
+            _ => Err(UnknownServerSuffrage(value)),
+        }
+    }
+}
+
+/// An unknown [`ServerSuffrage`] discriminant.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+#[error("Unknown server suffrage: {0}")]
+pub struct UnknownServerSuffrage(pub u8);
+
+/// A server of the raft cluster, returned by `conductor_leaderWithID`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ServerInfo {
+    /// The server id.
+    pub id: String,
+    /// The raft consensus address of the server.
+    pub addr: String,
+    /// The suffrage of the server.
+    pub suffrage: ServerSuffrage,
+}
+
+/// The membership of the raft cluster, returned by `conductor_clusterMembership`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ClusterMembership {
+    /// The servers of the cluster.
+    pub servers: Vec<ServerInfo>,
+    /// The version of the membership, to be passed to membership changes.
+    pub version: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_serde_cluster_membership() {
+        let json = r#"{"servers":[{"id":"sequencer-0","addr":"10.0.0.1:50050","suffrage":0},{"id":"sequencer-1","addr":"10.0.0.2:50050","suffrage":1}],"version":7}"#;
+        let membership: ClusterMembership = serde_json::from_str(json).unwrap();
+
+        assert_eq!(membership.version, 7);
+        assert_eq!(
+            membership.servers[0],
+            ServerInfo {
+                id: "sequencer-0".to_string(),
+                addr: "10.0.0.1:50050".to_string(),
+                suffrage: ServerSuffrage::Voter,
+            }
+        );
+        assert_eq!(membership.servers[1].suffrage, ServerSuffrage::Nonvoter);
+        assert_eq!(serde_json::to_string(&membership).unwrap(), json);
+        assert!(serde_json::from_str::<ServerSuffrage>("2").is_err());
+    }
+}
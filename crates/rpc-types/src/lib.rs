@@ -15,8 +15,37 @@ pub use genesis::{OpBaseFeeInfo, OpChainInfo, OpGenesisInfo};
 mod receipt;
 pub use receipt::{L1BlockInfo, OpTransactionReceipt, OpTransactionReceiptFields};
 
+mod receipt_builder;
+pub use receipt_builder::{OpReceiptBuilder, OpReceiptBuilderError};
+
 mod transaction;
 pub use transaction::{OpTransactionFields, OpTransactionRequest, Transaction};
 
+pub mod conductor;
+pub use conductor::{ClusterMembership, ServerInfo, ServerSuffrage, UnknownServerSuffrage};
+
+mod conditional;
+pub use alloy_rpc_types_eth::erc4337::{AccountStorage, TransactionConditional};
+pub use conditional::{
+    MAX_TRANSACTION_CONDITIONAL_COST, TransactionConditionalError, TransactionConditionalExt,
+};
+
 pub mod error;
 pub use error::SuperchainDAError;
+
+pub mod node;
+pub use node::{
+    OutputResponse, RollupConfig, RollupGenesis, SafeHeadResponse, SyncStatus, SystemConfig,
+};
+
+pub mod p2p;
+pub use p2p::{
+    BannedSubnet, Connectedness, Direction, GossipScores, P2pTypeError, PeerDump, PeerInfo,
+    PeerScores, PeerStats, ReqRespScores, TopicScores,
+};
+
+pub mod supervisor;
+pub use supervisor::{
+    BlockSeal, ChainRootInfo, DerivedIdPair, ExecutingDescriptor, SuperRootOutput,
+    SupervisorChainSyncStatus, SupervisorSyncStatus,
+};
@@ -0,0 +1,255 @@
+//! Types for the rollup node `optimism` RPC namespace.
+//!
+//! Reference: <https://docs.optimism.io/operators/node-operators/json-rpc>
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{Address, B64, B256, ChainId};
+use op_alloy_consensus::{BlockInfo, L2BlockInfo, OpHardforkSchedule};
+use serde::{Deserialize, Serialize};
+
+/// The response of `optimism_outputAtBlock`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputResponse {
+    /// The version of the output root.
+    pub version: B256,
+    /// The output root of the block.
+    pub output_root: B256,
+    /// The block the output root commits to.
+    pub block_ref: L2BlockInfo,
+    /// The storage root of the `L2ToL1MessagePasser` contract.
+    pub withdrawal_storage_root: B256,
+    /// The state root of the block.
+    pub state_root: B256,
+    /// The sync status of the node at the time of the request.
+    pub sync_status: SyncStatus,
+}
+
+/// The sync status of a rollup node, returned by `optimism_syncStatus`.
+///
+/// The field names match the ones used by op-node.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SyncStatus {
+    /// The L1 block the derivation pipeline is currently processing.
+    pub current_l1: BlockInfo,
+    /// The L1 block that was finalized when the pipeline last derived finalized L2 blocks.
+    pub current_l1_finalized: BlockInfo,
+    /// The head of the L1 chain.
+    pub head_l1: BlockInfo,
+    /// The safe L1 block.
+    pub safe_l1: BlockInfo,
+    /// The finalized L1 block.
+    pub finalized_l1: BlockInfo,
+    /// The unsafe L2 head.
+    pub unsafe_l2: L2BlockInfo,
+    /// The safe L2 head.
+    pub safe_l2: L2BlockInfo,
+    /// The finalized L2 head.
+    pub finalized_l2: L2BlockInfo,
+    /// The pending-safe L2 head, derived from a not yet complete span batch.
+    pub pending_safe_l2: L2BlockInfo,
+    /// The cross-unsafe L2 head. Only reported by interop-aware nodes.
+    #[serde(default)]
+    pub cross_unsafe_l2: L2BlockInfo,
+    /// The local-safe L2 head. Only reported by interop-aware nodes.
+    #[serde(default)]
+    pub local_safe_l2: L2BlockInfo,
+}
+
+/// The response of `optimism_safeHeadAtL1Block`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeHeadResponse {
+    /// The L1 block at which the safe head was recorded.
+    pub l1_block: BlockNumHash,
+    /// The safe L2 head derived up to `l1_block`.
+    pub safe_head: BlockNumHash,
+}
+
+/// The L1 system config at the rollup genesis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemConfig {
+    /// The address of the batcher.
+    #[serde(rename = "batcherAddr")]
+    pub batcher_address: Address,
+    /// The pre-Ecotone L1 fee overhead.
+    pub overhead: B256,
+    /// The L1 fee scalar, versioned since Ecotone.
+    pub scalar: B256,
+    /// The L2 block gas limit.
+    pub gas_limit: u64,
+    /// The Holocene EIP-1559 parameters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eip1559_params: Option<B64>,
+    /// The Isthmus operator fee parameters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator_fee_params: Option<B256>,
+}
+
+/// The genesis anchor of a rollup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RollupGenesis {
+    /// The L1 block the rollup starts deriving from.
+    pub l1: BlockNumHash,
+    /// The L2 genesis block.
+    pub l2: BlockNumHash,
+    /// The timestamp of the L2 genesis block.
+    pub l2_time: u64,
+    /// The L1 system config at genesis.
+    pub system_config: SystemConfig,
+}
+
+/// The rollup config of a rollup node, returned by `optimism_rollupConfig`.
+///
+/// The field names match the ones used by op-node. Fields this type does not know about are
+/// ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RollupConfig {
+    /// The genesis anchor of the rollup.
+    pub genesis: RollupGenesis,
+    /// The L2 block time, in seconds.
+    pub block_time: u64,
+    /// The maximum number of seconds an L2 block timestamp may be ahead of its L1 origin, before
+    /// Fjord.
+    pub max_sequencer_drift: u64,
+    /// The number of L1 blocks in which a sequencing window is closed.
+    pub seq_window_size: u64,
+    /// The number of L1 blocks after which a channel times out.
+    pub channel_timeout: u64,
+    /// The number of L1 blocks after which a channel times out, since Granite.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub granite_channel_timeout: Option<u64>,
+    /// The chain id of L1.
+    pub l1_chain_id: ChainId,
+    /// The chain id of L2.
+    pub l2_chain_id: ChainId,
+    /// The activation timestamps of the hardforks.
+    #[serde(flatten)]
+    pub hardforks: OpHardforkSchedule,
+    /// The address batches are sent to.
+    pub batch_inbox_address: Address,
+    /// The address of the L1 deposit contract.
+    pub deposit_contract_address: Address,
+    /// The address of the L1 `SystemConfig` contract.
+    pub l1_system_config_address: Address,
+    /// The address of the L1 `ProtocolVersions` contract, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol_versions_address: Option<Address>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256};
+
+    #[test]
+    fn test_serde_sync_status() {
+        let json = r#"{"current_l1":{"hash":"0x0101010101010101010101010101010101010101010101010101010101010101","number":100,"parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","timestamp":1200},"current_l1_finalized":{"hash":"0x0000000000000000000000000000000000000000000000000000000000000000","number":0,"parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","timestamp":0},"head_l1":{"hash":"0x0101010101010101010101010101010101010101010101010101010101010101","number":100,"parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","timestamp":1200},"safe_l1":{"hash":"0x0000000000000000000000000000000000000000000000000000000000000000","number":0,"parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","timestamp":0},"finalized_l1":{"hash":"0x0000000000000000000000000000000000000000000000000000000000000000","number":0,"parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","timestamp":0},"unsafe_l2":{"hash":"0x0202020202020202020202020202020202020202020202020202020202020202","number":500,"parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","timestamp":1202,"l1origin":{"hash":"0x0101010101010101010101010101010101010101010101010101010101010101","number":100},"sequenceNumber":1},"safe_l2":{"hash":"0x0000000000000000000000000000000000000000000000000000000000000000","number":0,"parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","timestamp":0,"l1origin":{"hash":"0x0000000000000000000000000000000000000000000000000000000000000000","number":0},"sequenceNumber":0},"finalized_l2":{"hash":"0x0000000000000000000000000000000000000000000000000000000000000000","number":0,"parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","timestamp":0,"l1origin":{"hash":"0x0000000000000000000000000000000000000000000000000000000000000000","number":0},"sequenceNumber":0},"pending_safe_l2":{"hash":"0x0000000000000000000000000000000000000000000000000000000000000000","number":0,"parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","timestamp":0,"l1origin":{"hash":"0x0000000000000000000000000000000000000000000000000000000000000000","number":0},"sequenceNumber":0}}"#;
+        let status: SyncStatus = serde_json::from_str(json).unwrap();
+
+        let l1_head = BlockInfo::new(B256::repeat_byte(1), 100, B256::ZERO, 1200);
+        assert_eq!(status.current_l1, l1_head);
+        assert_eq!(status.head_l1, l1_head);
+        assert_eq!(
+            status.unsafe_l2,
+            L2BlockInfo::new(
+                BlockInfo::new(B256::repeat_byte(2), 500, B256::ZERO, 1202),
+                l1_head.id(),
+                1
+            )
+        );
+        assert_eq!(status.cross_unsafe_l2, L2BlockInfo::default());
+
+        let roundtrip: SyncStatus =
+            serde_json::from_str(&serde_json::to_string(&status).unwrap()).unwrap();
+        assert_eq!(roundtrip, status);
+    }
+
+    #[test]
+    fn test_serde_output_response() {
+        let output = OutputResponse {
+            version: B256::ZERO,
+            output_root: B256::repeat_byte(3),
+            block_ref: L2BlockInfo::default(),
+            withdrawal_storage_root: B256::repeat_byte(4),
+            state_root: B256::repeat_byte(5),
+            sync_status: SyncStatus::default(),
+        };
+
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["outputRoot"], serde_json::to_value(B256::repeat_byte(3)).unwrap());
+        assert_eq!(json["blockRef"]["sequenceNumber"], 0);
+        assert!(json["syncStatus"]["pending_safe_l2"].is_object());
+        assert_eq!(serde_json::from_value::<OutputResponse>(json).unwrap(), output);
+    }
+
+    #[test]
+    fn test_serde_safe_head_response() {
+        let json = r#"{"l1Block":{"hash":"0x0101010101010101010101010101010101010101010101010101010101010101","number":100},"safeHead":{"hash":"0x0202020202020202020202020202020202020202020202020202020202020202","number":500}}"#;
+        let response: SafeHeadResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.l1_block, BlockNumHash::new(100, B256::repeat_byte(1)));
+        assert_eq!(response.safe_head, BlockNumHash::new(500, B256::repeat_byte(2)));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(json).unwrap(),
+            serde_json::to_value(response).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_rollup_config() {
+        let json = r#"{
+          "genesis": {
+            "l1": {"hash": "0x438335a20d98863a4c0c97999eb2481921ccd28553eac6f913af7c12aec04108", "number": 17422590},
+            "l2": {"hash": "0xdbf6a80fef073de06add9b0d14026d6e5a86c85f6d102c36d3d8e9cf89c2afd3", "number": 105235063},
+            "l2_time": 1686068903,
+            "system_config": {
+              "batcherAddr": "0x6887246668a3b87f54deb3b94ba47a6f63f32985",
+              "overhead": "0x00000000000000000000000000000000000000000000000000000000000000bc",
+              "scalar": "0x00000000000000000000000000000000000000000000000000000000000a6fe0",
+              "gasLimit": 30000000
+            }
+          },
+          "block_time": 2,
+          "max_sequencer_drift": 600,
+          "seq_window_size": 3600,
+          "channel_timeout": 300,
+          "l1_chain_id": 1,
+          "l2_chain_id": 10,
+          "regolith_time": 0,
+          "canyon_time": 1704992401,
+          "delta_time": 1708560000,
+          "ecotone_time": 1710374401,
+          "batch_inbox_address": "0xff00000000000000000000000000000000000010",
+          "deposit_contract_address": "0xbeb5fc579115071764c7423a4f12edde41f106ed",
+          "l1_system_config_address": "0x229047fed2591dbec1ef1118d64f7af3db9eb290",
+          "protocol_versions_address": "0x8062abc286f5e7d9428a0ccb9abd71e50d93b935",
+          "unknown_field": true
+        }"#;
+        let config: RollupConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.genesis.l2.number, 105235063);
+        assert_eq!(
+            config.genesis.l1.hash,
+            b256!("0x438335a20d98863a4c0c97999eb2481921ccd28553eac6f913af7c12aec04108")
+        );
+        assert_eq!(
+            config.genesis.system_config.batcher_address,
+            address!("0x6887246668a3b87f54deb3b94ba47a6f63f32985")
+        );
+        assert_eq!(config.genesis.system_config.gas_limit, 30_000_000);
+        assert_eq!(config.l2_chain_id, 10);
+        assert!(config.hardforks.is_delta_active(1708560000));
+        assert!(!config.hardforks.is_fjord_active(u64::MAX));
+        assert_eq!(config.granite_channel_timeout, None);
+        assert_eq!(
+            config.batch_inbox_address,
+            address!("0xff00000000000000000000000000000000000010")
+        );
+
+        let roundtrip: RollupConfig =
+            serde_json::from_str(&serde_json::to_string(&config).unwrap()).unwrap();
+        assert_eq!(roundtrip, config);
+    }
+}
@@ -0,0 +1,290 @@
+//! Types for the rollup node `opp2p` RPC namespace.
+//!
+//! Reference: <https://docs.optimism.io/operators/node-operators/json-rpc>
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use alloy_primitives::{B256, ChainId};
+use core::net::IpAddr;
+use serde::{Deserialize, Serialize};
+
+/// The connection state of a peer, encoded as its libp2p discriminant.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "u8", try_from = "u8")]
+#[repr(u8)]
+pub enum Connectedness {
+    /// There is no connection to the peer.
+    #[default]
+    NotConnected = 0,
+    /// There is a connection to the peer.
+    Connected = 1,
+    /// The peer was disconnected gracefully and can be connected to again.
+    CanConnect = 2,
+    /// A connection to the peer was attempted and failed.
+    CannotConnect = 3,
+    /// There is a limited, relayed connection to the peer.
+    Limited = 4,
+}
+
+impl From<Connectedness> for u8 {
+    fn from(value: Connectedness) -> Self {
+        value as Self
+    }
+}
+
+impl TryFrom<u8> for Connectedness {
+    type Error = P2pTypeError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::NotConnected),
+            1 => Ok(Self::Connected),
+            2 => Ok(Self::CanConnect),
+            3 => Ok(Self::CannotConnect),
+            4 => Ok(Self::Limited),
+            _ => Err(P2pTypeError::UnknownConnectedness(value)),
+        }
+    }
+}
+
+/// The direction of a peer connection, encoded as its libp2p discriminant.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "u8", try_from = "u8")]
+#[repr(u8)]
+pub enum Direction {
+    /// The direction is unknown.
+    #[default]
+    Unknown = 0,
+    /// The peer connected to us.
+    Inbound = 1,
+    /// We connected to the peer.
+    Outbound = 2,
+}
+
+impl From<Direction> for u8 {
+    fn from(value: Direction) -> Self {
+        value as Self
+    }
+}
+
+impl TryFrom<u8> for Direction {
+    type Error = P2pTypeError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Unknown),
+            1 => Ok(Self::Inbound),
+            2 => Ok(Self::Outbound),
+            _ => Err(P2pTypeError::UnknownDirection(value)),
+        }
+    }
+}
+
+/// An error converting a libp2p discriminant into a [`Connectedness`] or [`Direction`].
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum P2pTypeError {
+    /// The connectedness discriminant is unknown.
+    #[error("Unknown connectedness: {0}")]
+    UnknownConnectedness(u8),
+    /// The direction discriminant is unknown.
+    #[error("Unknown direction: {0}")]
+    UnknownDirection(u8),
+}
+
+/// The gossip scores of a peer on a single topic.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicScores {
+    /// The score for the time spent in the mesh.
+    pub time_in_mesh: f64,
+    /// The score for the messages first delivered by the peer.
+    pub first_message_deliveries: f64,
+    /// The score for the messages delivered by the peer while in the mesh.
+    pub mesh_message_deliveries: f64,
+    /// The score for the invalid messages delivered by the peer.
+    pub invalid_message_deliveries: f64,
+}
+
+/// The gossip scores of a peer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GossipScores {
+    /// The total gossip score.
+    pub total: f64,
+    /// The scores on the blocks topic.
+    pub blocks: TopicScores,
+    /// The penalty for sharing an IP address with other peers.
+    #[serde(rename = "IPColocationFactor")]
+    pub ip_colocation_factor: f64,
+    /// The penalty for misbehaving.
+    pub behavioral_penalty: f64,
+}
+
+/// The request-response scores of a peer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReqRespScores {
+    /// The score for valid responses.
+    pub valid_responses: f64,
+    /// The score for error responses.
+    pub error_responses: f64,
+    /// The score for rejected payloads.
+    pub rejected_payloads: f64,
+}
+
+/// The scores of a peer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerScores {
+    /// The gossip scores.
+    pub gossip: GossipScores,
+    /// The request-response scores.
+    pub req_resp: ReqRespScores,
+}
+
+/// A peer of the rollup node, returned by `opp2p_self` and as part of a [`PeerDump`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerInfo {
+    /// The libp2p peer id.
+    #[serde(rename = "peerID")]
+    pub peer_id: String,
+    /// The discv5 node id.
+    #[serde(rename = "nodeID")]
+    pub node_id: B256,
+    /// The user agent of the peer.
+    pub user_agent: String,
+    /// The protocol version of the peer.
+    pub protocol_version: String,
+    /// The ENR of the peer, empty if the peer was not discovered by us.
+    #[serde(rename = "ENR")]
+    pub enr: String,
+    /// The multi-addresses of the peer.
+    pub addresses: Vec<String>,
+    /// The protocols negotiated with the peer.
+    pub protocols: Option<Vec<String>>,
+    /// The connection state of the peer.
+    pub connectedness: Connectedness,
+    /// The direction of the connection to the peer.
+    pub direction: Direction,
+    /// Whether the peer is protected from being pruned.
+    pub protected: bool,
+    /// The chain id of the peer, zero if unknown.
+    #[serde(rename = "chainID")]
+    pub chain_id: ChainId,
+    /// The latency to the peer, in nanoseconds.
+    pub latency: u64,
+    /// Whether the peer is subscribed to the blocks topic.
+    pub gossip_blocks: bool,
+    /// The scores of the peer.
+    #[serde(rename = "scores")]
+    pub peer_scores: PeerScores,
+}
+
+/// A subnet banned by the rollup node.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BannedSubnet {
+    /// The network address.
+    #[serde(rename = "IP")]
+    pub ip: Option<IpAddr>,
+    /// The base64-encoded network mask.
+    #[serde(rename = "Mask")]
+    pub mask: String,
+}
+
+/// The peers of the rollup node, returned by `opp2p_peers`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerDump {
+    /// The number of connected peers.
+    pub total_connected: u32,
+    /// The peers, by peer id.
+    pub peers: BTreeMap<String, PeerInfo>,
+    /// The banned peer ids.
+    #[serde(default, deserialize_with = "alloy_serde::null_as_default")]
+    pub banned_peers: Vec<String>,
+    /// The banned IP addresses.
+    #[serde(rename = "bannedIPS", default, deserialize_with = "alloy_serde::null_as_default")]
+    pub banned_ips: Vec<IpAddr>,
+    /// The banned subnets.
+    #[serde(default, deserialize_with = "alloy_serde::null_as_default")]
+    pub banned_subnets: Vec<BannedSubnet>,
+}
+
+/// The peer statistics of the rollup node, returned by `opp2p_peerStats`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerStats {
+    /// The number of connected peers.
+    pub connected: u32,
+    /// The number of peers in the discovery table.
+    pub table: u32,
+    /// The number of peers on the V1 blocks topic.
+    pub blocks_topic: u32,
+    /// The number of peers on the V2 blocks topic.
+    pub blocks_topic_v2: u32,
+    /// The number of peers on the V3 blocks topic.
+    pub blocks_topic_v3: u32,
+    /// The number of peers on the V4 blocks topic.
+    #[serde(default)]
+    pub blocks_topic_v4: u32,
+    /// The number of banned peers.
+    pub banned: u32,
+    /// The number of known peers.
+    pub known: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::net::Ipv4Addr;
+
+    #[test]
+    fn test_serde_peer_info() {
+        let json = r#"{"peerID":"16Uiu2HAmJJxNPnSdSR3BbPrS2ZNxTHpfn9nUQqTtPsqzKLBo2tWF","nodeID":"0x8e0cf4a35ee38f1a96d25e6d3bc2893b55f1f8f0b7dd0b7b1bdbf7de5c5c5c5c","userAgent":"optimism","protocolVersion":"","ENR":"enr:-J24QAYqKlgc","addresses":["/ip4/10.0.0.1/tcp/9222"],"protocols":["/meshsub/1.1.0"],"connectedness":1,"direction":2,"protected":false,"chainID":5000,"latency":1500000,"gossipBlocks":true,"scores":{"gossip":{"total":1.5,"blocks":{"timeInMesh":1,"firstMessageDeliveries":2,"meshMessageDeliveries":0,"invalidMessageDeliveries":0},"IPColocationFactor":0,"behavioralPenalty":0},"reqResp":{"validResponses":3,"errorResponses":0,"rejectedPayloads":0}}}"#;
+        let peer: PeerInfo = serde_json::from_str(json).unwrap();
+
+        assert_eq!(peer.peer_id, "16Uiu2HAmJJxNPnSdSR3BbPrS2ZNxTHpfn9nUQqTtPsqzKLBo2tWF");
+        assert_eq!(peer.connectedness, Connectedness::Connected);
+        assert_eq!(peer.direction, Direction::Outbound);
+        assert_eq!(peer.chain_id, 5000);
+        assert_eq!(peer.peer_scores.gossip.total, 1.5);
+        assert_eq!(peer.peer_scores.gossip.blocks.first_message_deliveries, 2.0);
+        assert_eq!(peer.peer_scores.req_resp.valid_responses, 3.0);
+        assert_eq!(
+            serde_json::from_str::<PeerInfo>(&serde_json::to_string(&peer).unwrap()).unwrap(),
+            peer
+        );
+    }
+
+    #[test]
+    fn test_serde_peer_dump() {
+        let json = r#"{"totalConnected":1,"peers":{"16Uiu2HAm":{"peerID":"16Uiu2HAm","nodeID":"0x0000000000000000000000000000000000000000000000000000000000000000","userAgent":"","protocolVersion":"","ENR":"","addresses":[],"protocols":null,"connectedness":2,"direction":1,"protected":true,"chainID":0,"latency":0,"gossipBlocks":false,"scores":{"gossip":{"total":0,"blocks":{"timeInMesh":0,"firstMessageDeliveries":0,"meshMessageDeliveries":0,"invalidMessageDeliveries":0},"IPColocationFactor":0,"behavioralPenalty":0},"reqResp":{"validResponses":0,"errorResponses":0,"rejectedPayloads":0}}}},"bannedPeers":null,"bannedIPS":["10.0.0.2"],"bannedSubnets":[{"IP":"192.168.0.0","Mask":"//8AAA=="}]}"#;
+        let dump: PeerDump = serde_json::from_str(json).unwrap();
+
+        assert_eq!(dump.total_connected, 1);
+        assert_eq!(dump.peers["16Uiu2HAm"].connectedness, Connectedness::CanConnect);
+        assert!(dump.peers["16Uiu2HAm"].protected);
+        assert!(dump.banned_peers.is_empty());
+        assert_eq!(dump.banned_ips, [IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2))]);
+        assert_eq!(dump.banned_subnets[0].ip, Some(IpAddr::V4(Ipv4Addr::new(192, 168, 0, 0))));
+        assert_eq!(dump.banned_subnets[0].mask, "//8AAA==");
+    }
+
+    #[test]
+    fn test_serde_peer_stats() {
+        let json = r#"{"connected":20,"table":94,"blocksTopic":20,"blocksTopicV2":20,"blocksTopicV3":20,"blocksTopicV4":0,"banned":1,"known":120}"#;
+        let stats: PeerStats = serde_json::from_str(json).unwrap();
+
+        assert_eq!(stats.connected, 20);
+        assert_eq!(stats.table, 94);
+        assert_eq!(stats.known, 120);
+        assert_eq!(serde_json::to_string(&stats).unwrap(), json);
+    }
+
+    #[test]
+    fn test_invalid_discriminants() {
+        assert!(serde_json::from_str::<Connectedness>("5").is_err());
+        assert!(serde_json::from_str::<Direction>("3").is_err());
+        assert_eq!(Connectedness::try_from(4), Ok(Connectedness::Limited));
+    }
+}
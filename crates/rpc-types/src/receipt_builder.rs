@@ -0,0 +1,451 @@
+//! Assembles the RPC receipts of a block from its consensus receipts.
+
+use crate::{L1BlockInfo, OpTransactionReceipt};
+use alloc::vec::Vec;
+use alloy_consensus::{Transaction, TxReceipt};
+use alloy_eips::Encodable2718;
+use alloy_primitives::{Address, B256, U256};
+use op_alloy_consensus::{
+    L1BlockInfoTx, OpBlock, OpHardforkSchedule, OpReceipt, OpReceiptEnvelope, OpTxEnvelope,
+};
+
+/// Builds the [`OpTransactionReceipt`]s of a block from the block, its [`OpReceipt`]s and its
+/// decoded L1 info deposit.
+///
+/// The builder derives the fields that are not part of the consensus receipts, like op-geth does
+/// when serving `eth_getBlockReceipts`:
+/// - the gas used by each transaction, from the cumulative gas used,
+/// - the effective gas price, zero for deposits,
+/// - the address of created contracts, using the deposit nonce for deposits,
+/// - the log indices, counted from the start of the block,
+/// - the L1 data fee and fee parameters of each non-deposit transaction.
+///
+/// On Mantle, the L1 data fee is paid in MNT, and is converted from ETH with the token ratio of
+/// the `GasPriceOracle` set with [`with_token_ratio`](Self::with_token_ratio).
+#[derive(Debug, Clone, Copy)]
+pub struct OpReceiptBuilder<'a> {
+    block: &'a OpBlock,
+    receipts: &'a [OpReceipt],
+    l1_info: &'a L1BlockInfoTx,
+    hardforks: &'a OpHardforkSchedule,
+    block_hash: Option<B256>,
+    senders: Option<&'a [Address]>,
+    token_ratio: Option<U256>,
+}
+
+impl<'a> OpReceiptBuilder<'a> {
+    /// Creates a new [`OpReceiptBuilder`] for the given block and its receipts.
+    ///
+    /// The hardfork schedule selects the L1 fee formula of the block.
+    pub const fn new(
+        block: &'a OpBlock,
+        receipts: &'a [OpReceipt],
+        l1_info: &'a L1BlockInfoTx,
+        hardforks: &'a OpHardforkSchedule,
+    ) -> Self {
+        Self {
+            block,
+            receipts,
+            l1_info,
+            hardforks,
+            block_hash: None,
+            senders: None,
+            token_ratio: None,
+        }
+    }
+
+    /// Sets the hash of the block, which is otherwise computed from its header.
+    pub const fn with_block_hash(mut self, block_hash: B256) -> Self {
+        self.block_hash = Some(block_hash);
+        self
+    }
+
+    /// Sets the already recovered senders of the transactions of the block.
+    ///
+    /// Without senders, the senders are recovered from the transaction signatures, which
+    /// requires the `k256` feature.
+    pub const fn with_senders(mut self, senders: &'a [Address]) -> Self {
+        self.senders = Some(senders);
+        self
+    }
+
+    /// Sets the ETH to MNT token ratio of the block.
+    ///
+    /// The L1 data fee of each transaction is multiplied by the ratio, which is also reported in
+    /// the receipts.
+    pub const fn with_token_ratio(mut self, token_ratio: U256) -> Self {
+        self.token_ratio = Some(token_ratio);
+        self
+    }
+
+    /// Builds the receipts of all the transactions of the block, in block order.
+    pub fn build(self) -> Result<Vec<OpTransactionReceipt>, OpReceiptBuilderError> {
+        let transactions = &self.block.body.transactions;
+        if transactions.len() != self.receipts.len() {
+            return Err(OpReceiptBuilderError::ReceiptCountMismatch {
+                transactions: transactions.len(),
+                receipts: self.receipts.len(),
+            });
+        }
+        if let Some(senders) = self.senders
+            && senders.len() != transactions.len()
+        {
+            return Err(OpReceiptBuilderError::SenderCountMismatch {
+                transactions: transactions.len(),
+                senders: senders.len(),
+            });
+        }
+
+        let header = &self.block.header;
+        let block_hash = self.block_hash.unwrap_or_else(|| header.hash_slow());
+        let mut cumulative_gas_used = 0;
+        let mut log_index = 0;
+        let mut receipts = Vec::with_capacity(transactions.len());
+        for (index, (tx, receipt)) in transactions.iter().zip(self.receipts).enumerate() {
+            let from = match self.senders {
+                Some(senders) => senders[index],
+                None => {
+                    Self::recover_sender(tx).ok_or(OpReceiptBuilderError::SenderRecovery(index))?
+                }
+            };
+            let deposit_nonce = match receipt {
+                OpReceipt::Deposit(receipt) => receipt.deposit_nonce,
+                _ => None,
+            };
+            let contract_address = if tx.is_create() {
+                if tx.is_deposit() { deposit_nonce } else { Some(tx.nonce()) }
+                    .map(|nonce| from.create(nonce))
+            } else {
+                None
+            };
+            let gas_used = receipt.cumulative_gas_used().saturating_sub(cumulative_gas_used);
+            cumulative_gas_used = receipt.cumulative_gas_used();
+
+            let tx_hash = tx.tx_hash();
+            let envelope = Self::receipt_envelope(receipt).map_logs(|log| {
+                let log = alloy_rpc_types_eth::Log {
+                    inner: log,
+                    block_hash: Some(block_hash),
+                    block_number: Some(header.number),
+                    block_timestamp: Some(header.timestamp),
+                    transaction_hash: Some(tx_hash),
+                    transaction_index: Some(index as u64),
+                    log_index: Some(log_index),
+                    removed: false,
+                };
+                log_index += 1;
+                log
+            });
+
+            receipts.push(OpTransactionReceipt {
+                inner: alloy_rpc_types_eth::TransactionReceipt {
+                    inner: envelope,
+                    transaction_hash: tx_hash,
+                    transaction_index: Some(index as u64),
+                    block_hash: Some(block_hash),
+                    block_number: Some(header.number),
+                    gas_used,
+                    effective_gas_price: if tx.is_deposit() {
+                        0
+                    } else {
+                        tx.effective_gas_price(header.base_fee_per_gas)
+                    },
+                    blob_gas_used: None,
+                    blob_gas_price: None,
+                    from,
+                    to: tx.to(),
+                    contract_address,
+                },
+                l1_block_info: if tx.is_deposit() {
+                    L1BlockInfo::default()
+                } else {
+                    self.l1_block_info(tx)
+                },
+            });
+        }
+        Ok(receipts)
+    }
+
+    /// Returns the L1 block info of a non-deposit transaction.
+    fn l1_block_info(&self, tx: &OpTxEnvelope) -> L1BlockInfo {
+        let info = self.l1_info;
+        let cost =
+            info.l1_data_cost(&tx.encoded_2718(), self.hardforks, self.block.header.timestamp);
+        let fee = self.token_ratio.map_or(cost.fee, |ratio| cost.fee.saturating_mul(ratio));
+        let mut l1_block_info = L1BlockInfo {
+            l1_gas_price: Some(info.base_fee.saturating_to()),
+            l1_gas_used: Some(cost.gas_used.saturating_to()),
+            l1_fee: Some(fee.saturating_to()),
+            token_ratio: self.token_ratio.map(|ratio| ratio.saturating_to()),
+            ..Default::default()
+        };
+        if info.is_ecotone() {
+            l1_block_info.l1_base_fee_scalar = info.base_fee_scalar.map(u128::from);
+            l1_block_info.l1_blob_base_fee = info.blob_base_fee.map(|fee| fee.saturating_to());
+            l1_block_info.l1_blob_base_fee_scalar = info.blob_base_fee_scalar.map(u128::from);
+            l1_block_info.operator_fee_scalar = info.operator_fee_scalar.map(u128::from);
+            l1_block_info.operator_fee_constant = info.operator_fee_constant.map(u128::from);
+            l1_block_info.da_footprint_gas_scalar = info.da_footprint_gas_scalar;
+        } else {
+            l1_block_info.l1_fee_scalar =
+                info.l1_fee_scalar.map(|scalar| scalar.saturating_to::<u64>() as f64 / 1_000_000.0);
+        }
+        l1_block_info
+    }
+
+    /// Returns the receipt envelope of a consensus receipt, with its bloom.
+    fn receipt_envelope(receipt: &OpReceipt) -> OpReceiptEnvelope {
+        match receipt.clone() {
+            OpReceipt::Legacy(receipt) => OpReceiptEnvelope::Legacy(receipt.with_bloom()),
+            OpReceipt::Eip2930(receipt) => OpReceiptEnvelope::Eip2930(receipt.with_bloom()),
+            OpReceipt::Eip1559(receipt) => OpReceiptEnvelope::Eip1559(receipt.with_bloom()),
+            OpReceipt::Eip7702(receipt) => OpReceiptEnvelope::Eip7702(receipt.with_bloom()),
+            OpReceipt::Deposit(receipt) => OpReceiptEnvelope::Deposit(receipt.with_bloom()),
+        }
+    }
+
+    /// Recovers the sender of a transaction, which is only possible for deposits without the
+    /// `k256` feature.
+    fn recover_sender(tx: &OpTxEnvelope) -> Option<Address> {
+        #[cfg(feature = "k256")]
+        {
+            alloy_consensus::transaction::SignerRecoverable::recover_signer(tx).ok()
+        }
+        #[cfg(not(feature = "k256"))]
+        {
+            tx.as_deposit().map(|deposit| deposit.from)
+        }
+    }
+}
+
+/// An error building the receipts of a block with [`OpReceiptBuilder`].
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum OpReceiptBuilderError {
+    /// The number of receipts does not match the number of transactions.
+    #[error("Block has {transactions} transactions but {receipts} receipts")]
+    ReceiptCountMismatch {
+        /// The number of transactions of the block.
+        transactions: usize,
+        /// The number of receipts.
+        receipts: usize,
+    },
+    /// The number of senders does not match the number of transactions.
+    #[error("Block has {transactions} transactions but {senders} senders")]
+    SenderCountMismatch {
+        /// The number of transactions of the block.
+        transactions: usize,
+        /// The number of senders.
+        senders: usize,
+    },
+    /// The sender of the transaction at the given index could not be recovered.
+    #[error("Failed to recover the sender of transaction {0}")]
+    SenderRecovery(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_consensus::{BlockBody, Header, Receipt, SignableTransaction, TxEip1559};
+    use alloy_primitives::{Bytes, Log, LogData, Sealable, Signature, TxKind, address, hex};
+    use op_alloy_consensus::{
+        L1_BLOCK_ADDRESS, L1_INFO_DEPOSITOR_ADDRESS, OpDepositReceipt, OpTxType, TxDeposit,
+    };
+
+    const ECOTONE_L1_INFO: [u8; 164] = hex!(
+        "440a5e20000008dd00101c1200000000000000040000000066c352bb000000000139c4f500000000000000000000000000000000000000000000000000000000c0cff1460000000000000000000000000000000000000000000000000000000000000001d4c88f4065ac9671e8b1329b90773e89b5ddff9cf8675b2b5e9c1b28320609930000000000000000000000005050f69a9786f081509234f1a7f4684b5e5b76c9"
+    );
+
+    fn log(byte: u8) -> Log {
+        Log {
+            address: Address::repeat_byte(byte),
+            data: LogData::new_unchecked(vec![B256::repeat_byte(byte)], Bytes::new()),
+        }
+    }
+
+    fn block_and_receipts() -> (OpBlock, Vec<OpReceipt>, [Address; 3]) {
+        let depositor = address!("0x1111111111111111111111111111111111111111");
+        let sender = address!("0x2222222222222222222222222222222222222222");
+        let l1_info = TxDeposit {
+            from: L1_INFO_DEPOSITOR_ADDRESS,
+            to: TxKind::Call(L1_BLOCK_ADDRESS),
+            gas_limit: 1_000_000,
+            is_system_transaction: false,
+            input: ECOTONE_L1_INFO.into(),
+            ..Default::default()
+        };
+        let deposit = TxDeposit {
+            from: depositor,
+            to: TxKind::Create,
+            gas_limit: 200_000,
+            ..Default::default()
+        };
+        let tx = TxEip1559 {
+            chain_id: 10,
+            nonce: 7,
+            gas_limit: 21_000,
+            max_fee_per_gas: 100,
+            max_priority_fee_per_gas: 2,
+            to: TxKind::Call(Address::repeat_byte(0x33)),
+            ..Default::default()
+        };
+        let transactions = vec![
+            OpTxEnvelope::Deposit(l1_info.seal_slow()),
+            OpTxEnvelope::Deposit(deposit.seal_slow()),
+            tx.into_signed(Signature::test_signature()).into(),
+        ];
+        let header = Header {
+            number: 100,
+            timestamp: 1_000,
+            base_fee_per_gas: Some(10),
+            ..Default::default()
+        };
+        let block = OpBlock::new(header, BlockBody { transactions, ..Default::default() });
+
+        let receipt =
+            |cumulative_gas_used, logs| Receipt { status: true.into(), cumulative_gas_used, logs };
+        let receipts = vec![
+            OpReceipt::Deposit(OpDepositReceipt {
+                inner: receipt(50_000, vec![]),
+                deposit_nonce: Some(4),
+                deposit_receipt_version: Some(1),
+            }),
+            OpReceipt::Deposit(OpDepositReceipt {
+                inner: receipt(150_000, vec![log(1)]),
+                deposit_nonce: Some(5),
+                deposit_receipt_version: Some(1),
+            }),
+            OpReceipt::Eip1559(receipt(171_000, vec![log(2), log(3)])),
+        ];
+        (block, receipts, [L1_INFO_DEPOSITOR_ADDRESS, depositor, sender])
+    }
+
+    #[test]
+    fn test_build_receipts() {
+        let (block, receipts, senders) = block_and_receipts();
+        let l1_info = L1BlockInfoTx::decode(&ECOTONE_L1_INFO).unwrap();
+        let hardforks = OpHardforkSchedule {
+            regolith_time: Some(0),
+            ecotone_time: Some(0),
+            ..Default::default()
+        };
+        let built = OpReceiptBuilder::new(&block, &receipts, &l1_info, &hardforks)
+            .with_senders(&senders)
+            .build()
+            .unwrap();
+        let block_hash = block.header.hash_slow();
+
+        assert_eq!(built.len(), 3);
+        for (index, receipt) in built.iter().enumerate() {
+            assert_eq!(receipt.inner.transaction_hash, block.body.transactions[index].tx_hash());
+            assert_eq!(receipt.inner.transaction_index, Some(index as u64));
+            assert_eq!(receipt.inner.block_hash, Some(block_hash));
+            assert_eq!(receipt.inner.block_number, Some(100));
+            assert_eq!(receipt.inner.from, senders[index]);
+        }
+        assert_eq!(
+            built.iter().map(|receipt| receipt.inner.gas_used).collect::<Vec<_>>(),
+            [50_000, 100_000, 21_000]
+        );
+        assert_eq!(
+            built.iter().map(|receipt| receipt.inner.effective_gas_price).collect::<Vec<_>>(),
+            [0, 0, 12]
+        );
+
+        // Deposits carry their nonce in the receipt.
+        let deposit = &built[1];
+        assert_eq!(deposit.inner.to, None);
+        assert_eq!(deposit.inner.contract_address, Some(senders[1].create(5)));
+        assert_eq!(deposit.inner.inner.tx_type(), OpTxType::Deposit);
+        assert_eq!(deposit.inner.inner.deposit_nonce(), Some(5));
+        assert_eq!(deposit.l1_block_info, L1BlockInfo::default());
+
+        // Log indices are counted from the start of the block.
+        let logs =
+            built.iter().flat_map(|receipt| receipt.inner.inner.logs().iter()).collect::<Vec<_>>();
+        assert_eq!(
+            logs.iter().map(|log| log.log_index).collect::<Vec<_>>(),
+            [Some(0), Some(1), Some(2)]
+        );
+        assert_eq!(logs[2].transaction_index, Some(2));
+        assert_eq!(logs[2].transaction_hash, Some(built[2].inner.transaction_hash));
+        assert_eq!(logs[2].block_timestamp, Some(1_000));
+        assert_eq!(logs[2].inner, log(3));
+        assert_eq!(built[2].inner.inner.logs_bloom(), &receipts[2].bloom());
+
+        let tx = &built[2];
+        assert_eq!(tx.inner.to, Some(Address::repeat_byte(0x33)));
+        assert_eq!(tx.inner.contract_address, None);
+        let cost = l1_info.l1_data_cost(
+            &block.body.transactions[2].encoded_2718(),
+            &hardforks,
+            block.header.timestamp,
+        );
+        assert_eq!(
+            tx.l1_block_info,
+            L1BlockInfo {
+                l1_gas_price: Some(0xc0cff146),
+                l1_gas_used: Some(cost.gas_used.to()),
+                l1_fee: Some(cost.fee.to()),
+                l1_base_fee_scalar: Some(2269),
+                l1_blob_base_fee: Some(1),
+                l1_blob_base_fee_scalar: Some(1_055_762),
+                ..Default::default()
+            }
+        );
+        assert!(cost.fee > U256::ZERO);
+    }
+
+    #[test]
+    fn test_build_receipts_token_ratio() {
+        let (block, receipts, senders) = block_and_receipts();
+        let l1_info = L1BlockInfoTx::decode(&ECOTONE_L1_INFO).unwrap();
+        let hardforks = OpHardforkSchedule {
+            regolith_time: Some(0),
+            ecotone_time: Some(0),
+            ..Default::default()
+        };
+        let token_ratio = U256::from(4_000);
+        let built = OpReceiptBuilder::new(&block, &receipts, &l1_info, &hardforks)
+            .with_senders(&senders)
+            .with_token_ratio(token_ratio)
+            .build()
+            .unwrap();
+
+        // Deposits pay no L1 data fee, so they carry no token ratio either.
+        assert_eq!(built[1].l1_block_info, L1BlockInfo::default());
+
+        let cost = l1_info.l1_data_cost(
+            &block.body.transactions[2].encoded_2718(),
+            &hardforks,
+            block.header.timestamp,
+        );
+        let info = &built[2].l1_block_info;
+        assert_eq!(info.l1_fee, Some((cost.fee * token_ratio).to()));
+        assert_eq!(info.l1_gas_used, Some(cost.gas_used.to()));
+        assert_eq!(info.token_ratio, Some(4_000));
+
+        // The fields are reported like in the receipts of Mantle nodes.
+        let json = serde_json::to_value(&built[2]).unwrap();
+        assert_eq!(json["tokenRatio"], "0xfa0");
+        assert_eq!(json["l1Fee"], alloc::format!("{:#x}", cost.fee * token_ratio));
+    }
+
+    #[test]
+    fn test_build_receipts_count_mismatch() {
+        let (block, receipts, senders) = block_and_receipts();
+        let l1_info = L1BlockInfoTx::decode(&ECOTONE_L1_INFO).unwrap();
+        let hardforks = OpHardforkSchedule::default();
+
+        assert_eq!(
+            OpReceiptBuilder::new(&block, &receipts[..2], &l1_info, &hardforks).build(),
+            Err(OpReceiptBuilderError::ReceiptCountMismatch { transactions: 3, receipts: 2 })
+        );
+        assert_eq!(
+            OpReceiptBuilder::new(&block, &receipts, &l1_info, &hardforks)
+                .with_senders(&senders[..1])
+                .build(),
+            Err(OpReceiptBuilderError::SenderCountMismatch { transactions: 3, senders: 1 })
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Types for the `supervisor` RPC namespace.
+//!
+//! Specs: <https://specs.optimism.io/interop/supervisor.html>
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{B256, Bytes, ChainId};
+use op_alloy_consensus::BlockInfo;
+use serde::{Deserialize, Serialize};
+
+/// The context in which an access list is checked by `supervisor_checkAccessList`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutingDescriptor {
+    /// The chain id of the executing chain.
+    #[serde(rename = "chainID", with = "alloy_serde::displayfromstr")]
+    pub chain_id: ChainId,
+    /// The timestamp of the executing block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub timestamp: u64,
+    /// The duration after `timestamp` for which the messages must stay valid, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub timeout: Option<u64>,
+}
+
+impl ExecutingDescriptor {
+    /// Creates a new [`ExecutingDescriptor`].
+    pub const fn new(chain_id: ChainId, timestamp: u64, timeout: Option<u64>) -> Self {
+        Self { chain_id, timestamp, timeout }
+    }
+}
+
+/// A block sealed by the supervisor: its id and timestamp.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BlockSeal {
+    /// The block hash.
+    pub hash: B256,
+    /// The block number.
+    pub number: u64,
+    /// The block timestamp.
+    pub timestamp: u64,
+}
+
+impl BlockSeal {
+    /// Creates a new [`BlockSeal`].
+    pub const fn new(hash: B256, number: u64, timestamp: u64) -> Self {
+        Self { hash, number, timestamp }
+    }
+
+    /// Returns the block number and hash.
+    pub const fn id(&self) -> BlockNumHash {
+        BlockNumHash { number: self.number, hash: self.hash }
+    }
+}
+
+impl From<BlockInfo> for BlockSeal {
+    fn from(block: BlockInfo) -> Self {
+        Self { hash: block.hash, number: block.number, timestamp: block.timestamp }
+    }
+}
+
+/// An L2 block id, together with the id of the L1 block it was derived from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DerivedIdPair {
+    /// The L1 block the L2 block was derived from.
+    pub source: BlockNumHash,
+    /// The derived L2 block.
+    pub derived: BlockNumHash,
+}
+
+/// The output root of a single chain in a [`SuperRootOutput`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainRootInfo {
+    /// The chain id.
+    #[serde(rename = "chainID", with = "alloy_serde::displayfromstr")]
+    pub chain_id: ChainId,
+    /// The canonical output root of the chain.
+    pub canonical: B256,
+    /// The pending output root preimage of the chain.
+    pub pending: Bytes,
+}
+
+/// The response of `supervisor_superRootAtTimestamp`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuperRootOutput {
+    /// The L1 block the cross-safe chains at `timestamp` were derived from.
+    pub cross_safe_derived_from: BlockNumHash,
+    /// The timestamp of the super root.
+    #[serde(with = "alloy_serde::quantity")]
+    pub timestamp: u64,
+    /// The super root.
+    pub super_root: B256,
+    /// The version of the super root.
+    #[serde(with = "alloy_serde::quantity")]
+    pub version: u8,
+    /// The output roots of the chains in the dependency set.
+    pub chains: Vec<ChainRootInfo>,
+}
+
+/// The sync status of a single chain in a [`SupervisorSyncStatus`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupervisorChainSyncStatus {
+    /// The local-unsafe head.
+    pub local_unsafe: BlockInfo,
+    /// The local-safe head.
+    pub local_safe: BlockNumHash,
+    /// The cross-unsafe head.
+    pub cross_unsafe: BlockNumHash,
+    /// The cross-safe head.
+    pub cross_safe: BlockNumHash,
+    /// The finalized head.
+    pub finalized: BlockNumHash,
+}
+
+/// The response of `supervisor_syncStatus`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupervisorSyncStatus {
+    /// The lowest L1 block all chains are synced to.
+    #[serde(rename = "minSyncedL1")]
+    pub min_synced_l1: BlockInfo,
+    /// The timestamp up to which all chains are cross-safe.
+    #[serde(with = "alloy_serde::quantity")]
+    pub safe_timestamp: u64,
+    /// The timestamp up to which all chains are finalized.
+    #[serde(with = "alloy_serde::quantity")]
+    pub finalized_timestamp: u64,
+    /// The sync status of each chain, by chain id.
+    pub chains: BTreeMap<ChainId, SupervisorChainSyncStatus>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    #[test]
+    fn test_serde_executing_descriptor() {
+        let descriptor = ExecutingDescriptor::new(901, 0x6553f100, None);
+        let json = r#"{"chainID":"901","timestamp":"0x6553f100"}"#;
+
+        assert_eq!(serde_json::to_string(&descriptor).unwrap(), json);
+        assert_eq!(serde_json::from_str::<ExecutingDescriptor>(json).unwrap(), descriptor);
+    }
+
+    #[test]
+    fn test_serde_super_root_output() {
+        let json = r#"{"crossSafeDerivedFrom":{"number":20,"hash":"0x0101010101010101010101010101010101010101010101010101010101010101"},"timestamp":"0x64","superRoot":"0xcbb0be0fd0ea1f5d47f1a71fb3cbd9d1e1c4ad93f8a8c1a3d9e6c2de5fae1e03","version":"0x1","chains":[{"chainID":"901","canonical":"0x0202020202020202020202020202020202020202020202020202020202020202","pending":"0x01"}]}"#;
+        let output: SuperRootOutput = serde_json::from_str(json).unwrap();
+
+        assert_eq!(output.cross_safe_derived_from.number, 20);
+        assert_eq!(
+            output.super_root,
+            b256!("0xcbb0be0fd0ea1f5d47f1a71fb3cbd9d1e1c4ad93f8a8c1a3d9e6c2de5fae1e03")
+        );
+        assert_eq!(output.version, 1);
+        assert_eq!(output.chains[0].chain_id, 901);
+        assert_eq!(serde_json::to_string(&output).unwrap(), json);
+    }
+
+    #[test]
+    fn test_serde_sync_status() {
+        let mut status = SupervisorSyncStatus {
+            min_synced_l1: BlockInfo::new(B256::repeat_byte(1), 10, B256::ZERO, 100),
+            safe_timestamp: 90,
+            finalized_timestamp: 80,
+            chains: BTreeMap::new(),
+        };
+        status.chains.insert(901, SupervisorChainSyncStatus::default());
+
+        let json = serde_json::to_string(&status).unwrap();
+        assert!(json.contains(r#""minSyncedL1":{"#));
+        assert!(json.contains(r#""chains":{"901":{"localUnsafe""#));
+        assert_eq!(serde_json::from_str::<SupervisorSyncStatus>(&json).unwrap(), status);
+    }
+}
@@ -206,6 +206,16 @@ impl<T> AsRef<T> for Transaction<T> {
     }
 }
 
+impl<T: OpTransaction> OpTransaction for Transaction<T> {
+    fn is_deposit(&self) -> bool {
+        self.inner.inner.is_deposit()
+    }
+
+    fn as_deposit(&self) -> Option<&alloy_consensus::Sealed<op_alloy_consensus::TxDeposit>> {
+        self.inner.inner.as_deposit()
+    }
+}
+
 mod tx_serde {
     //! Helper module for serializing and deserializing OP [`Transaction`].
     //!
@@ -362,4 +372,35 @@ mod tests {
         let expected = serde_json::from_str::<serde_json::Value>(rpc_tx).unwrap();
         similar_asserts::assert_eq!(deserialized, expected);
     }
+
+    #[test]
+    fn can_derive_l2_block_info_from_rpc_block() {
+        // The L1 info deposit of optimism block 0x798ad0b, see `can_deserialize_deposit`.
+        let rpc_tx = r#"{"blockHash":"0x9d86bb313ebeedf4f9f82bf8a19b426be656a365648a7c089b618771311db9f9","blockNumber":"0x798ad0b","hash":"0xbc9329afac05556497441e2b3ee4c5d4da7ca0b2a4c212c212d0739e94a24df9","transactionIndex":"0x0","type":"0x7e","nonce":"0x152ea95","input":"0x440a5e200000146b000f79c50000000000000003000000006725333f000000000141e287000000000000000000000000000000000000000000000000000000012439ee7e0000000000000000000000000000000000000000000000000000000063f363e973e96e7145ff001c81b9562cba7b6104eeb12a2bc4ab9f07c27d45cd81a986620000000000000000000000006887246668a3b87f54deb3b94ba47a6f63f32985","mint":"0x0","sourceHash":"0x04e9a69416471ead93b02f0c279ab11ca0b635db5c1726a56faf22623bafde52","r":"0x0","s":"0x0","v":"0x0","yParity":"0x0","gas":"0xf4240","from":"0xdeaddeaddeaddeaddeaddeaddeaddeaddead0001","to":"0x4200000000000000000000000000000000000015","depositReceiptVersion":"0x1","value":"0x0","gasPrice":"0x0","ethValue":"0x0","ethTxValue":"0x0"}"#;
+        let tx = serde_json::from_str::<Transaction>(rpc_tx).unwrap();
+
+        let block = alloy_rpc_types_eth::Block {
+            header: alloy_rpc_types_eth::Header {
+                hash: tx.inner.block_hash.unwrap(),
+                inner: alloy_consensus::Header {
+                    number: tx.inner.block_number.unwrap(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            transactions: alloy_rpc_types_eth::BlockTransactions::Full(vec![tx]),
+            ..Default::default()
+        };
+        let info = op_alloy_consensus::L2BlockInfo::from_rpc_block(&block).unwrap();
+        assert_eq!(info.block_info.hash, block.header.hash);
+        assert_eq!(info.block_info.number, 0x798ad0b);
+        assert_eq!(info.l1_origin.number, 0x141e287);
+        assert_eq!(
+            info.l1_origin.hash,
+            alloy_primitives::b256!(
+                "0x73e96e7145ff001c81b9562cba7b6104eeb12a2bc4ab9f07c27d45cd81a98662"
+            )
+        );
+        assert_eq!(info.seq_num, 3);
+    }
 }
@@ -6,7 +6,10 @@ use alloy_eips::eip7702::SignedAuthorization;
 use alloy_network_primitives::TransactionBuilder7702;
 use alloy_primitives::{Address, Signature, TxKind, U256};
 use alloy_rpc_types_eth::{AccessList, TransactionInput, TransactionRequest};
-use op_alloy_consensus::{OpTxEnvelope, OpTypedTransaction, TxDeposit};
+use op_alloy_consensus::{
+    OpTxEnvelope, OpTypedTransaction, TxDeposit,
+    interop::{ExecutingMessage, encode_access_list},
+};
 use serde::{Deserialize, Serialize};
 
 /// Builder for [`OpTypedTransaction`].
@@ -84,6 +87,22 @@ impl OpTransactionRequest {
         self
     }
 
+    /// Declares the given executing messages in the access list of the transaction, as required
+    /// for interop transactions.
+    ///
+    /// The `CrossL2Inbox` entries are appended to the existing access list, if any. Nothing is
+    /// added if there are no messages.
+    pub fn executing_messages<'a>(
+        mut self,
+        messages: impl IntoIterator<Item = &'a ExecutingMessage>,
+    ) -> Self {
+        let item = encode_access_list(messages);
+        if !item.storage_keys.is_empty() {
+            self.0.access_list.get_or_insert_default().0.push(item);
+        }
+        self
+    }
+
     /// Sets the input data for the transaction.
     pub fn input(mut self, input: TransactionInput) -> Self {
         self.0.input = input;
@@ -207,3 +226,26 @@ impl TransactionBuilder7702 for OpTransactionRequest {
         self.as_mut().set_authorization_list(authorization_list);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use op_alloy_consensus::interop::{CROSS_L2_INBOX_ADDRESS, Identifier};
+
+    #[test]
+    fn test_executing_messages() {
+        let message = ExecutingMessage::new(
+            B256::repeat_byte(0xaa),
+            Identifier::new(Address::repeat_byte(0x11), 1, 2, 3, U256::from(10)),
+        );
+        let request = OpTransactionRequest::default().executing_messages([&message]);
+        let access_list = request.as_ref().access_list.as_ref().unwrap();
+        assert_eq!(access_list.0.len(), 1);
+        assert_eq!(access_list.0[0].address, CROSS_L2_INBOX_ADDRESS);
+        assert_eq!(access_list.0[0].storage_keys.len(), 2);
+
+        let request = OpTransactionRequest::default().executing_messages([]);
+        assert_eq!(request.as_ref().access_list, None);
+    }
+}